@@ -1,4 +1,8 @@
+use crate::donation_bot_config::*;
 use crate::log_level_wrapper::*;
+use crate::post_stream_report_config::*;
+use crate::queue_full_policy::*;
+use crate::report_sink_config::*;
 use crate::rolling_appender_rotation::*;
 use crate::secret_string::Secret;
 use schematic::{Config, ConfigLoader};
@@ -27,6 +31,61 @@ pub struct AppConfig {
   #[setting(default = 0)]
   queries_per_minute: usize,
 
+  /// How often every third party emote list is queried again, in seconds.
+  ///
+  /// Acts as the fallback for when 7TV's EventAPI is disabled or can't be reached.
+  #[setting(default = 600)]
+  emote_list_refresh_interval: u64,
+  /// Whether to listen for 7TV emote set changes as they happen.
+  #[setting(default = true)]
+  seventv_event_api_enabled: bool,
+
+  /// How many workers parse IRC messages at once.
+  ///
+  /// Messages from the same channel are always handled by the same worker, in the order they were received.
+  #[setting(default = 4)]
+  message_worker_count: usize,
+  /// How many messages each worker can have waiting before the queue full policy is applied.
+  #[setting(default = 1000)]
+  message_queue_capacity: usize,
+  #[setting(default = "block")]
+  message_queue_full_policy: QueueFullPolicy,
+  /// Where IRC messages are written when they can't be processed right away.
+  #[setting(default = "./spool/irc_messages.spool")]
+  message_spool_path: PathBuf,
+  /// How often, in seconds, spooled messages are replayed once the database is reachable.
+  #[setting(default = 30)]
+  message_spool_replay_interval: u64,
+  /// Whether to keep a compressed copy of every raw IRC line, rotated daily.
+  #[setting(default = false)]
+  raw_archive_enabled: bool,
+  #[setting(default = "./archive")]
+  raw_archive_directory: PathBuf,
+  /// Whether to subscribe to channel point redemptions through EventSub to fill in reward titles and costs.
+  ///
+  /// Requires the access token to have the `channel:read:redemptions` scope for every tracked channel.
+  #[setting(default = false)]
+  channel_point_reward_titles_enabled: bool,
+  /// Whether to subscribe to hype train events through EventSub.
+  ///
+  /// Requires the access token to have the `channel:read:hype_train` scope for every tracked channel.
+  #[setting(default = false)]
+  hype_train_tracking_enabled: bool,
+  /// The bots that post donation alerts in each channel.
+  ///
+  /// Channels without an entry read StreamElements' alerts with the Streamlabs parser.
+  donation_bots: Vec<DonationBotConfig>,
+  /// The bits charged for gigantifying an emote.
+  ///
+  /// Power-up messages don't say how many bits were spent, so this is recorded as the amount.
+  #[setting(default = 20)]
+  gigantified_emote_bit_cost: u32,
+  /// The bits charged for sending a message with an effect.
+  ///
+  /// Power-up messages don't say how many bits were spent, so this is recorded as the amount.
+  #[setting(default = 30)]
+  message_effect_bit_cost: u32,
+
   /// Required for the main app.
   twitch_nickname: Option<String>,
   /// Required for the main app.
@@ -50,10 +109,18 @@ pub struct AppConfig {
   /// Obtained from https://pastebin.com/doc_api#1
   #[setting(env = "PASTEBIN_API_KEY")]
   pastebin_api_key: Option<Secret>,
-
-  /// Obtained from https://app.exchangerate-api.com
-  #[setting(env = "EXCHANGE_RATE_API_KEY")]
-  exchange_rate_api_key: Option<Secret>,
+  /// Where the report generator sends reports. Each sink is sent every report.
+  ///
+  /// Reports are uploaded to pastebin when there are none.
+  report_sinks: Vec<ReportSinkConfig>,
+  /// The channels that have reports generated and sent to the report sinks whenever one of their streams ends.
+  ///
+  /// Channels without an entry only get reports when the report generator is run by hand.
+  post_stream_reports: Vec<PostStreamReportConfig>,
+
+  /// The ISO 4217 code donations are converted to in reports.
+  #[setting(default = "GBP", env = "REPORTING_CURRENCY")]
+  reporting_currency: String,
 }
 
 impl AppConfig {
@@ -135,6 +202,64 @@ impl AppConfig {
     Self::get_or_set().queries_per_minute
   }
 
+  /// In seconds.
+  pub fn emote_list_refresh_interval() -> u64 {
+    Self::get_or_set().emote_list_refresh_interval
+  }
+
+  pub fn seventv_event_api_enabled() -> bool {
+    Self::get_or_set().seventv_event_api_enabled
+  }
+
+  pub fn message_worker_count() -> usize {
+    Self::get_or_set().message_worker_count
+  }
+
+  pub fn message_queue_capacity() -> usize {
+    Self::get_or_set().message_queue_capacity
+  }
+
+  pub fn message_queue_full_policy() -> QueueFullPolicy {
+    Self::get_or_set().message_queue_full_policy
+  }
+
+  pub fn message_spool_path() -> &'static PathBuf {
+    &Self::get_or_set().message_spool_path
+  }
+
+  /// In seconds.
+  pub fn message_spool_replay_interval() -> u64 {
+    Self::get_or_set().message_spool_replay_interval
+  }
+
+  pub fn raw_archive_enabled() -> bool {
+    Self::get_or_set().raw_archive_enabled
+  }
+
+  pub fn raw_archive_directory() -> &'static PathBuf {
+    &Self::get_or_set().raw_archive_directory
+  }
+
+  pub fn channel_point_reward_titles_enabled() -> bool {
+    Self::get_or_set().channel_point_reward_titles_enabled
+  }
+
+  pub fn hype_train_tracking_enabled() -> bool {
+    Self::get_or_set().hype_train_tracking_enabled
+  }
+
+  pub fn donation_bots() -> &'static [DonationBotConfig] {
+    &Self::get_or_set().donation_bots
+  }
+
+  pub fn gigantified_emote_bit_cost() -> u32 {
+    Self::get_or_set().gigantified_emote_bit_cost
+  }
+
+  pub fn message_effect_bit_cost() -> u32 {
+    Self::get_or_set().message_effect_bit_cost
+  }
+
   pub fn twitch_nickname() -> &'static str {
     Self::get_or_set().twitch_nickname.as_ref().unwrap()
   }
@@ -168,9 +293,16 @@ impl AppConfig {
     Self::get_or_set().pastebin_api_key.as_ref()
   }
 
-  /// Obtained from https://app.exchangerate-api.com
-  pub fn exchange_rate_api_key() -> Option<&'static Secret> {
-    Self::get_or_set().exchange_rate_api_key.as_ref()
+  pub fn report_sinks() -> &'static [ReportSinkConfig] {
+    &Self::get_or_set().report_sinks
+  }
+
+  pub fn post_stream_reports() -> &'static [PostStreamReportConfig] {
+    &Self::get_or_set().post_stream_reports
+  }
+
+  pub fn reporting_currency() -> &'static str {
+    &Self::get_or_set().reporting_currency
   }
 }
 
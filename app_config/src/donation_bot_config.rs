@@ -0,0 +1,30 @@
+/// A bot account that posts donation alerts in a channel's chat, and how to read its alerts.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DonationBotConfig {
+  /// The login name of the channel the bot posts in.
+  pub channel: String,
+  /// The Twitch ID of the bot account.
+  pub bot_user_id: String,
+  #[serde(default)]
+  pub parser: DonationParserKind,
+  /// The alert text with `{name}`, `{amount}` and `{message}` placeholders.
+  ///
+  /// Only used by the [`Template`](DonationParserKind::Template) parser.
+  pub template: Option<String>,
+  /// A regex with `name` and `amount` groups, and optionally `currency` and `message` groups. Takes priority over the template.
+  ///
+  /// Only used by the [`Template`](DonationParserKind::Template) parser.
+  pub pattern: Option<String>,
+  /// The ISO 4217 code used when an alert's amount has no currency. Defaults to the reporting currency.
+  pub currency: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DonationParserKind {
+  /// `<name> just tipped <amount>! <text> here's what they say: <message>`
+  #[default]
+  Streamlabs,
+  /// Reads alerts with the configured template or pattern.
+  Template,
+}
@@ -1,5 +1,9 @@
 pub mod config;
+pub mod donation_bot_config;
 pub mod log_level_wrapper;
+pub mod post_stream_report_config;
+pub mod queue_full_policy;
+pub mod report_sink_config;
 pub mod rolling_appender_rotation;
 pub mod secret_string;
 
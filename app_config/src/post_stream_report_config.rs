@@ -0,0 +1,22 @@
+/// The reports generated for a channel as soon as one of its streams goes offline.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PostStreamReportConfig {
+  /// The login name of the channel.
+  pub channel: String,
+  #[serde(default)]
+  pub report: PostStreamReportKind,
+  /// When the subathon started, as an RFC 3339 timestamp.
+  ///
+  /// Required for [`Subathon`](PostStreamReportKind::Subathon) reports.
+  pub subathon_start: Option<String>,
+  /// When the subathon ended, as an RFC 3339 timestamp. Defaults to the time the reports are generated.
+  pub subathon_end: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostStreamReportKind {
+  #[default]
+  Basic,
+  Subathon,
+}
@@ -0,0 +1,33 @@
+use std::str::FromStr;
+
+/// What to do with an incoming IRC message when its message processing queue is full.
+///
+/// Can be converted from a string. Unknown values fall back to [`Block`](QueueFullPolicy::Block).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum QueueFullPolicy {
+  /// Wait for room in the queue before reading the next message from Twitch.
+  #[default]
+  Block,
+  /// Write the message to the spool file on disk and continue.
+  Spill,
+}
+
+impl<S> From<S> for QueueFullPolicy
+where
+  S: AsRef<str>,
+{
+  fn from(queue_full_policy_value: S) -> Self {
+    match queue_full_policy_value.as_ref().to_lowercase().trim() {
+      "spill" | "disk" => Self::Spill,
+      _ => Self::Block,
+    }
+  }
+}
+
+impl FromStr for QueueFullPolicy {
+  type Err = Box<dyn std::error::Error>;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Ok(Self::from(s))
+  }
+}
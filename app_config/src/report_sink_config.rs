@@ -0,0 +1,73 @@
+use crate::secret_string::Secret;
+use std::path::PathBuf;
+
+const DEFAULT_FILE_REPORTS_DIRECTORY: &str = "file_reports";
+const DEFAULT_NAME_TEMPLATE: &str = "{stream_id}/[{date}]|{report_name}";
+const DEFAULT_S3_REGION: &str = "us-east-1";
+
+/// A destination reports are sent to by the report generator.
+///
+/// Name templates take the `{stream_id}`, `{date}` and `{report_name}` placeholders.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReportSinkConfig {
+  /// Writes each report into a file in the directory.
+  File {
+    #[serde(default = "default_file_reports_directory")]
+    directory: PathBuf,
+    #[serde(default = "default_name_template")]
+    name_template: String,
+  },
+  /// Uploads each report to a bucket of an S3-compatible object storage.
+  S3 {
+    /// The address of the storage, such as `https://s3.us-east-1.amazonaws.com` or `http://localhost:9000`.
+    endpoint: String,
+    bucket: String,
+    #[serde(default = "default_s3_region")]
+    region: String,
+    access_key_id: String,
+    secret_access_key: Secret,
+    #[serde(default = "default_name_template")]
+    key_template: String,
+  },
+  /// Posts each report to a webhook.
+  Webhook {
+    url: Secret,
+    #[serde(default)]
+    format: WebhookFormat,
+  },
+  /// Uploads each report to pastebin with the configured pastebin API key.
+  Pastebin,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+  /// Attaches the report as a file to a Discord message.
+  Discord,
+  /// Posts the report as JSON with `stream_id`, `date`, `name` and `contents` fields.
+  #[default]
+  Generic,
+}
+
+fn default_file_reports_directory() -> PathBuf {
+  PathBuf::from(DEFAULT_FILE_REPORTS_DIRECTORY)
+}
+
+fn default_name_template() -> String {
+  DEFAULT_NAME_TEMPLATE.to_string()
+}
+
+fn default_s3_region() -> String {
+  DEFAULT_S3_REGION.to_string()
+}
+
+impl ReportSinkConfig {
+  /// The file sink that `-f` writes to, which places reports in `file_reports/<stream_id>`.
+  pub fn default_file() -> Self {
+    Self::File {
+      directory: default_file_reports_directory(),
+      name_template: default_name_template(),
+    }
+  }
+}
@@ -0,0 +1,72 @@
+use crate::error::AppError;
+use entities::*;
+use entity::prelude::DateTimeUtc;
+use sea_orm::*;
+
+#[derive(Debug, serde::Serialize)]
+pub struct ChannelEmoteMembershipDto {
+  pub emote: emote::Model,
+  pub added_at: DateTimeUtc,
+  pub removed_at: Option<DateTimeUtc>,
+  /// How many times the emote was used in the channel while it was in the channel's emote set.
+  pub usage_count: i64,
+}
+
+impl ChannelEmoteMembershipDto {
+  pub async fn from_membership_list(
+    memberships: Vec<(channel_emote_membership::Model, Option<emote::Model>)>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Vec<Self>, AppError> {
+    let mut membership_dtos = vec![];
+
+    for (membership, emote) in memberships {
+      let Some(emote) = emote else {
+        tracing::error!(
+          "Failed to get the emote for a channel emote membership. Membership id: {} | Emote id: {}",
+          membership.id,
+          membership.emote_id
+        );
+
+        continue;
+      };
+      let usage_count = Self::get_usage_count(&membership, database_connection).await?;
+
+      membership_dtos.push(Self {
+        emote,
+        added_at: membership.added_at,
+        removed_at: membership.removed_at,
+        usage_count,
+      });
+    }
+
+    Ok(membership_dtos)
+  }
+
+  async fn get_usage_count(
+    membership: &channel_emote_membership::Model,
+    database_connection: &DatabaseConnection,
+  ) -> Result<i64, AppError> {
+    let mut usage_condition = Condition::all()
+      .add(emote_usage::Column::EmoteId.eq(membership.emote_id))
+      .add(stream_message::Column::ChannelId.eq(membership.channel_id))
+      .add(stream_message::Column::Timestamp.gte(membership.added_at));
+
+    if let Some(removed_at) = membership.removed_at {
+      usage_condition = usage_condition.add(stream_message::Column::Timestamp.lt(removed_at));
+    }
+
+    let usage_counts: Vec<i32> = emote_usage::Entity::find()
+      .select_only()
+      .column(emote_usage::Column::UsageCount)
+      .join(
+        JoinType::InnerJoin,
+        emote_usage::Relation::StreamMessage.def(),
+      )
+      .filter(usage_condition)
+      .into_tuple()
+      .all(database_connection)
+      .await?;
+
+    Ok(usage_counts.into_iter().map(i64::from).sum())
+  }
+}
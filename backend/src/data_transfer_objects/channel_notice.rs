@@ -0,0 +1,59 @@
+use crate::error::AppError;
+use entities::*;
+use entity::prelude::DateTimeUtc;
+use sea_orm::*;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, serde::Serialize)]
+pub struct ChannelNoticeDto {
+  pub id: i32,
+  pub timestamp: DateTimeUtc,
+  pub user: twitch_user::Model,
+  pub stream_id: Option<i32>,
+  /// The `msg-id` the notice was sent with, such as `announcement` or `viewermilestone`.
+  pub notice_type: String,
+  /// Every `msg-param-` tag sent with the notice, without the prefix.
+  pub parameters: Option<serde_json::Value>,
+  pub contents: Option<String>,
+}
+
+impl ChannelNoticeDto {
+  /// The users who sent the notices are looked up in a single query.
+  pub async fn from_channel_notice_list(
+    channel_notices: Vec<channel_notice::Model>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Vec<Self>, AppError> {
+    let user_ids: HashSet<i32> = channel_notices
+      .iter()
+      .map(|channel_notice| channel_notice.twitch_user_id)
+      .collect();
+    let users: HashMap<i32, twitch_user::Model> = twitch_user::Entity::find()
+      .filter(twitch_user::Column::Id.is_in(user_ids))
+      .all(database_connection)
+      .await?
+      .into_iter()
+      .map(|user| (user.id, user))
+      .collect();
+    let mut channel_notice_dtos = vec![];
+
+    for channel_notice in channel_notices {
+      let Some(user) = users.get(&channel_notice.twitch_user_id).cloned() else {
+        return Err(AppError::CouldNotFindUserByInternalID {
+          internal_id: channel_notice.twitch_user_id,
+        });
+      };
+
+      channel_notice_dtos.push(Self {
+        id: channel_notice.id,
+        timestamp: channel_notice.timestamp,
+        user,
+        stream_id: channel_notice.stream_id,
+        notice_type: channel_notice.notice_type,
+        parameters: channel_notice.parameters,
+        contents: channel_notice.contents,
+      });
+    }
+
+    Ok(channel_notice_dtos)
+  }
+}
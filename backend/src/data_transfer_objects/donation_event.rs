@@ -1,7 +1,7 @@
 use crate::data_transfer_objects::stream::StreamDto;
 use crate::error::AppError;
 use entities::*;
-use entity::prelude::DateTimeUtc;
+use entity::prelude::{DateTimeUtc, Decimal};
 use sea_orm::*;
 use sea_orm_active_enums::EventType;
 
@@ -9,7 +9,10 @@ use sea_orm_active_enums::EventType;
 pub struct DonationEventDto {
   pub id: i32,
   pub event_type: EventType,
-  pub amount: f32,
+  pub amount: Decimal,
+  /// The ISO 4217 code of the currency a Streamlabs donation was made in.
+  pub currency: Option<String>,
+  pub original_amount: Option<Decimal>,
   pub timestamp: DateTimeUtc,
   pub donator: Option<twitch_user::Model>,
   pub donation_receiver: twitch_user::Model,
@@ -44,6 +47,8 @@ impl DonationEventDto {
       id: donation_event.id,
       event_type: donation_event.event_type,
       amount: donation_event.amount,
+      currency: donation_event.currency,
+      original_amount: donation_event.original_amount,
       timestamp: donation_event.timestamp,
       donator,
       donation_receiver,
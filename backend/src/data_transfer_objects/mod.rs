@@ -1,3 +1,5 @@
+pub mod channel_emote_membership;
+pub mod channel_notice;
 pub mod donation_event;
 pub mod follow;
 pub mod gift_sub_recipient;
@@ -10,6 +10,8 @@ pub struct StreamMessageDto {
   pub timestamp: DateTimeUtc,
   pub contents: String,
   pub is_subscriber: bool,
+  /// When the message was removed by a moderator, either directly or by clearing chat.
+  pub deleted_at: Option<DateTimeUtc>,
   /// Contents index and emote data.
   pub emote_usage: Vec<StreamMessageEmote>,
 }
@@ -45,6 +47,7 @@ impl StreamMessageDto {
             timestamp: message.timestamp,
             contents: message_contents,
             is_subscriber: message.is_subscriber != 0,
+            deleted_at: message.deleted_at,
             emote_usage,
           }
         })
@@ -38,11 +38,17 @@ pub enum AppError {
   #[error("Failed to find a stream with the ID {}", stream_id)]
   FailedToFindStreamByID { stream_id: i32 },
 
+  #[error("Stream {} hasn't started yet", stream_id)]
+  StreamHasNotStarted { stream_id: i32 },
+
   #[error("Failed to find a donation event with the ID {}", donation_event_id)]
   FailedToFindDonationEventByID { donation_event_id: i32 },
 
   #[error("Failed to parse response {}", response)]
   FailedToParseResponse { response: String },
+
+  #[error("Invalid recap period. Year: {} Month: {:?}", year, month)]
+  InvalidRecapPeriod { year: i32, month: Option<u32> },
 }
 
 impl axum::response::IntoResponse for AppError {
@@ -69,8 +75,10 @@ impl From<AppError> for StatusCode {
       AppError::CouldNotFindUserByInternalID { .. } => StatusCode::NOT_FOUND,
       AppError::CouldNotFindUserByIdentifier { .. } => StatusCode::NOT_FOUND,
       AppError::FailedToFindStreamByID { .. } => StatusCode::NOT_FOUND,
+      AppError::StreamHasNotStarted { .. } => StatusCode::NOT_FOUND,
       AppError::FailedToFindDonationEventByID { .. } => StatusCode::NOT_FOUND,
       AppError::FailedToParseResponse { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+      AppError::InvalidRecapPeriod { .. } => StatusCode::BAD_REQUEST,
 
       AppError::ChronoError(_) => StatusCode::INTERNAL_SERVER_ERROR,
     }
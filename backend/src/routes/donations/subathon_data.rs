@@ -1,18 +1,23 @@
 use crate::{app::InterfaceConfig, error::AppError};
+use app_config::AppConfig;
 use axum::extract::State;
 use entities::{donation_event, sea_orm_active_enums::EventType, subscription_event};
+use entity_extensions::donation_sum::DonationSum;
+use entity_extensions::exchange_rate::CurrencyConverter;
+use sea_orm::prelude::Decimal;
 use sea_orm::*;
 use sqlx::types::chrono::{TimeZone, Utc};
 
-const POINTS_PER_BIT: f64 = 0.01;
-const POINTS_PER_TIER_1_SUB: f64 = 5.0;
-const POINTS_PER_TIER_2_SUB: f64 = 8.0;
-const POINTS_PER_TIER_3_SUB: f64 = 20.0;
-const POINTS_PER_DOLLAR: f64 = 1.0;
+const POINTS_PER_BIT: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+const POINTS_PER_TIER_1_SUB: Decimal = Decimal::from_parts(5, 0, 0, false, 0);
+const POINTS_PER_TIER_2_SUB: Decimal = Decimal::from_parts(8, 0, 0, false, 0);
+const POINTS_PER_TIER_3_SUB: Decimal = Decimal::from_parts(20, 0, 0, false, 0);
+/// Direct donations are counted in the reporting currency.
+const POINTS_PER_DOLLAR: Decimal = Decimal::ONE;
 
 #[derive(Debug, Default, serde::Serialize)]
 pub struct SubathonResponse {
-  total_points: f64,
+  total_points: Decimal,
 
   prime_subs: i32,
   tier_1_subs: i32,
@@ -20,7 +25,8 @@ pub struct SubathonResponse {
   tier_3_subs: i32,
 
   bits: i32,
-  direct_donations: f64,
+  /// Streamlabs donations and Hype Chats, in the reporting currency.
+  direct_donations: Decimal,
 }
 
 #[axum::debug_handler]
@@ -35,17 +41,11 @@ pub async fn get_subathon_data(
       "Failed to build datetime for subathon start.".into(),
     ));
   };
-  let mut all_donations = donation_event::Entity::find()
-    .filter(donation_event::Column::Timestamp.gte(subathon_start_result))
-    .select_only()
-    .column(donation_event::Column::EventType)
-    .column(donation_event::Column::SubscriptionTier)
-    .column_as(donation_event::Column::Amount.sum(), "sum_amount")
-    .group_by(donation_event::Column::EventType)
-    .group_by(donation_event::Column::SubscriptionTier)
-    .into_model::<DonationSum>()
-    .all(database_connection)
-    .await?;
+  let mut all_donations = DonationSum::get_sums(
+    donation_event::Column::Timestamp.gte(subathon_start_result),
+    database_connection,
+  )
+  .await?;
   let subscriptions = subscription_event::Entity::find()
     .filter(subscription_event::Column::Timestamp.gte(subathon_start_result))
     .select_only()
@@ -59,40 +59,40 @@ pub async fn get_subathon_data(
     .collect::<Vec<DonationSum>>();
   all_donations.extend(subscriptions);
 
+  let mut currency_converter = CurrencyConverter::new(AppConfig::reporting_currency());
   let mut subathon_response = SubathonResponse::default();
 
-  for DonationSum {
-    event_type,
-    sum_amount,
-    subscription_tier,
-  } in all_donations
-  {
-    let points_per_amount = match event_type {
+  for donation_sum in all_donations {
+    let sum_amount = donation_sum
+      .amount(&mut currency_converter, database_connection)
+      .await?;
+    let count = i32::try_from(sum_amount.trunc()).unwrap_or_default();
+    let points_per_amount = match donation_sum.event_type {
       EventType::Bits => {
-        subathon_response.bits += sum_amount as i32;
+        subathon_response.bits += count;
         POINTS_PER_BIT
       }
       EventType::GiftSubs => {
-        let Some(subscription_tier) = subscription_tier else {
+        let Some(subscription_tier) = donation_sum.subscription_tier else {
           tracing::error!("Missing subscription_tier for gift_sub donation event.");
           continue;
         };
 
         match subscription_tier {
           1 => {
-            subathon_response.tier_1_subs += sum_amount as i32;
+            subathon_response.tier_1_subs += count;
             POINTS_PER_TIER_1_SUB
           }
           4 => {
-            subathon_response.prime_subs += sum_amount as i32;
+            subathon_response.prime_subs += count;
             POINTS_PER_TIER_1_SUB
           }
           2 => {
-            subathon_response.tier_2_subs += sum_amount as i32;
+            subathon_response.tier_2_subs += count;
             POINTS_PER_TIER_2_SUB
           }
           3 => {
-            subathon_response.tier_3_subs += sum_amount as i32;
+            subathon_response.tier_3_subs += count;
             POINTS_PER_TIER_3_SUB
           }
           _ => {
@@ -101,7 +101,7 @@ pub async fn get_subathon_data(
           }
         }
       }
-      EventType::StreamlabsDonation => {
+      EventType::StreamlabsDonation | EventType::HypeChat => {
         subathon_response.direct_donations += sum_amount;
         POINTS_PER_DOLLAR
       }
@@ -118,13 +118,6 @@ pub async fn get_subathon_data(
   Ok(axum::Json(subathon_response))
 }
 
-#[derive(Debug, FromQueryResult)]
-pub struct DonationSum {
-  pub event_type: EventType,
-  pub sum_amount: f64,
-  pub subscription_tier: Option<i32>,
-}
-
 #[derive(Debug, FromQueryResult)]
 pub struct StrippedSubscriptionEvent {
   pub subscription_tier: i32,
@@ -134,8 +127,10 @@ impl From<StrippedSubscriptionEvent> for DonationSum {
   fn from(value: StrippedSubscriptionEvent) -> Self {
     DonationSum {
       event_type: EventType::GiftSubs,
-      sum_amount: 1.0,
+      sum_amount: Decimal::ONE,
       subscription_tier: Some(value.subscription_tier),
+      currency: None,
+      day: None,
     }
   }
 }
@@ -0,0 +1,101 @@
+use crate::app::InterfaceConfig;
+use crate::data_transfer_objects::channel_emote_membership::ChannelEmoteMembershipDto;
+use crate::error::*;
+use crate::response_models::{paginated_parameters::*, paginatied_response::*};
+use axum::extract::{Path, Query, State};
+use entities::*;
+use entity_extensions::twitch_user::*;
+use sea_orm::*;
+
+const MAX_PAGE_SIZE: u64 = 100;
+const MIN_PAGE_SIZE: u64 = 1;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct EmoteHistoryQuery {
+  emote_name: Option<String>,
+  /// Only returns emotes that are still in the channel's emote set.
+  #[serde(default)]
+  active_only: bool,
+
+  #[serde(flatten)]
+  pagination_parameters: PaginationParameters,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct EmoteHistoryResponse {
+  channel: twitch_user::Model,
+  emotes: Vec<ChannelEmoteMembershipDto>,
+}
+
+/// Lists when each third party emote was added to and removed from a channel's emote set, newest first.
+#[axum::debug_handler]
+pub async fn get_emote_history(
+  Query(query_payload): Query<EmoteHistoryQuery>,
+  State(interface_config): State<InterfaceConfig>,
+  Path(channel_name): Path<String>,
+) -> Result<axum::Json<PaginatedResponse<EmoteHistoryResponse>>, AppError> {
+  tracing::info!("Got an emote history request: {query_payload:?} For channel: {channel_name:?}");
+
+  let database_connection = interface_config.database_connection();
+  let pagination = query_payload
+    .pagination_parameters
+    .clamped_page_size(MIN_PAGE_SIZE, MAX_PAGE_SIZE);
+
+  let Some(channel) = twitch_user::Model::get_by_identifier(
+    ChannelIdentifier::Login(&channel_name),
+    database_connection,
+  )
+  .await?
+  else {
+    return Err(AppError::CouldNotFindUserByLoginName {
+      login: channel_name,
+    });
+  };
+
+  let emote_history_query = get_emote_history_query(&query_payload, &channel);
+  let paginated_emote_history =
+    emote_history_query.paginate(database_connection, pagination.page_size);
+  let emote_history = paginated_emote_history.fetch_page(pagination.page).await?;
+
+  let emote_history_dtos =
+    ChannelEmoteMembershipDto::from_membership_list(emote_history, database_connection).await?;
+  let ItemsAndPagesNumber {
+    number_of_items,
+    number_of_pages,
+  } = paginated_emote_history.num_items_and_pages().await?;
+
+  Ok(axum::Json(PaginatedResponse {
+    data: EmoteHistoryResponse {
+      channel,
+      emotes: emote_history_dtos,
+    },
+    pagination: Pagination {
+      total_items: number_of_items,
+      total_pages: number_of_pages,
+      page: pagination.page,
+      page_size: pagination.page_size,
+    },
+  }))
+}
+
+fn get_emote_history_query(
+  query_payload: &EmoteHistoryQuery,
+  channel: &twitch_user::Model,
+) -> SelectTwo<channel_emote_membership::Entity, emote::Entity> {
+  let mut emote_history_query = channel_emote_membership::Entity::find()
+    .filter(channel_emote_membership::Column::ChannelId.eq(channel.id))
+    .order_by(channel_emote_membership::Column::AddedAt, Order::Desc);
+
+  if query_payload.active_only {
+    emote_history_query =
+      emote_history_query.filter(channel_emote_membership::Column::RemovedAt.is_null());
+  }
+
+  let mut emote_history_query = emote_history_query.find_also_related(emote::Entity);
+
+  if let Some(emote_name) = &query_payload.emote_name {
+    emote_history_query = emote_history_query.filter(emote::Column::Name.contains(emote_name));
+  }
+
+  emote_history_query
+}
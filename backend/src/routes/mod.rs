@@ -1,4 +1,7 @@
 pub mod donations;
+pub mod emotes;
 pub mod helpers;
+pub mod notices;
 pub mod route_builder;
+pub mod streams;
 pub mod users;
@@ -0,0 +1,101 @@
+use crate::app::InterfaceConfig;
+use crate::data_transfer_objects::channel_notice::ChannelNoticeDto;
+use crate::error::*;
+use crate::response_models::{paginated_parameters::*, paginatied_response::*};
+use axum::extract::{Path, Query, State};
+use entities::*;
+use entity_extensions::twitch_user::*;
+use sea_orm::*;
+
+const MAX_PAGE_SIZE: u64 = 100;
+const MIN_PAGE_SIZE: u64 = 1;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ChannelNoticeQuery {
+  /// Only returns notices with this `msg-id`, such as `announcement`.
+  notice_type: Option<String>,
+  stream_id: Option<i32>,
+
+  #[serde(flatten)]
+  pagination_parameters: PaginationParameters,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct ChannelNoticeResponse {
+  channel: twitch_user::Model,
+  notices: Vec<ChannelNoticeDto>,
+}
+
+/// Lists the announcements, viewer milestones and bits badge tiers sent in a channel, newest first.
+#[axum::debug_handler]
+pub async fn get_channel_notices(
+  Query(query_payload): Query<ChannelNoticeQuery>,
+  State(interface_config): State<InterfaceConfig>,
+  Path(channel_name): Path<String>,
+) -> Result<axum::Json<PaginatedResponse<ChannelNoticeResponse>>, AppError> {
+  tracing::info!("Got a channel notice request: {query_payload:?} For channel: {channel_name:?}");
+
+  let database_connection = interface_config.database_connection();
+  let pagination = query_payload
+    .pagination_parameters
+    .clamped_page_size(MIN_PAGE_SIZE, MAX_PAGE_SIZE);
+
+  let Some(channel) = twitch_user::Model::get_by_identifier(
+    ChannelIdentifier::Login(&channel_name),
+    database_connection,
+  )
+  .await?
+  else {
+    return Err(AppError::CouldNotFindUserByLoginName {
+      login: channel_name,
+    });
+  };
+
+  let channel_notice_query = get_channel_notice_query(&query_payload, &channel);
+  let paginated_channel_notices =
+    channel_notice_query.paginate(database_connection, pagination.page_size);
+  let channel_notices = paginated_channel_notices
+    .fetch_page(pagination.page)
+    .await?;
+
+  let channel_notice_dtos =
+    ChannelNoticeDto::from_channel_notice_list(channel_notices, database_connection).await?;
+  let ItemsAndPagesNumber {
+    number_of_items,
+    number_of_pages,
+  } = paginated_channel_notices.num_items_and_pages().await?;
+
+  Ok(axum::Json(PaginatedResponse {
+    data: ChannelNoticeResponse {
+      channel,
+      notices: channel_notice_dtos,
+    },
+    pagination: Pagination {
+      total_items: number_of_items,
+      total_pages: number_of_pages,
+      page: pagination.page,
+      page_size: pagination.page_size,
+    },
+  }))
+}
+
+fn get_channel_notice_query(
+  query_payload: &ChannelNoticeQuery,
+  channel: &twitch_user::Model,
+) -> Select<channel_notice::Entity> {
+  let mut channel_notice_query = channel_notice::Entity::find()
+    .filter(channel_notice::Column::ChannelId.eq(channel.id))
+    .order_by(channel_notice::Column::Timestamp, Order::Desc);
+
+  if let Some(notice_type) = &query_payload.notice_type {
+    channel_notice_query =
+      channel_notice_query.filter(channel_notice::Column::NoticeType.eq(notice_type));
+  }
+
+  if let Some(stream_id) = query_payload.stream_id {
+    channel_notice_query =
+      channel_notice_query.filter(channel_notice::Column::StreamId.eq(stream_id));
+  }
+
+  channel_notice_query
+}
@@ -5,6 +5,9 @@ pub trait RouteBuilder {
   fn apply_all_routes(self) -> Self;
   fn apply_user_routes(self) -> Self;
   fn apply_donation_routes(self) -> Self;
+  fn apply_emote_routes(self) -> Self;
+  fn apply_notice_routes(self) -> Self;
+  fn apply_stream_routes(self) -> Self;
 }
 
 impl RouteBuilder for axum::Router<InterfaceConfig> {
@@ -12,6 +15,9 @@ impl RouteBuilder for axum::Router<InterfaceConfig> {
     self //
       .apply_user_routes()
       .apply_donation_routes()
+      .apply_emote_routes()
+      .apply_notice_routes()
+      .apply_stream_routes()
   }
 
   fn apply_user_routes(self) -> Self {
@@ -29,6 +35,10 @@ impl RouteBuilder for axum::Router<InterfaceConfig> {
         "/{channel}/users/messages",
         get(crate::routes::users::messages::get_messages),
       )
+      .route(
+        "/{channel}/users/recap",
+        get(crate::routes::users::recap::get_user_recap),
+      )
       .route(
         "/users/streams",
         get(crate::routes::users::streams::get_streams),
@@ -58,4 +68,25 @@ impl RouteBuilder for axum::Router<InterfaceConfig> {
         get(crate::routes::donations::subathon_data::get_subathon_data),
       )
   }
+
+  fn apply_emote_routes(self) -> Self {
+    self.route(
+      "/{channel}/emotes/history",
+      get(crate::routes::emotes::history::get_emote_history),
+    )
+  }
+
+  fn apply_notice_routes(self) -> Self {
+    self.route(
+      "/{channel}/notices",
+      get(crate::routes::notices::channel_notices::get_channel_notices),
+    )
+  }
+
+  fn apply_stream_routes(self) -> Self {
+    self.route(
+      "/streams/{stream_id}/timeline",
+      get(crate::routes::streams::timeline::get_stream_timeline),
+    )
+  }
 }
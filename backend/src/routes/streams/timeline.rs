@@ -0,0 +1,31 @@
+use crate::app::InterfaceConfig;
+use crate::error::*;
+use axum::extract::{Path, State};
+use entities::*;
+use entity_extensions::chat_timeline::ChatTimeline;
+use sea_orm::*;
+
+/// Returns the per-minute chat activity of a stream, along with the moments where it spiked.
+#[axum::debug_handler]
+pub async fn get_stream_timeline(
+  State(interface_config): State<InterfaceConfig>,
+  Path(stream_id): Path<i32>,
+) -> Result<axum::Json<ChatTimeline>, AppError> {
+  tracing::info!("Got a stream timeline request for stream: {stream_id:?}");
+
+  let database_connection = interface_config.database_connection();
+  let Some(stream) = stream::Entity::find_by_id(stream_id)
+    .one(database_connection)
+    .await?
+  else {
+    return Err(AppError::FailedToFindStreamByID { stream_id });
+  };
+
+  if stream.start_timestamp.is_none() {
+    return Err(AppError::StreamHasNotStarted { stream_id });
+  }
+
+  let chat_timeline = ChatTimeline::new(&stream, None, database_connection).await?;
+
+  Ok(axum::Json(chat_timeline))
+}
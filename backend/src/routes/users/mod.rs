@@ -2,4 +2,5 @@ pub mod following;
 pub mod get_users;
 pub mod messages;
 pub mod name_changes;
+pub mod recap;
 pub mod streams;
@@ -0,0 +1,71 @@
+use crate::app::InterfaceConfig;
+use crate::error::*;
+use crate::routes::helpers::get_users::GetUsers;
+use axum::extract::{Path, Query, State};
+use chrono::{Datelike, Utc};
+use entities::*;
+use entity_extensions::user_recap::UserRecap;
+use sea_orm::*;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct UserRecapQuery {
+  maybe_login: Option<String>,
+  user_id: Option<String>,
+
+  /// Defaults to the current year.
+  year: Option<i32>,
+  /// Narrows the recap down to a single month of the year.
+  month: Option<u32>,
+}
+
+/// Returns a recap of a user's activity in the channel over a year, or a month of it.
+#[axum::debug_handler]
+pub async fn get_user_recap(
+  Query(query_payload): Query<UserRecapQuery>,
+  State(interface_config): State<InterfaceConfig>,
+  Path(channel_name): Path<String>,
+) -> Result<axum::Json<UserRecap>, AppError> {
+  tracing::info!("Got a user recap request: {query_payload:?} For channel: {channel_name:?}");
+
+  let database_connection = interface_config.database_connection();
+  let year = query_payload.year.unwrap_or(Utc::now().year());
+  let Some((period_start, period_end)) = UserRecap::calendar_period(year, query_payload.month)
+  else {
+    return Err(AppError::InvalidRecapPeriod {
+      year,
+      month: query_payload.month,
+    });
+  };
+
+  let Some(user) = query_payload
+    .get_user_query()?
+    .one(database_connection)
+    .await?
+  else {
+    return Err(query_payload.get_missing_user_error());
+  };
+  let Some(channel) = twitch_user::Entity::find()
+    .filter(twitch_user::Column::LoginName.eq(&channel_name))
+    .one(database_connection)
+    .await?
+  else {
+    return Err(AppError::CouldNotFindUserByLoginName {
+      login: channel_name,
+    });
+  };
+
+  let user_recap =
+    UserRecap::new(user, channel, period_start, period_end, database_connection).await?;
+
+  Ok(axum::Json(user_recap))
+}
+
+impl GetUsers for UserRecapQuery {
+  fn get_login(&self) -> Option<&str> {
+    self.maybe_login.as_deref()
+  }
+
+  fn get_twitch_id(&self) -> Option<&str> {
+    self.user_id.as_deref()
+  }
+}
@@ -10,15 +10,27 @@ static DATABASE_CONNECTION: OnceCell<DatabaseConnection> = OnceCell::const_new()
 
 pub async fn get_database_connection() -> &'static DatabaseConnection {
   DATABASE_CONNECTION
-    .get_or_init(|| async { get_connection().await.unwrap() })
+    .get_or_init(|| async { get_connection(AppConfig::database()).await.unwrap() })
     .await
 }
 
 pub async fn get_owned_database_connection() -> DatabaseConnection {
-  get_connection().await.unwrap()
+  get_connection(AppConfig::database()).await.unwrap()
 }
 
-async fn get_connection() -> anyhow::Result<sea_orm::DatabaseConnection> {
+/// Connects to a database other than the one in the config, creating and migrating it if needed.
+pub async fn get_owned_database_connection_for(database_name: &str) -> DatabaseConnection {
+  get_connection(database_name).await.unwrap()
+}
+
+/// Connects to an existing database without creating or migrating it, for when nothing should be written.
+pub async fn get_unmigrated_database_connection_for(database_name: &str) -> DatabaseConnection {
+  Database::connect(database_connection_string(Some(database_name)))
+    .await
+    .unwrap()
+}
+
+async fn get_connection(database_name: &str) -> anyhow::Result<sea_orm::DatabaseConnection> {
   let database_connection = Database::connect(database_connection_string(None))
     .await
     .unwrap();
@@ -27,7 +39,7 @@ async fn get_connection() -> anyhow::Result<sea_orm::DatabaseConnection> {
     DbBackend::MySql => database_connection
       .execute(Statement::from_string(
         database_connection.get_database_backend(),
-        format!("CREATE DATABASE IF NOT EXISTS `{}`;", AppConfig::database()),
+        format!("CREATE DATABASE IF NOT EXISTS `{}`;", database_name),
       ))
       .await
       .unwrap(),
@@ -36,10 +48,9 @@ async fn get_connection() -> anyhow::Result<sea_orm::DatabaseConnection> {
 
   drop(database_connection);
 
-  let database_connection =
-    Database::connect(database_connection_string(Some(AppConfig::database())))
-      .await
-      .unwrap();
+  let database_connection = Database::connect(database_connection_string(Some(database_name)))
+    .await
+    .unwrap();
 
   run_migration(&database_connection).await?;
 
@@ -4,14 +4,14 @@ use super::sea_orm_active_enums::EventType;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "donation_event")]
 pub struct Model {
   #[sea_orm(primary_key)]
   pub id: i32,
   pub event_type: EventType,
-  #[sea_orm(column_type = "Float")]
-  pub amount: f32,
+  #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+  pub amount: Decimal,
   pub timestamp: DateTimeUtc,
   pub donator_twitch_user_id: Option<i32>,
   pub donation_receiver_twitch_user_id: i32,
@@ -21,12 +21,28 @@ pub struct Model {
   pub origin_id: Option<String>,
   #[sea_orm(unique)]
   pub source_id: Option<String>,
+  pub hype_train_id: Option<i32>,
+  pub currency: Option<String>,
+  #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
+  pub original_amount: Option<Decimal>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+  #[sea_orm(has_many = "super::bits_cheermote::Entity")]
+  BitsCheermote,
+  #[sea_orm(has_one = "super::bits_power_up::Entity")]
+  BitsPowerUp,
   #[sea_orm(has_many = "super::gift_sub_recipient::Entity")]
   GiftSubRecipient,
+  #[sea_orm(
+    belongs_to = "super::hype_train::Entity",
+    from = "Column::HypeTrainId",
+    to = "super::hype_train::Column::Id",
+    on_update = "NoAction",
+    on_delete = "SetNull"
+  )]
+  HypeTrain,
   #[sea_orm(
     belongs_to = "super::stream::Entity",
     from = "Column::StreamId",
@@ -61,12 +77,30 @@ pub enum Relation {
   UnknownUser,
 }
 
+impl Related<super::bits_cheermote::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::BitsCheermote.def()
+  }
+}
+
+impl Related<super::bits_power_up::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::BitsPowerUp.def()
+  }
+}
+
 impl Related<super::gift_sub_recipient::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::GiftSubRecipient.def()
   }
 }
 
+impl Related<super::hype_train::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::HypeTrain.def()
+  }
+}
+
 impl Related<super::stream::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::Stream.def()
@@ -17,10 +17,26 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+  #[sea_orm(has_many = "super::bits_power_up::Entity")]
+  BitsPowerUp,
+  #[sea_orm(has_many = "super::channel_emote_membership::Entity")]
+  ChannelEmoteMembership,
   #[sea_orm(has_many = "super::emote_usage::Entity")]
   EmoteUsage,
 }
 
+impl Related<super::bits_power_up::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::BitsPowerUp.def()
+  }
+}
+
+impl Related<super::channel_emote_membership::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::ChannelEmoteMembership.def()
+  }
+}
+
 impl Related<super::emote_usage::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::EmoteUsage.def()
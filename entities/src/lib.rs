@@ -2,11 +2,22 @@
 
 pub mod prelude;
 
+pub mod bits_cheermote;
+pub mod bits_power_up;
+pub mod channel_emote_membership;
+pub mod channel_notice;
+pub mod channel_point_redemption;
 pub mod donation_event;
 pub mod emote;
 pub mod emote_usage;
+pub mod exchange_rate;
+pub mod generated_report;
 pub mod gift_sub_recipient;
+pub mod hype_train;
+pub mod hype_train_contributor;
+pub mod message_deletion;
 pub mod raid;
+pub mod report_run;
 pub mod sea_orm_active_enums;
 pub mod stream;
 pub mod stream_message;
@@ -1,10 +1,21 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.7
 
+pub use super::bits_cheermote::Entity as BitsCheermote;
+pub use super::bits_power_up::Entity as BitsPowerUp;
+pub use super::channel_emote_membership::Entity as ChannelEmoteMembership;
+pub use super::channel_notice::Entity as ChannelNotice;
+pub use super::channel_point_redemption::Entity as ChannelPointRedemption;
 pub use super::donation_event::Entity as DonationEvent;
 pub use super::emote::Entity as Emote;
 pub use super::emote_usage::Entity as EmoteUsage;
+pub use super::exchange_rate::Entity as ExchangeRate;
+pub use super::generated_report::Entity as GeneratedReport;
 pub use super::gift_sub_recipient::Entity as GiftSubRecipient;
+pub use super::hype_train::Entity as HypeTrain;
+pub use super::hype_train_contributor::Entity as HypeTrainContributor;
+pub use super::message_deletion::Entity as MessageDeletion;
 pub use super::raid::Entity as Raid;
+pub use super::report_run::Entity as ReportRun;
 pub use super::stream::Entity as Stream;
 pub use super::stream_message::Entity as StreamMessage;
 pub use super::stream_name::Entity as StreamName;
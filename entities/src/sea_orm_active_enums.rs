@@ -3,6 +3,14 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "bits_power_up_type")]
+pub enum BitsPowerUpType {
+  #[sea_orm(string_value = "gigantified_emote")]
+  GigantifiedEmote,
+  #[sea_orm(string_value = "message_effect")]
+  MessageEffect,
+}
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "event_type")]
 pub enum EventType {
@@ -12,6 +20,8 @@ pub enum EventType {
   GiftSubs,
   #[sea_orm(string_value = "streamlabs_donation")]
   StreamlabsDonation,
+  #[sea_orm(string_value = "hype_chat")]
+  HypeChat,
 }
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "external_service")]
@@ -25,3 +35,27 @@ pub enum ExternalService {
   #[sea_orm(string_value = "franker_face_z")]
   FrankerFaceZ,
 }
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(
+  rs_type = "String",
+  db_type = "Enum",
+  enum_name = "hype_train_contribution_type"
+)]
+pub enum HypeTrainContributionType {
+  #[sea_orm(string_value = "bits")]
+  Bits,
+  #[sea_orm(string_value = "other")]
+  Other,
+  #[sea_orm(string_value = "subscription")]
+  Subscription,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "report_run_status")]
+pub enum ReportRunStatus {
+  #[sea_orm(string_value = "done")]
+  Done,
+  #[sea_orm(string_value = "failed")]
+  Failed,
+  #[sea_orm(string_value = "pending")]
+  Pending,
+}
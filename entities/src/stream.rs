@@ -17,10 +17,22 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+  #[sea_orm(has_many = "super::channel_notice::Entity")]
+  ChannelNotice,
+  #[sea_orm(has_many = "super::channel_point_redemption::Entity")]
+  ChannelPointRedemption,
   #[sea_orm(has_many = "super::donation_event::Entity")]
   DonationEvent,
+  #[sea_orm(has_many = "super::generated_report::Entity")]
+  GeneratedReport,
+  #[sea_orm(has_many = "super::hype_train::Entity")]
+  HypeTrain,
+  #[sea_orm(has_many = "super::message_deletion::Entity")]
+  MessageDeletion,
   #[sea_orm(has_many = "super::raid::Entity")]
   Raid,
+  #[sea_orm(has_one = "super::report_run::Entity")]
+  ReportRun,
   #[sea_orm(has_many = "super::stream_message::Entity")]
   StreamMessage,
   #[sea_orm(has_many = "super::stream_name::Entity")]
@@ -39,18 +51,54 @@ pub enum Relation {
   UserTimeout,
 }
 
+impl Related<super::channel_notice::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::ChannelNotice.def()
+  }
+}
+
+impl Related<super::channel_point_redemption::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::ChannelPointRedemption.def()
+  }
+}
+
 impl Related<super::donation_event::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::DonationEvent.def()
   }
 }
 
+impl Related<super::generated_report::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::GeneratedReport.def()
+  }
+}
+
+impl Related<super::hype_train::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::HypeTrain.def()
+  }
+}
+
+impl Related<super::message_deletion::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::MessageDeletion.def()
+  }
+}
+
 impl Related<super::raid::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::Raid.def()
   }
 }
 
+impl Related<super::report_run::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::ReportRun.def()
+  }
+}
+
 impl Related<super::stream_message::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::StreamMessage.def()
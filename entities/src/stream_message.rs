@@ -19,6 +19,7 @@ pub struct Model {
   pub is_subscriber: i8,
   #[sea_orm(unique)]
   pub origin_id: Option<String>,
+  pub deleted_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -16,8 +16,16 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+  #[sea_orm(has_many = "super::channel_emote_membership::Entity")]
+  ChannelEmoteMembership,
   #[sea_orm(has_many = "super::gift_sub_recipient::Entity")]
   GiftSubRecipient,
+  #[sea_orm(has_many = "super::hype_train::Entity")]
+  HypeTrain,
+  #[sea_orm(has_many = "super::hype_train_contributor::Entity")]
+  HypeTrainContributor,
+  #[sea_orm(has_many = "super::message_deletion::Entity")]
+  MessageDeletion,
   #[sea_orm(has_many = "super::stream::Entity")]
   Stream,
   #[sea_orm(has_many = "super::twitch_user_name_change::Entity")]
@@ -26,12 +34,36 @@ pub enum Relation {
   TwitchUserUnknownUserAssociation,
 }
 
+impl Related<super::channel_emote_membership::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::ChannelEmoteMembership.def()
+  }
+}
+
 impl Related<super::gift_sub_recipient::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::GiftSubRecipient.def()
   }
 }
 
+impl Related<super::hype_train::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::HypeTrain.def()
+  }
+}
+
+impl Related<super::hype_train_contributor::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::HypeTrainContributor.def()
+  }
+}
+
+impl Related<super::message_deletion::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::MessageDeletion.def()
+  }
+}
+
 impl Related<super::stream::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::Stream.def()
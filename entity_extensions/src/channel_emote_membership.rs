@@ -0,0 +1,192 @@
+use crate::errors::EntityExtensionError;
+use chrono::{DateTime, Utc};
+use entities::*;
+use sea_orm::*;
+use std::collections::HashSet;
+
+pub trait ChannelEmoteMembershipExtensions {
+  /// Compares the given emote IDs against the channel's currently active memberships.
+  ///
+  /// Emotes missing a membership are added with `added_at` set to the timestamp passed in.
+  /// Active memberships for emotes no longer in the list are closed by setting `removed_at`.
+  async fn update_channel_memberships(
+    channel_id: i32,
+    current_emote_ids: &HashSet<i32>,
+    timestamp: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), EntityExtensionError>;
+
+  /// Returns the memberships for a channel that haven't been removed.
+  async fn get_active_memberships_for_channel(
+    channel_id: i32,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Vec<channel_emote_membership::Model>, EntityExtensionError>;
+}
+
+impl ChannelEmoteMembershipExtensions for channel_emote_membership::Model {
+  async fn update_channel_memberships(
+    channel_id: i32,
+    current_emote_ids: &HashSet<i32>,
+    timestamp: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), EntityExtensionError> {
+    let active_memberships =
+      Self::get_active_memberships_for_channel(channel_id, database_connection).await?;
+    let (added_emote_ids, removed_membership_ids) =
+      membership_changes(&active_memberships, current_emote_ids);
+
+    if !removed_membership_ids.is_empty() {
+      tracing::info!(
+        "Closing {} emote memberships for channel {}.",
+        removed_membership_ids.len(),
+        channel_id
+      );
+
+      channel_emote_membership::Entity::update_many()
+        .col_expr(
+          channel_emote_membership::Column::RemovedAt,
+          sea_query::Expr::value(timestamp),
+        )
+        .filter(channel_emote_membership::Column::Id.is_in(removed_membership_ids))
+        .exec(database_connection)
+        .await?;
+    }
+
+    if !added_emote_ids.is_empty() {
+      tracing::info!(
+        "Adding {} emote memberships for channel {}.",
+        added_emote_ids.len(),
+        channel_id
+      );
+
+      let added_memberships =
+        added_emote_ids
+          .into_iter()
+          .map(|emote_id| channel_emote_membership::ActiveModel {
+            channel_id: Set(channel_id),
+            emote_id: Set(emote_id),
+            added_at: Set(timestamp),
+            removed_at: Set(None),
+            ..Default::default()
+          });
+
+      channel_emote_membership::Entity::insert_many(added_memberships)
+        .exec(database_connection)
+        .await?;
+    }
+
+    Ok(())
+  }
+
+  async fn get_active_memberships_for_channel(
+    channel_id: i32,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Vec<channel_emote_membership::Model>, EntityExtensionError> {
+    channel_emote_membership::Entity::find()
+      .filter(channel_emote_membership::Column::ChannelId.eq(channel_id))
+      .filter(channel_emote_membership::Column::RemovedAt.is_null())
+      .all(database_connection)
+      .await
+      .map_err(Into::into)
+  }
+}
+
+/// Returns the emote IDs that need a new membership, and the IDs of the memberships that need to be closed.
+///
+/// Both lists are sorted to keep the resulting queries consistent.
+fn membership_changes(
+  active_memberships: &[channel_emote_membership::Model],
+  current_emote_ids: &HashSet<i32>,
+) -> (Vec<i32>, Vec<i32>) {
+  let active_emote_ids: HashSet<i32> = active_memberships
+    .iter()
+    .map(|membership| membership.emote_id)
+    .collect();
+
+  let mut added_emote_ids: Vec<i32> = current_emote_ids
+    .difference(&active_emote_ids)
+    .copied()
+    .collect();
+  let mut removed_membership_ids: Vec<i32> = active_memberships
+    .iter()
+    .filter(|membership| !current_emote_ids.contains(&membership.emote_id))
+    .map(|membership| membership.id)
+    .collect();
+
+  added_emote_ids.sort_unstable();
+  removed_membership_ids.sort_unstable();
+
+  (added_emote_ids, removed_membership_ids)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::TimeZone;
+
+  fn membership(id: i32, emote_id: i32) -> channel_emote_membership::Model {
+    channel_emote_membership::Model {
+      id,
+      channel_id: 1,
+      emote_id,
+      added_at: Utc.with_ymd_and_hms(2025, 10, 1, 0, 0, 0).unwrap(),
+      removed_at: None,
+    }
+  }
+
+  #[test]
+  fn membership_changes_finds_added_and_removed_emotes() {
+    let active_memberships = vec![membership(10, 1), membership(11, 2), membership(12, 3)];
+    let current_emote_ids = HashSet::from([2, 3, 4, 5]);
+
+    let (added_emote_ids, removed_membership_ids) =
+      membership_changes(&active_memberships, &current_emote_ids);
+
+    assert_eq!(added_emote_ids, vec![4, 5]);
+    assert_eq!(removed_membership_ids, vec![10]);
+  }
+
+  #[test]
+  fn membership_changes_is_empty_when_nothing_changed() {
+    let active_memberships = vec![membership(10, 1), membership(11, 2)];
+    let current_emote_ids = HashSet::from([1, 2]);
+
+    let (added_emote_ids, removed_membership_ids) =
+      membership_changes(&active_memberships, &current_emote_ids);
+
+    assert!(added_emote_ids.is_empty());
+    assert!(removed_membership_ids.is_empty());
+  }
+
+  #[tokio::test]
+  async fn update_channel_memberships_closes_removed_and_inserts_added() {
+    let timestamp = Utc.with_ymd_and_hms(2025, 10, 15, 12, 0, 0).unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([vec![membership(10, 1), membership(11, 2)]])
+      .append_exec_results([
+        MockExecResult {
+          last_insert_id: 0,
+          rows_affected: 1,
+        },
+        MockExecResult {
+          last_insert_id: 12,
+          rows_affected: 1,
+        },
+      ])
+      .into_connection();
+
+    channel_emote_membership::Model::update_channel_memberships(
+      1,
+      &HashSet::from([2, 3]),
+      timestamp,
+      &mock_database,
+    )
+    .await
+    .unwrap();
+
+    let transaction_log = mock_database.into_transaction_log();
+
+    // Select active, close removed, insert added.
+    assert_eq!(transaction_log.len(), 3);
+  }
+}
@@ -0,0 +1,65 @@
+use crate::errors::EntityExtensionError;
+use entities::*;
+use sea_orm::*;
+use sea_query::Expr;
+
+pub trait ChannelPointRedemptionExtensions {
+  /// Returns the latest redemption of the reward that has its title filled in.
+  async fn get_latest_titled_redemption_for_reward(
+    channel_id: i32,
+    reward_id: &str,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Option<channel_point_redemption::Model>, EntityExtensionError>;
+  async fn fill_reward_details(
+    channel_id: i32,
+    reward_id: &str,
+    reward_title: &str,
+    reward_cost: i32,
+    database_connection: &DatabaseConnection,
+  ) -> Result<u64, EntityExtensionError>;
+}
+
+impl ChannelPointRedemptionExtensions for channel_point_redemption::Model {
+  async fn get_latest_titled_redemption_for_reward(
+    channel_id: i32,
+    reward_id: &str,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Option<channel_point_redemption::Model>, EntityExtensionError> {
+    channel_point_redemption::Entity::find()
+      .filter(channel_point_redemption::Column::ChannelId.eq(channel_id))
+      .filter(channel_point_redemption::Column::RewardId.eq(reward_id))
+      .filter(channel_point_redemption::Column::RewardTitle.is_not_null())
+      .order_by_desc(channel_point_redemption::Column::Timestamp)
+      .one(database_connection)
+      .await
+      .map_err(Into::into)
+  }
+
+  /// Sets the title and cost on every redemption of the reward that doesn't have a title yet.
+  ///
+  /// Returns the amount of redemptions that were updated.
+  async fn fill_reward_details(
+    channel_id: i32,
+    reward_id: &str,
+    reward_title: &str,
+    reward_cost: i32,
+    database_connection: &DatabaseConnection,
+  ) -> Result<u64, EntityExtensionError> {
+    let update_result = channel_point_redemption::Entity::update_many()
+      .col_expr(
+        channel_point_redemption::Column::RewardTitle,
+        Expr::value(reward_title),
+      )
+      .col_expr(
+        channel_point_redemption::Column::RewardCost,
+        Expr::value(reward_cost),
+      )
+      .filter(channel_point_redemption::Column::ChannelId.eq(channel_id))
+      .filter(channel_point_redemption::Column::RewardId.eq(reward_id))
+      .filter(channel_point_redemption::Column::RewardTitle.is_null())
+      .exec(database_connection)
+      .await?;
+
+    Ok(update_result.rows_affected)
+  }
+}
@@ -0,0 +1,352 @@
+use crate::errors::EntityExtensionError;
+use chrono::{DateTime, Utc};
+use entities::*;
+use sea_orm::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// How many minutes before a minute are averaged to decide what's normal for it.
+pub const SPIKE_BASELINE_WINDOW: usize = 10;
+/// How many times above the baseline a minute has to be to count as a spike.
+pub const SPIKE_MULTIPLIER: f64 = 3.0;
+/// The fewest messages in a minute that can be a spike.
+pub const MIN_MESSAGE_SPIKE: u64 = 20;
+/// The fewest uses of an emote in a minute that can be a spike.
+pub const MIN_EMOTE_SPIKE: u64 = 10;
+/// The fewest donation events in a minute that can be a spike.
+pub const MIN_DONATION_SPIKE: u64 = 3;
+
+/// What happened in chat each minute of a stream, and the moments where activity suddenly jumped.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ChatTimeline {
+  pub stream_id: i32,
+  pub stream_start: DateTime<Utc>,
+  pub minutes: Vec<TimelineMinute>,
+  /// The spikes in activity, in the order they happened.
+  pub moments: Vec<ChatMoment>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct TimelineMinute {
+  /// Minutes since the stream started.
+  pub minute: u64,
+  pub messages: u64,
+  pub unique_chatters: u64,
+  pub emote_uses: u64,
+  pub donation_events: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ChatMoment {
+  /// Seconds since the stream started, for jumping to the moment in the VOD.
+  pub offset_seconds: u64,
+  pub kind: ChatMomentKind,
+  /// The activity in the busiest minute of the moment.
+  pub peak: u64,
+  /// The average activity in the minutes before the moment.
+  pub baseline: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "kind", content = "emote", rename_all = "snake_case")]
+pub enum ChatMomentKind {
+  Messages,
+  Emote(String),
+  Donations,
+}
+
+/// A run of minutes that were above their baseline.
+#[derive(Debug, Clone, PartialEq)]
+struct Spike {
+  minute: usize,
+  peak: u64,
+  baseline: f64,
+}
+
+impl ChatTimeline {
+  /// Builds the timeline of a stream, up to the cutoff if one is given.
+  ///
+  /// The timeline runs until the stream ended, the cutoff, or now, whichever is first.
+  pub async fn new(
+    stream: &stream::Model,
+    cutoff: Option<DateTime<Utc>>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Self, EntityExtensionError> {
+    let Some(stream_start) = stream.start_timestamp else {
+      return Err(EntityExtensionError::FailedToGetValue {
+        value_name: "stream start time",
+        location: "chat timeline",
+        additional_data: format!("Stream ID: {}", stream.id),
+      });
+    };
+    let timeline_end = [stream.end_timestamp, cutoff]
+      .into_iter()
+      .flatten()
+      .fold(Utc::now(), DateTime::min);
+
+    let messages = stream_message::Entity::find()
+      .filter(stream_message::Column::StreamId.eq(stream.id))
+      .filter(stream_message::Column::Timestamp.between(stream_start, timeline_end))
+      .all(database_connection)
+      .await?;
+    let emotes_used = emote_usage::Entity::find()
+      .join(
+        JoinType::InnerJoin,
+        emote_usage::Relation::StreamMessage.def(),
+      )
+      .filter(stream_message::Column::StreamId.eq(stream.id))
+      .filter(stream_message::Column::Timestamp.between(stream_start, timeline_end))
+      .find_also_related(emote::Entity)
+      .all(database_connection)
+      .await?;
+    let donations = donation_event::Entity::find()
+      .filter(donation_event::Column::StreamId.eq(stream.id))
+      .filter(donation_event::Column::Timestamp.between(stream_start, timeline_end))
+      .all(database_connection)
+      .await?;
+
+    let minute_of = |timestamp: DateTime<Utc>| (timestamp - stream_start).num_minutes() as usize;
+    let minute_count = if timeline_end < stream_start {
+      0
+    } else {
+      minute_of(timeline_end) + 1
+    };
+    let message_minutes: HashMap<i32, usize> = messages
+      .iter()
+      .map(|message| (message.id, minute_of(message.timestamp)))
+      .filter(|(_, minute)| *minute < minute_count)
+      .collect();
+    let donation_minutes: Vec<usize> = donations
+      .iter()
+      .map(|donation| minute_of(donation.timestamp))
+      .filter(|minute| *minute < minute_count)
+      .collect();
+
+    let mut minutes: Vec<TimelineMinute> = (0..minute_count)
+      .map(|minute| TimelineMinute {
+        minute: minute as u64,
+        ..Default::default()
+      })
+      .collect();
+    let mut chatters: Vec<HashSet<i32>> = vec![HashSet::new(); minute_count];
+    let mut emote_series: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+
+    for message in &messages {
+      // Anything outside the stream has no minute, so it's left out.
+      let Some(&minute) = message_minutes.get(&message.id) else {
+        continue;
+      };
+
+      minutes[minute].messages += 1;
+      chatters[minute].insert(message.twitch_user_id);
+    }
+
+    for (emote_usage, emote) in emotes_used {
+      // Messages outside the stream have no minute, so their emotes are left out too.
+      let (Some(emote), Some(&minute)) =
+        (emote, message_minutes.get(&emote_usage.stream_message_id))
+      else {
+        continue;
+      };
+      let uses = emote_usage.usage_count as u64;
+
+      minutes[minute].emote_uses += uses;
+      emote_series
+        .entry(emote.name)
+        .or_insert_with(|| vec![0; minute_count])[minute] += uses;
+    }
+
+    for minute in donation_minutes {
+      minutes[minute].donation_events += 1;
+    }
+
+    for (timeline_minute, minute_chatters) in minutes.iter_mut().zip(chatters) {
+      timeline_minute.unique_chatters = minute_chatters.len() as u64;
+    }
+
+    let moments = Self::find_moments(&minutes, &emote_series);
+
+    Ok(Self {
+      stream_id: stream.id,
+      stream_start,
+      minutes,
+      moments,
+    })
+  }
+
+  fn find_moments(
+    minutes: &[TimelineMinute],
+    emote_series: &BTreeMap<String, Vec<u64>>,
+  ) -> Vec<ChatMoment> {
+    let message_series: Vec<u64> = minutes.iter().map(|minute| minute.messages).collect();
+    let donation_series: Vec<u64> = minutes
+      .iter()
+      .map(|minute| minute.donation_events)
+      .collect();
+
+    let mut moments: Vec<ChatMoment> = vec![];
+    let mut add_moments = |kind: ChatMomentKind, spikes: Vec<Spike>| {
+      moments.extend(spikes.into_iter().map(|spike| ChatMoment {
+        offset_seconds: spike.minute as u64 * 60,
+        kind: kind.clone(),
+        peak: spike.peak,
+        baseline: spike.baseline,
+      }))
+    };
+
+    add_moments(
+      ChatMomentKind::Messages,
+      detect_spikes(&message_series, MIN_MESSAGE_SPIKE),
+    );
+
+    for (emote_name, series) in emote_series {
+      add_moments(
+        ChatMomentKind::Emote(emote_name.clone()),
+        detect_spikes(series, MIN_EMOTE_SPIKE),
+      );
+    }
+
+    add_moments(
+      ChatMomentKind::Donations,
+      detect_spikes(&donation_series, MIN_DONATION_SPIKE),
+    );
+
+    moments.sort_by_key(|moment| moment.offset_seconds);
+
+    moments
+  }
+}
+
+impl ChatMoment {
+  /// The offset as `h:mm:ss`.
+  pub fn offset(&self) -> String {
+    format!(
+      "{}:{:02}:{:02}",
+      self.offset_seconds / 3600,
+      self.offset_seconds % 3600 / 60,
+      self.offset_seconds % 60
+    )
+  }
+}
+
+/// Finds the runs of minutes that were at least [`SPIKE_MULTIPLIER`] times the average of the
+/// [`SPIKE_BASELINE_WINDOW`] minutes before them, and at least the minimum given.
+///
+/// Each run is returned as one spike starting at its first minute.
+fn detect_spikes(series: &[u64], minimum: u64) -> Vec<Spike> {
+  let mut spikes: Vec<Spike> = vec![];
+  let mut current_spike: Option<Spike> = None;
+
+  for (minute, &count) in series.iter().enumerate() {
+    let window_start = minute.saturating_sub(SPIKE_BASELINE_WINDOW);
+    let window = &series[window_start..minute];
+    let baseline = window.iter().sum::<u64>() as f64 / window.len().max(1) as f64;
+    // There's nothing to compare the first minute to, so it can't be a spike.
+    let is_spiking =
+      !window.is_empty() && count >= minimum && count as f64 >= baseline * SPIKE_MULTIPLIER;
+
+    match (&mut current_spike, is_spiking) {
+      (Some(spike), true) => spike.peak = spike.peak.max(count),
+      (None, true) => {
+        current_spike = Some(Spike {
+          minute,
+          peak: count,
+          baseline,
+        })
+      }
+      (Some(_), false) => spikes.extend(current_spike.take()),
+      (None, false) => (),
+    }
+  }
+
+  spikes.extend(current_spike);
+
+  spikes
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bursts_are_detected_once_per_run() {
+    let series = [2, 3, 2, 2, 40, 55, 3, 2, 2, 2];
+
+    assert_eq!(
+      detect_spikes(&series, 20),
+      vec![Spike {
+        minute: 4,
+        peak: 55,
+        baseline: 2.25,
+      }]
+    );
+  }
+
+  #[test]
+  fn steady_activity_is_not_a_spike() {
+    let series = [30, 32, 35, 31, 33, 30, 34];
+
+    assert!(detect_spikes(&series, 20).is_empty());
+  }
+
+  #[test]
+  fn small_bursts_under_the_minimum_are_ignored() {
+    let series = [0, 0, 0, 5, 0];
+
+    assert!(detect_spikes(&series, 10).is_empty());
+  }
+
+  #[tokio::test]
+  async fn timelines_end_with_the_stream() {
+    let stream = stream::Model {
+      id: 1,
+      twitch_stream_id: 1,
+      start_timestamp: Some("2025-03-01T12:00:00Z".parse().unwrap()),
+      end_timestamp: Some("2025-03-01T12:10:00Z".parse().unwrap()),
+      twitch_user_id: 1,
+    };
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([vec![stream_message::Model {
+        id: 1,
+        is_first_message: 0,
+        timestamp: "2025-03-01T12:05:30Z".parse().unwrap(),
+        emote_only: 0,
+        contents: Some("hello".into()),
+        twitch_user_id: 2,
+        channel_id: 1,
+        stream_id: Some(1),
+        is_subscriber: 0,
+        origin_id: None,
+        deleted_at: None,
+      }]])
+      .append_query_results([Vec::<emote_usage::Model>::new()])
+      .append_query_results([Vec::<donation_event::Model>::new()])
+      .into_connection();
+
+    let chat_timeline = ChatTimeline::new(&stream, None, &mock_database)
+      .await
+      .unwrap();
+
+    assert_eq!(chat_timeline.minutes.len(), 11);
+    assert_eq!(chat_timeline.minutes[5].messages, 1);
+
+    let emote_query = &mock_database.into_transaction_log()[1];
+
+    assert!(
+      emote_query.statements()[0]
+        .sql
+        .contains("`stream_message`.`timestamp` BETWEEN")
+    );
+  }
+
+  #[test]
+  fn moment_offsets_are_formatted_for_vods() {
+    let moment = ChatMoment {
+      offset_seconds: 3 * 3600 + 5 * 60,
+      kind: ChatMomentKind::Messages,
+      peak: 40,
+      baseline: 2.0,
+    };
+
+    assert_eq!(moment.offset(), "3:05:00");
+  }
+}
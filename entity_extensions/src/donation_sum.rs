@@ -0,0 +1,151 @@
+use crate::errors::EntityExtensionError;
+use crate::exchange_rate::CurrencyConverter;
+use chrono::{NaiveDate, NaiveTime};
+use entities::donation_event;
+use entities::sea_orm_active_enums::EventType;
+use sea_orm::prelude::Decimal;
+use sea_orm::sea_query::{Alias, Expr, Func, IntoCondition, SimpleExpr};
+use sea_orm::*;
+
+/// The total of a group of donations of the same type, sub tier, currency and day.
+#[derive(Debug, FromQueryResult)]
+pub struct DonationSum {
+  pub event_type: EventType,
+  pub sum_amount: Decimal,
+  pub subscription_tier: Option<i32>,
+  /// The currency direct donations in the group were made in.
+  pub currency: Option<String>,
+  /// The day the donations in the group were made on.
+  pub day: Option<NaiveDate>,
+}
+
+impl DonationSum {
+  /// Sums the donations matching the condition.
+  ///
+  /// Donations are grouped by day so each currency can be converted with the rate for the day it was sent.
+  pub async fn get_sums<C: IntoCondition>(
+    condition: C,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Vec<Self>, EntityExtensionError> {
+    let donation_day: SimpleExpr = Func::cust(Alias::new("DATE"))
+      .arg(Expr::col(donation_event::Column::Timestamp))
+      .into();
+
+    donation_event::Entity::find()
+      .filter(condition)
+      .select_only()
+      .column(donation_event::Column::EventType)
+      .column(donation_event::Column::SubscriptionTier)
+      .column(donation_event::Column::Currency)
+      .column_as(donation_event::Column::Amount.sum(), "sum_amount")
+      .column_as(donation_day.clone(), "day")
+      .group_by(donation_event::Column::EventType)
+      .group_by(donation_event::Column::SubscriptionTier)
+      .group_by(donation_event::Column::Currency)
+      .group_by(donation_day)
+      .into_model::<Self>()
+      .all(database_connection)
+      .await
+      .map_err(Into::into)
+  }
+
+  /// The summed amount, with donations made in a currency converted with the rate for their day.
+  ///
+  /// Groups without a rate to convert them with keep their amount as it was sent, so no money is left out.
+  pub async fn amount(
+    &self,
+    currency_converter: &mut CurrencyConverter,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Decimal, EntityExtensionError> {
+    let (Some(currency), Some(day)) = (&self.currency, self.day) else {
+      return Ok(self.sum_amount);
+    };
+
+    let converted_amount = currency_converter
+      .convert(
+        self.sum_amount,
+        currency,
+        day.and_time(NaiveTime::MIN).and_utc(),
+        database_connection,
+      )
+      .await?;
+
+    Ok(converted_amount.unwrap_or_else(|| {
+      tracing::warn!(
+        "Counting {:?} donations in {currency} on {day} without converting them to {}.",
+        self.event_type,
+        currency_converter.to_currency()
+      );
+
+      self.sum_amount
+    }))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use entities::exchange_rate;
+
+  fn direct_donation_sum(currency: &str) -> DonationSum {
+    DonationSum {
+      event_type: EventType::StreamlabsDonation,
+      sum_amount: Decimal::from(10),
+      subscription_tier: None,
+      currency: Some(currency.into()),
+      day: "2025-10-15".parse().ok(),
+    }
+  }
+
+  #[tokio::test]
+  async fn direct_donations_are_converted_on_their_day() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([
+        vec![exchange_rate::Model {
+          id: 1,
+          date: "2025-10-15".parse().unwrap(),
+          from_currency: "EUR".into(),
+          to_currency: "GBP".into(),
+          rate: 0.85,
+        }],
+        vec![],
+      ])
+      .into_connection();
+    let mut currency_converter = CurrencyConverter::new("GBP");
+
+    let amount = direct_donation_sum("EUR")
+      .amount(&mut currency_converter, &mock_database)
+      .await
+      .unwrap();
+
+    assert_eq!(amount, Decimal::new(85, 1));
+  }
+
+  #[tokio::test]
+  async fn direct_donations_without_a_rate_are_counted_unconverted() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([Vec::<exchange_rate::Model>::new(), vec![], vec![], vec![]])
+      .into_connection();
+    let mut currency_converter = CurrencyConverter::new("GBP");
+
+    let amount = direct_donation_sum("EUR")
+      .amount(&mut currency_converter, &mock_database)
+      .await
+      .unwrap();
+
+    assert_eq!(amount, Decimal::from(10));
+  }
+
+  #[tokio::test]
+  async fn donations_in_the_same_currency_need_no_rate() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql).into_connection();
+    let mut currency_converter = CurrencyConverter::new("GBP");
+
+    let amount = direct_donation_sum("GBP")
+      .amount(&mut currency_converter, &mock_database)
+      .await
+      .unwrap();
+
+    assert_eq!(amount, Decimal::from(10));
+  }
+}
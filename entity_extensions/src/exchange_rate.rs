@@ -0,0 +1,319 @@
+use crate::errors::EntityExtensionError;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use entities::*;
+use sea_orm::prelude::Decimal;
+use sea_orm::*;
+use sea_query::OnConflict;
+use std::collections::HashMap;
+
+/// How far from a date a stored rate can be and still be used for it.
+///
+/// Rates are imported daily, so anything further than this means the imports have stopped.
+pub const MAX_EXCHANGE_RATE_AGE_DAYS: u64 = 7;
+
+pub trait ExchangeRateExtensions {
+  /// Returns the rate from one currency to another on the date closest to the timestamp.
+  ///
+  /// Stored rates for the opposite direction are inverted if there's none for the given direction.
+  /// Returns None if neither direction has a rate within [`MAX_EXCHANGE_RATE_AGE_DAYS`] of the date.
+  async fn get_closest_rate(
+    from_currency: &str,
+    to_currency: &str,
+    timestamp: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Option<f64>, EntityExtensionError>;
+  async fn insert_or_update_many(
+    exchange_rate_active_models: Vec<exchange_rate::ActiveModel>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), EntityExtensionError>;
+}
+
+impl ExchangeRateExtensions for exchange_rate::Model {
+  async fn get_closest_rate(
+    from_currency: &str,
+    to_currency: &str,
+    timestamp: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Option<f64>, EntityExtensionError> {
+    let date = timestamp.date_naive();
+
+    if let Some(exchange_rate) =
+      get_closest_stored_rate(from_currency, to_currency, date, database_connection).await?
+    {
+      return Ok(Some(exchange_rate.rate));
+    }
+
+    let inverse_exchange_rate =
+      get_closest_stored_rate(to_currency, from_currency, date, database_connection)
+        .await?
+        .filter(|exchange_rate| exchange_rate.rate != 0.0)
+        .map(|exchange_rate| 1.0 / exchange_rate.rate);
+
+    if inverse_exchange_rate.is_none() {
+      tracing::warn!(
+        "No exchange rate from {from_currency} to {to_currency} within {MAX_EXCHANGE_RATE_AGE_DAYS} days of {date}."
+      );
+    }
+
+    Ok(inverse_exchange_rate)
+  }
+
+  /// Inserts the rates, replacing the rate of any that were already stored for the same date and currencies.
+  async fn insert_or_update_many(
+    exchange_rate_active_models: Vec<exchange_rate::ActiveModel>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), EntityExtensionError> {
+    if exchange_rate_active_models.is_empty() {
+      return Ok(());
+    }
+
+    exchange_rate::Entity::insert_many(exchange_rate_active_models)
+      .on_conflict(
+        OnConflict::columns([
+          exchange_rate::Column::Date,
+          exchange_rate::Column::FromCurrency,
+          exchange_rate::Column::ToCurrency,
+        ])
+        .update_column(exchange_rate::Column::Rate)
+        .to_owned(),
+      )
+      .exec(database_connection)
+      .await?;
+
+    Ok(())
+  }
+}
+
+/// Converts amounts into a single currency, looking up the rate for each currency and day only once.
+#[derive(Debug)]
+pub struct CurrencyConverter {
+  to_currency: String,
+  exchange_rates: HashMap<(String, NaiveDate), Option<Decimal>>,
+}
+
+impl CurrencyConverter {
+  pub fn new(to_currency: &str) -> Self {
+    Self {
+      to_currency: to_currency.to_uppercase(),
+      exchange_rates: HashMap::new(),
+    }
+  }
+
+  pub fn to_currency(&self) -> &str {
+    &self.to_currency
+  }
+
+  /// Returns None if there's no rate close enough to the timestamp to use.
+  pub async fn convert(
+    &mut self,
+    amount: Decimal,
+    from_currency: &str,
+    timestamp: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Option<Decimal>, EntityExtensionError> {
+    let from_currency = from_currency.to_uppercase();
+
+    if from_currency == self.to_currency {
+      return Ok(Some(amount));
+    }
+
+    let rate_key = (from_currency, timestamp.date_naive());
+
+    let exchange_rate = match self.exchange_rates.get(&rate_key) {
+      Some(exchange_rate) => *exchange_rate,
+      None => {
+        let exchange_rate = exchange_rate::Model::get_closest_rate(
+          &rate_key.0,
+          &self.to_currency,
+          timestamp,
+          database_connection,
+        )
+        .await?
+        .and_then(|exchange_rate| Decimal::try_from(exchange_rate).ok());
+
+        self.exchange_rates.insert(rate_key, exchange_rate);
+
+        exchange_rate
+      }
+    };
+
+    Ok(exchange_rate.map(|exchange_rate| (amount * exchange_rate).round_dp(4)))
+  }
+
+  /// Converts the donation's amount from its currency.
+  ///
+  /// Donations without a currency, like bits and gift subs, are returned as they were stored.
+  pub async fn convert_donation(
+    &mut self,
+    donation: &donation_event::Model,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Option<Decimal>, EntityExtensionError> {
+    let Some(currency) = &donation.currency else {
+      return Ok(Some(donation.amount));
+    };
+
+    self
+      .convert(
+        donation.amount,
+        currency,
+        donation.timestamp,
+        database_connection,
+      )
+      .await
+  }
+}
+
+/// Returns whichever of the nearest rates on or before, and after the date is closer to it.
+///
+/// Rates more than [`MAX_EXCHANGE_RATE_AGE_DAYS`] away from the date are ignored.
+async fn get_closest_stored_rate(
+  from_currency: &str,
+  to_currency: &str,
+  date: NaiveDate,
+  database_connection: &DatabaseConnection,
+) -> Result<Option<exchange_rate::Model>, EntityExtensionError> {
+  let currency_pair = Condition::all()
+    .add(exchange_rate::Column::FromCurrency.eq(from_currency.to_uppercase()))
+    .add(exchange_rate::Column::ToCurrency.eq(to_currency.to_uppercase()));
+
+  let max_age = Days::new(MAX_EXCHANGE_RATE_AGE_DAYS);
+  let oldest_date = date.checked_sub_days(max_age).unwrap_or(NaiveDate::MIN);
+  let newest_date = date.checked_add_days(max_age).unwrap_or(NaiveDate::MAX);
+
+  let rate_on_or_before = exchange_rate::Entity::find()
+    .filter(currency_pair.clone())
+    .filter(exchange_rate::Column::Date.between(oldest_date, date))
+    .order_by_desc(exchange_rate::Column::Date)
+    .one(database_connection)
+    .await?;
+  let rate_after = exchange_rate::Entity::find()
+    .filter(currency_pair)
+    .filter(exchange_rate::Column::Date.gt(date))
+    .filter(exchange_rate::Column::Date.lte(newest_date))
+    .order_by_asc(exchange_rate::Column::Date)
+    .one(database_connection)
+    .await?;
+
+  Ok(match (rate_on_or_before, rate_after) {
+    (Some(rate_on_or_before), Some(rate_after)) => {
+      if date - rate_on_or_before.date <= rate_after.date - date {
+        Some(rate_on_or_before)
+      } else {
+        Some(rate_after)
+      }
+    }
+    (rate_on_or_before, rate_after) => rate_on_or_before.or(rate_after),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn exchange_rate(date: &str, rate: f64) -> exchange_rate::Model {
+    exchange_rate::Model {
+      id: 1,
+      date: date.parse().unwrap(),
+      from_currency: "USD".into(),
+      to_currency: "GBP".into(),
+      rate,
+    }
+  }
+
+  #[tokio::test]
+  async fn rates_are_looked_up_once_per_currency_and_day() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([
+        vec![exchange_rate("2025-05-08", 0.5)],
+        Vec::<exchange_rate::Model>::new(),
+      ])
+      .into_connection();
+    let mut currency_converter = CurrencyConverter::new("gbp");
+    let timestamp = "2025-05-08T12:00:00Z".parse().unwrap();
+
+    let first_conversion = currency_converter
+      .convert(Decimal::from(10), "usd", timestamp, &mock_database)
+      .await
+      .unwrap();
+    let second_conversion = currency_converter
+      .convert(Decimal::from(4), "USD", timestamp, &mock_database)
+      .await
+      .unwrap();
+
+    assert_eq!(first_conversion, Some(Decimal::from(5)));
+    assert_eq!(second_conversion, Some(Decimal::from(2)));
+    assert_eq!(mock_database.into_transaction_log().len(), 2);
+  }
+
+  #[tokio::test]
+  async fn amounts_already_in_the_currency_are_not_looked_up() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql).into_connection();
+    let mut currency_converter = CurrencyConverter::new("GBP");
+
+    let conversion = currency_converter
+      .convert(
+        Decimal::from(10),
+        "gbp",
+        "2025-05-08T12:00:00Z".parse().unwrap(),
+        &mock_database,
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(conversion, Some(Decimal::from(10)));
+  }
+
+  #[tokio::test]
+  async fn donations_without_a_currency_keep_their_amount() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql).into_connection();
+    let mut currency_converter = CurrencyConverter::new("GBP");
+    let donation = donation_event::Model {
+      id: 1,
+      event_type: sea_orm_active_enums::EventType::StreamlabsDonation,
+      amount: Decimal::from(10),
+      timestamp: "2025-05-08T12:00:00Z".parse().unwrap(),
+      donator_twitch_user_id: Some(3),
+      donation_receiver_twitch_user_id: 1,
+      stream_id: None,
+      subscription_tier: None,
+      unknown_user_id: None,
+      origin_id: None,
+      source_id: None,
+      hype_train_id: None,
+      currency: None,
+      original_amount: None,
+    };
+
+    let conversion = currency_converter
+      .convert_donation(&donation, &mock_database)
+      .await
+      .unwrap();
+
+    assert_eq!(conversion, Some(Decimal::from(10)));
+  }
+
+  #[tokio::test]
+  async fn currencies_without_a_recent_rate_are_not_converted() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([
+        Vec::<exchange_rate::Model>::new(),
+        Vec::<exchange_rate::Model>::new(),
+        Vec::<exchange_rate::Model>::new(),
+        Vec::<exchange_rate::Model>::new(),
+      ])
+      .into_connection();
+    let mut currency_converter = CurrencyConverter::new("GBP");
+
+    let conversion = currency_converter
+      .convert(
+        Decimal::from(10),
+        "USD",
+        "2025-05-08T12:00:00Z".parse().unwrap(),
+        &mock_database,
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(conversion, None);
+  }
+}
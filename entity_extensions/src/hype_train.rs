@@ -0,0 +1,145 @@
+use crate::errors::EntityExtensionError;
+use chrono::{DateTime, TimeDelta, Utc};
+use entities::*;
+use sea_orm::*;
+use sea_query::Expr;
+
+/// How long a hype train without an end or expiry time is assumed to have run for.
+///
+/// Twitch only sends the end time with the `end` event, so this stops a train whose end was missed
+/// from picking up every donation after it.
+const MAX_HYPE_TRAIN_MINUTES: i64 = 60;
+
+pub trait HypeTrainExtensions {
+  async fn get_by_twitch_hype_train_id(
+    twitch_hype_train_id: &str,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Option<hype_train::Model>, EntityExtensionError>;
+  async fn replace_top_contributors(
+    &self,
+    contributor_active_models: Vec<hype_train_contributor::ActiveModel>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), EntityExtensionError>;
+  async fn link_donation_events(
+    &self,
+    expires_at: Option<&DateTime<Utc>>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<u64, EntityExtensionError>;
+}
+
+impl HypeTrainExtensions for hype_train::Model {
+  async fn get_by_twitch_hype_train_id(
+    twitch_hype_train_id: &str,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Option<hype_train::Model>, EntityExtensionError> {
+    hype_train::Entity::find()
+      .filter(hype_train::Column::TwitchHypeTrainId.eq(twitch_hype_train_id))
+      .one(database_connection)
+      .await
+      .map_err(Into::into)
+  }
+
+  /// Twitch sends the full list of top contributors with every update, so the stored list is swapped out as a whole.
+  async fn replace_top_contributors(
+    &self,
+    contributor_active_models: Vec<hype_train_contributor::ActiveModel>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), EntityExtensionError> {
+    let transaction = database_connection.begin().await?;
+
+    hype_train_contributor::Entity::delete_many()
+      .filter(hype_train_contributor::Column::HypeTrainId.eq(self.id))
+      .exec(&transaction)
+      .await?;
+
+    if !contributor_active_models.is_empty() {
+      hype_train_contributor::Entity::insert_many(contributor_active_models)
+        .exec(&transaction)
+        .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(())
+  }
+
+  /// Links every donation the channel received during the hype train.
+  ///
+  /// If the train hasn't ended yet, donations are only linked up to when it expires, or
+  /// [`MAX_HYPE_TRAIN_MINUTES`] after it started if that isn't known.
+  ///
+  /// Returns the amount of donations that were linked.
+  async fn link_donation_events(
+    &self,
+    expires_at: Option<&DateTime<Utc>>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<u64, EntityExtensionError> {
+    let update_result = donation_event::Entity::update_many()
+      .col_expr(donation_event::Column::HypeTrainId, Expr::value(self.id))
+      .filter(donation_event::Column::DonationReceiverTwitchUserId.eq(self.channel_id))
+      .filter(donation_event::Column::Timestamp.gte(self.start_timestamp))
+      .filter(donation_event::Column::Timestamp.lte(linked_until(self, expires_at)))
+      .filter(donation_event::Column::HypeTrainId.is_null())
+      .exec(database_connection)
+      .await?;
+
+    Ok(update_result.rows_affected)
+  }
+}
+
+/// The latest a donation can be made and still count towards the hype train.
+fn linked_until(
+  hype_train: &hype_train::Model,
+  expires_at: Option<&DateTime<Utc>>,
+) -> DateTime<Utc> {
+  hype_train
+    .end_timestamp
+    .or(expires_at.copied())
+    .unwrap_or(hype_train.start_timestamp + TimeDelta::minutes(MAX_HYPE_TRAIN_MINUTES))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn hype_train(end_timestamp: Option<DateTime<Utc>>) -> hype_train::Model {
+    hype_train::Model {
+      id: 1,
+      twitch_hype_train_id: "1b0AsbInCHZW2SQFQkCzqN07Ib2".into(),
+      channel_id: 1,
+      stream_id: None,
+      level: 2,
+      total: 700,
+      start_timestamp: "2025-05-08T00:02:29Z".parse().unwrap(),
+      end_timestamp,
+    }
+  }
+
+  #[test]
+  fn ended_hype_trains_link_up_to_their_end() {
+    let ended_at = "2025-05-08T00:20:29Z".parse().unwrap();
+    let expires_at = "2025-05-08T00:25:29Z".parse().unwrap();
+
+    assert_eq!(
+      linked_until(&hype_train(Some(ended_at)), Some(&expires_at)),
+      ended_at
+    );
+  }
+
+  #[test]
+  fn unfinished_hype_trains_link_up_to_their_expiry() {
+    let expires_at = "2025-05-08T00:15:29Z".parse().unwrap();
+
+    assert_eq!(
+      linked_until(&hype_train(None), Some(&expires_at)),
+      expires_at
+    );
+  }
+
+  #[test]
+  fn unfinished_hype_trains_without_an_expiry_are_capped() {
+    let expected_cutoff: DateTime<Utc> = "2025-05-08T01:02:29Z".parse().unwrap();
+
+    assert_eq!(linked_until(&hype_train(None), None), expected_cutoff);
+  }
+}
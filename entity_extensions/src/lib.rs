@@ -3,12 +3,19 @@
 
 pub mod prelude;
 
+pub mod channel_emote_membership;
+pub mod channel_point_redemption;
+pub mod chat_timeline;
 pub mod donation_event;
+pub mod donation_sum;
 pub mod emote;
 pub mod errors;
+pub mod exchange_rate;
 pub mod external_service;
+pub mod hype_train;
 pub mod stream;
 pub mod stream_message;
 pub mod twitch_user;
 pub mod twitch_user_unknown_user_association;
 pub mod unknown_user;
+pub mod user_recap;
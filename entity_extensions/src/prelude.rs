@@ -1,4 +1,8 @@
+pub use crate::channel_emote_membership::ChannelEmoteMembershipExtensions;
+pub use crate::channel_point_redemption::ChannelPointRedemptionExtensions;
 pub use crate::emote::EmoteExtensions;
+pub use crate::exchange_rate::ExchangeRateExtensions;
+pub use crate::hype_train::HypeTrainExtensions;
 pub use crate::stream::StreamExtensions;
 pub use crate::twitch_user::TwitchUserExtensions;
 pub use crate::twitch_user_unknown_user_association::TwitchUserUnkownUserAssociationExtensions;
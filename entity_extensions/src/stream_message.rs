@@ -1,13 +1,24 @@
 use crate::errors::EntityExtensionError;
+use chrono::{DateTime, Utc};
 use entities::*;
 use sea_orm::*;
-use sea_query::OnConflict;
+use sea_query::{Expr, OnConflict};
 
 pub trait StreamMessageExtensions {
   async fn insert_many_emote_usages(
     emote_usage_active_models: Vec<emote_usage::ActiveModel>,
     database_connection: &DatabaseConnection,
   ) -> Result<(), EntityExtensionError>;
+  async fn mark_deleted_by_origin_id(
+    origin_id: &str,
+    deleted_at: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<u64, EntityExtensionError>;
+  async fn mark_stream_messages_deleted(
+    stream_id: i32,
+    deleted_at: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<u64, EntityExtensionError>;
 }
 
 impl StreamMessageExtensions for stream_message::Model {
@@ -32,4 +43,41 @@ impl StreamMessageExtensions for stream_message::Model {
 
     Ok(())
   }
+
+  /// Sets `deleted_at` on the message with the given origin id, if it hasn't already been deleted.
+  ///
+  /// Returns the amount of messages that were marked.
+  async fn mark_deleted_by_origin_id(
+    origin_id: &str,
+    deleted_at: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<u64, EntityExtensionError> {
+    let update_result = stream_message::Entity::update_many()
+      .col_expr(stream_message::Column::DeletedAt, Expr::value(deleted_at))
+      .filter(stream_message::Column::OriginId.eq(origin_id))
+      .filter(stream_message::Column::DeletedAt.is_null())
+      .exec(database_connection)
+      .await?;
+
+    Ok(update_result.rows_affected)
+  }
+
+  /// Sets `deleted_at` on every message in the stream sent before the given time that hasn't already been deleted.
+  ///
+  /// Returns the amount of messages that were marked.
+  async fn mark_stream_messages_deleted(
+    stream_id: i32,
+    deleted_at: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<u64, EntityExtensionError> {
+    let update_result = stream_message::Entity::update_many()
+      .col_expr(stream_message::Column::DeletedAt, Expr::value(deleted_at))
+      .filter(stream_message::Column::StreamId.eq(stream_id))
+      .filter(stream_message::Column::Timestamp.lte(deleted_at))
+      .filter(stream_message::Column::DeletedAt.is_null())
+      .exec(database_connection)
+      .await?;
+
+    Ok(update_result.rows_affected)
+  }
 }
@@ -0,0 +1,506 @@
+use crate::errors::EntityExtensionError;
+use crate::exchange_rate::CurrencyConverter;
+use app_config::AppConfig;
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveTime, Utc};
+use entities::sea_orm_active_enums::EventType;
+use entities::*;
+use sea_orm::prelude::Decimal;
+use sea_orm::*;
+use std::collections::{HashMap, HashSet};
+
+/// How many of a user's most used emotes are in their recap.
+pub const FAVOURITE_EMOTE_COUNT: usize = 5;
+
+/// A chatter's activity in a channel over a period of time.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct UserRecap {
+  pub user: twitch_user::Model,
+  pub channel: twitch_user::Model,
+  pub period_start: DateTime<Utc>,
+  pub period_end: DateTime<Utc>,
+  pub message_count: u64,
+  /// Where the user placed among the channel's chatters by messages sent, for each month of the period.
+  pub rank_history: Vec<MonthlyRank>,
+  /// The user's most used emotes, most used first.
+  pub favourite_emotes: Vec<EmoteUses>,
+  /// The amount of the channel's streams the user sent a message in.
+  pub streams_attended: u64,
+  /// The amount of streams the channel had in the period.
+  pub total_streams: u64,
+  /// The most streams in a row the user sent a message in.
+  pub longest_attendance_streak: u64,
+  pub donations: Vec<DonationTotal>,
+  pub gift_subs_given: u64,
+  pub timeouts_received: u64,
+  pub name_changes: Vec<twitch_user_name_change::Model>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MonthlyRank {
+  pub year: i32,
+  pub month: u32,
+  /// None if the user sent no messages that month.
+  pub rank: Option<u64>,
+  pub message_count: u64,
+  pub chatter_count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct EmoteUses {
+  pub name: String,
+  pub uses: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DonationTotal {
+  pub event_type: EventType,
+  /// The currency the amount is in, None for bits and gift subs.
+  ///
+  /// Donations are converted to the reporting currency, unless there's no rate to convert them with.
+  pub currency: Option<String>,
+  pub count: u64,
+  pub amount: Decimal,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ChatterMessageCount {
+  twitch_user_id: i32,
+  message_count: i64,
+}
+
+impl UserRecap {
+  /// Gathers the user's activity in the channel from `period_start` up to `period_end`.
+  pub async fn new(
+    user: twitch_user::Model,
+    channel: twitch_user::Model,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Self, EntityExtensionError> {
+    let user_messages_condition = Condition::all()
+      .add(stream_message::Column::TwitchUserId.eq(user.id))
+      .add(stream_message::Column::ChannelId.eq(channel.id))
+      .add(stream_message::Column::Timestamp.gte(period_start))
+      .add(stream_message::Column::Timestamp.lt(period_end));
+
+    let message_count = stream_message::Entity::find()
+      .filter(user_messages_condition.clone())
+      .count(database_connection)
+      .await?;
+    let attended_stream_ids: HashSet<i32> = stream_message::Entity::find()
+      .filter(user_messages_condition.clone())
+      .filter(stream_message::Column::StreamId.is_not_null())
+      .select_only()
+      .column(stream_message::Column::StreamId)
+      .distinct()
+      .into_tuple::<i32>()
+      .all(database_connection)
+      .await?
+      .into_iter()
+      .collect();
+    let channel_stream_ids: Vec<i32> = stream::Entity::find()
+      .filter(stream::Column::TwitchUserId.eq(channel.id))
+      .filter(stream::Column::StartTimestamp.gte(period_start))
+      .filter(stream::Column::StartTimestamp.lt(period_end))
+      .order_by_asc(stream::Column::StartTimestamp)
+      .select_only()
+      .column(stream::Column::Id)
+      .into_tuple::<i32>()
+      .all(database_connection)
+      .await?;
+
+    let rank_history = Self::get_rank_history(
+      &user,
+      &channel,
+      period_start,
+      period_end,
+      database_connection,
+    )
+    .await?;
+    let favourite_emotes =
+      Self::get_favourite_emotes(user_messages_condition, database_connection).await?;
+    let donations = Self::get_donations(
+      &user,
+      &channel,
+      period_start,
+      period_end,
+      database_connection,
+    )
+    .await?;
+    let gift_subs_given = donations
+      .iter()
+      .find(|donation_total| donation_total.event_type == EventType::GiftSubs)
+      .and_then(|donation_total| u64::try_from(donation_total.amount.trunc()).ok())
+      .unwrap_or_default();
+    let timeouts_received = user_timeout::Entity::find()
+      .filter(user_timeout::Column::TwitchUserId.eq(user.id))
+      .filter(user_timeout::Column::ChannelId.eq(channel.id))
+      .filter(user_timeout::Column::Timestamp.gte(period_start))
+      .filter(user_timeout::Column::Timestamp.lt(period_end))
+      .count(database_connection)
+      .await?;
+    let name_changes = twitch_user_name_change::Entity::find()
+      .filter(twitch_user_name_change::Column::TwitchUserId.eq(user.id))
+      .filter(twitch_user_name_change::Column::CreatedAt.gte(period_start))
+      .filter(twitch_user_name_change::Column::CreatedAt.lt(period_end))
+      .order_by_asc(twitch_user_name_change::Column::CreatedAt)
+      .all(database_connection)
+      .await?;
+
+    Ok(Self {
+      message_count,
+      rank_history,
+      favourite_emotes,
+      streams_attended: channel_stream_ids
+        .iter()
+        .filter(|stream_id| attended_stream_ids.contains(stream_id))
+        .count() as u64,
+      total_streams: channel_stream_ids.len() as u64,
+      longest_attendance_streak: longest_attendance_streak(
+        &channel_stream_ids,
+        &attended_stream_ids,
+      ),
+      donations,
+      gift_subs_given,
+      timeouts_received,
+      name_changes,
+      user,
+      channel,
+      period_start,
+      period_end,
+    })
+  }
+
+  /// Returns the start and end of the given month, or of the whole year if there's no month.
+  ///
+  /// None is returned if the year or month doesn't exist.
+  pub fn calendar_period(year: i32, month: Option<u32>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let period_start = NaiveDate::from_ymd_opt(year, month.unwrap_or(1), 1)?;
+    let period_length = if month.is_some() {
+      Months::new(1)
+    } else {
+      Months::new(12)
+    };
+    let period_end = period_start.checked_add_months(period_length)?;
+
+    Some((
+      period_start.and_time(NaiveTime::MIN).and_utc(),
+      period_end.and_time(NaiveTime::MIN).and_utc(),
+    ))
+  }
+
+  async fn get_rank_history(
+    user: &twitch_user::Model,
+    channel: &twitch_user::Model,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Vec<MonthlyRank>, EntityExtensionError> {
+    let mut rank_history = vec![];
+
+    for (month_start, month_end) in month_ranges(period_start, period_end) {
+      let chatter_message_counts = stream_message::Entity::find()
+        .select_only()
+        .column(stream_message::Column::TwitchUserId)
+        .column_as(stream_message::Column::Id.count(), "message_count")
+        .filter(stream_message::Column::ChannelId.eq(channel.id))
+        .filter(stream_message::Column::Timestamp.gte(month_start))
+        .filter(stream_message::Column::Timestamp.lt(month_end))
+        .group_by(stream_message::Column::TwitchUserId)
+        .into_model::<ChatterMessageCount>()
+        .all(database_connection)
+        .await?;
+
+      let message_count = chatter_message_counts
+        .iter()
+        .find(|chatter| chatter.twitch_user_id == user.id)
+        .map(|chatter| chatter.message_count)
+        .unwrap_or_default();
+      let rank = (message_count > 0).then(|| {
+        chatter_message_counts
+          .iter()
+          .filter(|chatter| chatter.message_count > message_count)
+          .count() as u64
+          + 1
+      });
+
+      rank_history.push(MonthlyRank {
+        year: month_start.year(),
+        month: month_start.month(),
+        rank,
+        message_count: message_count as u64,
+        chatter_count: chatter_message_counts.len() as u64,
+      });
+    }
+
+    Ok(rank_history)
+  }
+
+  async fn get_favourite_emotes(
+    user_messages_condition: Condition,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Vec<EmoteUses>, EntityExtensionError> {
+    let emotes_used = emote_usage::Entity::find()
+      .join(
+        JoinType::LeftJoin,
+        emote_usage::Relation::StreamMessage.def(),
+      )
+      .filter(user_messages_condition)
+      .find_also_related(emote::Entity)
+      .all(database_connection)
+      .await?;
+
+    let emote_use_totals = emotes_used.into_iter().fold(
+      HashMap::<String, u64>::new(),
+      |mut emote_use_totals, (emote_usage, emote)| {
+        if let Some(emote) = emote {
+          *emote_use_totals.entry(emote.name).or_default() += emote_usage.usage_count as u64;
+        }
+
+        emote_use_totals
+      },
+    );
+    let mut favourite_emotes: Vec<EmoteUses> = emote_use_totals
+      .into_iter()
+      .map(|(name, uses)| EmoteUses { name, uses })
+      .collect();
+
+    favourite_emotes.sort_by(|lhs, rhs| rhs.uses.cmp(&lhs.uses).then(lhs.name.cmp(&rhs.name)));
+    favourite_emotes.truncate(FAVOURITE_EMOTE_COUNT);
+
+    Ok(favourite_emotes)
+  }
+
+  async fn get_donations(
+    user: &twitch_user::Model,
+    channel: &twitch_user::Model,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Vec<DonationTotal>, EntityExtensionError> {
+    let donation_events = donation_event::Entity::find()
+      .filter(donation_event::Column::DonatorTwitchUserId.eq(user.id))
+      .filter(donation_event::Column::DonationReceiverTwitchUserId.eq(channel.id))
+      .filter(donation_event::Column::Timestamp.gte(period_start))
+      .filter(donation_event::Column::Timestamp.lt(period_end))
+      .all(database_connection)
+      .await?;
+
+    let mut currency_converter = CurrencyConverter::new(AppConfig::reporting_currency());
+    let mut donation_totals: Vec<DonationTotal> = vec![];
+
+    for donation_event in donation_events {
+      let (currency, amount) = match &donation_event.currency {
+        None => (None, donation_event.amount),
+        Some(donation_currency) => match currency_converter
+          .convert_donation(&donation_event, database_connection)
+          .await?
+        {
+          Some(converted_amount) => (
+            Some(currency_converter.to_currency().to_owned()),
+            converted_amount,
+          ),
+          None => (Some(donation_currency.clone()), donation_event.amount),
+        },
+      };
+
+      match donation_totals.iter_mut().find(|donation_total| {
+        donation_total.event_type == donation_event.event_type
+          && donation_total.currency == currency
+      }) {
+        Some(donation_total) => {
+          donation_total.count += 1;
+          donation_total.amount += amount;
+        }
+        None => donation_totals.push(DonationTotal {
+          event_type: donation_event.event_type,
+          currency,
+          count: 1,
+          amount,
+        }),
+      }
+    }
+
+    Ok(donation_totals)
+  }
+}
+
+/// The most streams in a row that were attended, out of the streams given in the order they happened.
+fn longest_attendance_streak(stream_ids: &[i32], attended_stream_ids: &HashSet<i32>) -> u64 {
+  let mut longest_streak = 0;
+  let mut current_streak = 0;
+
+  for stream_id in stream_ids {
+    if attended_stream_ids.contains(stream_id) {
+      current_streak += 1;
+      longest_streak = longest_streak.max(current_streak);
+    } else {
+      current_streak = 0;
+    }
+  }
+
+  longest_streak
+}
+
+/// Splits the period into the calendar months it covers, cut down to the period.
+fn month_ranges(
+  period_start: DateTime<Utc>,
+  period_end: DateTime<Utc>,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+  let mut month_ranges = vec![];
+  let mut month_start = period_start;
+
+  while month_start < period_end {
+    let Some(next_month) = NaiveDate::from_ymd_opt(month_start.year(), month_start.month(), 1)
+      .and_then(|first_of_month| first_of_month.checked_add_months(Months::new(1)))
+    else {
+      break;
+    };
+    let month_end = next_month
+      .and_time(NaiveTime::MIN)
+      .and_utc()
+      .min(period_end);
+
+    month_ranges.push((month_start, month_end));
+    month_start = month_end;
+  }
+
+  month_ranges
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn attendance_streaks_are_broken_by_missed_streams() {
+    let stream_ids = [1, 2, 3, 4, 5, 6];
+    let attended_stream_ids = HashSet::from([1, 2, 4, 5, 6]);
+
+    assert_eq!(
+      longest_attendance_streak(&stream_ids, &attended_stream_ids),
+      3
+    );
+    assert_eq!(longest_attendance_streak(&stream_ids, &HashSet::new()), 0);
+  }
+
+  #[test]
+  fn periods_are_split_into_months() {
+    let period_start = "2024-12-15T00:00:00Z".parse().unwrap();
+    let period_end = "2025-02-10T00:00:00Z".parse().unwrap();
+
+    let month_ranges = month_ranges(period_start, period_end);
+
+    assert_eq!(
+      month_ranges,
+      vec![
+        (period_start, "2025-01-01T00:00:00Z".parse().unwrap()),
+        (
+          "2025-01-01T00:00:00Z".parse().unwrap(),
+          "2025-02-01T00:00:00Z".parse().unwrap()
+        ),
+        ("2025-02-01T00:00:00Z".parse().unwrap(), period_end),
+      ]
+    );
+  }
+
+  #[test]
+  fn calendar_periods_cover_the_month_or_year() {
+    assert_eq!(
+      UserRecap::calendar_period(2024, Some(12)),
+      Some((
+        "2024-12-01T00:00:00Z".parse().unwrap(),
+        "2025-01-01T00:00:00Z".parse().unwrap()
+      ))
+    );
+    assert_eq!(
+      UserRecap::calendar_period(2025, None),
+      Some((
+        "2025-01-01T00:00:00Z".parse().unwrap(),
+        "2026-01-01T00:00:00Z".parse().unwrap()
+      ))
+    );
+    assert_eq!(UserRecap::calendar_period(2025, Some(13)), None);
+  }
+
+  #[tokio::test]
+  async fn donations_are_totalled_by_type_and_currency() {
+    let donation = |id: i32, event_type: EventType, amount: i64| donation_event::Model {
+      id,
+      event_type,
+      amount: Decimal::from(amount),
+      timestamp: "2025-05-08T00:00:00Z".parse().unwrap(),
+      donator_twitch_user_id: Some(2),
+      donation_receiver_twitch_user_id: 1,
+      stream_id: Some(1),
+      subscription_tier: None,
+      unknown_user_id: None,
+      origin_id: None,
+      source_id: None,
+      hype_train_id: None,
+      currency: None,
+      original_amount: None,
+    };
+    let in_currency = |donation: donation_event::Model, currency: &str| donation_event::Model {
+      currency: Some(currency.into()),
+      original_amount: Some(donation.amount),
+      ..donation
+    };
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([vec![
+        donation(1, EventType::Bits, 100),
+        donation(2, EventType::GiftSubs, 5),
+        donation(3, EventType::Bits, 50),
+        in_currency(donation(4, EventType::StreamlabsDonation, 10), "GBP"),
+        in_currency(donation(5, EventType::StreamlabsDonation, 20), "EUR"),
+      ]])
+      .append_query_results([Vec::<exchange_rate::Model>::new(), vec![], vec![], vec![]])
+      .into_connection();
+    let user = |id: i32, login_name: &str| twitch_user::Model {
+      id,
+      twitch_id: id,
+      login_name: login_name.into(),
+      display_name: login_name.into(),
+    };
+
+    let donations = UserRecap::get_donations(
+      &user(2, "linkthedot"),
+      &user(1, "fallenshadow"),
+      "2025-01-01T00:00:00Z".parse().unwrap(),
+      "2026-01-01T00:00:00Z".parse().unwrap(),
+      &mock_database,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+      donations,
+      vec![
+        DonationTotal {
+          event_type: EventType::Bits,
+          currency: None,
+          count: 2,
+          amount: Decimal::from(150),
+        },
+        DonationTotal {
+          event_type: EventType::GiftSubs,
+          currency: None,
+          count: 1,
+          amount: Decimal::from(5),
+        },
+        DonationTotal {
+          event_type: EventType::StreamlabsDonation,
+          currency: Some("GBP".into()),
+          count: 1,
+          amount: Decimal::from(10),
+        },
+        DonationTotal {
+          event_type: EventType::StreamlabsDonation,
+          currency: Some("EUR".into()),
+          count: 1,
+          amount: Decimal::from(20),
+        },
+      ]
+    );
+  }
+}
@@ -13,6 +13,7 @@ export interface UserMessage {
   timestamp: string,
   contents: string,
   is_subscriber: boolean,
+  deleted_at: string | null,
   emote_usage: Emote[],
 }
 
@@ -27,6 +27,20 @@ mod m20250705_165646_add_unique_id_column_to_messages;
 mod m20250713_194533_add_message_source_id_to_sharedchat_tables;
 mod m20250721_001104_update_emote_table_for_third_party_emote_storage;
 mod m20250721_001110_convert_stream_message_emote_columns_to_many_to_many_tables;
+mod m20261017_120000_create_channel_emote_membership_table;
+mod m20261017_130000_create_message_deletion_table;
+mod m20261017_130100_add_deleted_at_column_to_stream_message_table;
+mod m20261017_140000_create_channel_point_redemption_table;
+mod m20261017_150000_create_hype_train_tables;
+mod m20261017_160000_create_channel_notice_table;
+mod m20261017_170000_add_currency_columns_to_donation_event_table;
+mod m20261017_180000_create_exchange_rate_table;
+mod m20261017_190000_change_donation_event_amounts_to_decimal;
+mod m20261017_200000_add_hype_chat_to_donation_event_type;
+mod m20261017_210000_create_bits_power_up_table;
+mod m20261017_220000_create_bits_cheermote_table;
+mod m20261017_230000_create_generated_report_table;
+mod m20261017_234000_create_report_run_table;
 
 pub struct Migrator;
 
@@ -67,6 +81,20 @@ impl MigratorTrait for Migrator {
       Box::new(
         m20250721_001110_convert_stream_message_emote_columns_to_many_to_many_tables::Migration,
       ),
+      Box::new(m20261017_120000_create_channel_emote_membership_table::Migration),
+      Box::new(m20261017_130000_create_message_deletion_table::Migration),
+      Box::new(m20261017_130100_add_deleted_at_column_to_stream_message_table::Migration),
+      Box::new(m20261017_140000_create_channel_point_redemption_table::Migration),
+      Box::new(m20261017_150000_create_hype_train_tables::Migration),
+      Box::new(m20261017_160000_create_channel_notice_table::Migration),
+      Box::new(m20261017_170000_add_currency_columns_to_donation_event_table::Migration),
+      Box::new(m20261017_180000_create_exchange_rate_table::Migration),
+      Box::new(m20261017_190000_change_donation_event_amounts_to_decimal::Migration),
+      Box::new(m20261017_200000_add_hype_chat_to_donation_event_type::Migration),
+      Box::new(m20261017_210000_create_bits_power_up_table::Migration),
+      Box::new(m20261017_220000_create_bits_cheermote_table::Migration),
+      Box::new(m20261017_230000_create_generated_report_table::Migration),
+      Box::new(m20261017_234000_create_report_run_table::Migration),
     ]
   }
 }
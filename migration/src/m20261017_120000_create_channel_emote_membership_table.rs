@@ -0,0 +1,115 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(ChannelEmoteMembership::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(ChannelEmoteMembership::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(ChannelEmoteMembership::ChannelId)
+              .integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(ChannelEmoteMembership::EmoteId)
+              .integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(ChannelEmoteMembership::AddedAt)
+              .timestamp()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            ColumnDef::new(ChannelEmoteMembership::RemovedAt)
+              .timestamp()
+              .null(),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-channel_emote_membership-channel_id")
+              .from(
+                ChannelEmoteMembership::Table,
+                ChannelEmoteMembership::ChannelId,
+              )
+              .to(TwitchUser::Table, TwitchUser::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-channel_emote_membership-emote_id")
+              .from(
+                ChannelEmoteMembership::Table,
+                ChannelEmoteMembership::EmoteId,
+              )
+              .to(Emote::Table, Emote::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-channel_emote_membership-channel_id-emote_id")
+          .table(ChannelEmoteMembership::Table)
+          .col(ChannelEmoteMembership::ChannelId)
+          .col(ChannelEmoteMembership::EmoteId)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(
+        Table::drop()
+          .table(ChannelEmoteMembership::Table)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum TwitchUser {
+  Table,
+  Id,
+  _TwitchId,
+  _DisplayName,
+  _LoginName,
+}
+
+#[derive(Iden)]
+enum Emote {
+  Table,
+  Id,
+  _Name,
+  _ExternalId,
+  _ExternalService,
+}
+
+#[derive(Iden)]
+enum ChannelEmoteMembership {
+  Table,
+  Id,
+  ChannelId,
+  EmoteId,
+  AddedAt,
+  RemovedAt,
+}
@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(MessageDeletion::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(MessageDeletion::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(MessageDeletion::Timestamp)
+              .timestamp()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(MessageDeletion::ChannelId)
+              .integer()
+              .not_null(),
+          )
+          .col(ColumnDef::new(MessageDeletion::StreamId).integer().null())
+          .col(
+            ColumnDef::new(MessageDeletion::TargetOriginId)
+              .char_len(64)
+              .null(),
+          )
+          .col(ColumnDef::new(MessageDeletion::LoginName).string().null())
+          .col(ColumnDef::new(MessageDeletion::Contents).text().null())
+          .col(
+            ColumnDef::new(MessageDeletion::IsChatClear)
+              .tiny_integer()
+              .not_null()
+              .default(0),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-message_deletion-channel_id")
+              .from(MessageDeletion::Table, MessageDeletion::ChannelId)
+              .to(TwitchUser::Table, TwitchUser::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-message_deletion-stream_id")
+              .from(MessageDeletion::Table, MessageDeletion::StreamId)
+              .to(Stream::Table, Stream::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-message_deletion-target_origin_id")
+          .table(MessageDeletion::Table)
+          .col(MessageDeletion::TargetOriginId)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(MessageDeletion::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum TwitchUser {
+  Table,
+  Id,
+  _TwitchId,
+  _DisplayName,
+  _LoginName,
+}
+
+#[derive(Iden)]
+enum Stream {
+  Table,
+  Id,
+  _TwitchStreamId,
+  _StartTimestamp,
+  _EndTimestamp,
+  _TwitchUserId,
+}
+
+#[derive(Iden)]
+enum MessageDeletion {
+  Table,
+  Id,
+  Timestamp,
+  ChannelId,
+  StreamId,
+  TargetOriginId,
+  LoginName,
+  Contents,
+  IsChatClear,
+}
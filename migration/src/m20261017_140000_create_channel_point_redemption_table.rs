@@ -0,0 +1,155 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(ChannelPointRedemption::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(ChannelPointRedemption::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(ChannelPointRedemption::Timestamp)
+              .timestamp()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(ChannelPointRedemption::ChannelId)
+              .integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(ChannelPointRedemption::TwitchUserId)
+              .integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(ChannelPointRedemption::StreamId)
+              .integer()
+              .null(),
+          )
+          .col(
+            ColumnDef::new(ChannelPointRedemption::RewardId)
+              .char_len(64)
+              .null(),
+          )
+          .col(
+            ColumnDef::new(ChannelPointRedemption::RewardTitle)
+              .string()
+              .null(),
+          )
+          .col(
+            ColumnDef::new(ChannelPointRedemption::RewardCost)
+              .integer()
+              .null(),
+          )
+          .col(
+            ColumnDef::new(ChannelPointRedemption::IsHighlightedMessage)
+              .tiny_integer()
+              .not_null()
+              .default(0),
+          )
+          .col(
+            ColumnDef::new(ChannelPointRedemption::Contents)
+              .text()
+              .null(),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-channel_point_redemption-channel_id")
+              .from(
+                ChannelPointRedemption::Table,
+                ChannelPointRedemption::ChannelId,
+              )
+              .to(TwitchUser::Table, TwitchUser::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-channel_point_redemption-twitch_user_id")
+              .from(
+                ChannelPointRedemption::Table,
+                ChannelPointRedemption::TwitchUserId,
+              )
+              .to(TwitchUser::Table, TwitchUser::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-channel_point_redemption-stream_id")
+              .from(
+                ChannelPointRedemption::Table,
+                ChannelPointRedemption::StreamId,
+              )
+              .to(Stream::Table, Stream::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-channel_point_redemption-reward_id")
+          .table(ChannelPointRedemption::Table)
+          .col(ChannelPointRedemption::RewardId)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(
+        Table::drop()
+          .table(ChannelPointRedemption::Table)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum TwitchUser {
+  Table,
+  Id,
+  _TwitchId,
+  _DisplayName,
+  _LoginName,
+}
+
+#[derive(Iden)]
+enum Stream {
+  Table,
+  Id,
+  _TwitchStreamId,
+  _StartTimestamp,
+  _EndTimestamp,
+  _TwitchUserId,
+}
+
+#[derive(Iden)]
+enum ChannelPointRedemption {
+  Table,
+  Id,
+  Timestamp,
+  ChannelId,
+  TwitchUserId,
+  StreamId,
+  RewardId,
+  RewardTitle,
+  RewardCost,
+  IsHighlightedMessage,
+  Contents,
+}
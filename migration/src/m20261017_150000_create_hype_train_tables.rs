@@ -0,0 +1,231 @@
+use sea_orm::{DeriveActiveEnum, DeriveDisplay, EnumIter};
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(HypeTrain::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(HypeTrain::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(HypeTrain::TwitchHypeTrainId)
+              .char_len(64)
+              .not_null()
+              .unique_key(),
+          )
+          .col(ColumnDef::new(HypeTrain::ChannelId).integer().not_null())
+          .col(ColumnDef::new(HypeTrain::StreamId).integer().null())
+          .col(ColumnDef::new(HypeTrain::Level).integer().not_null())
+          .col(ColumnDef::new(HypeTrain::Total).integer().not_null())
+          .col(
+            ColumnDef::new(HypeTrain::StartTimestamp)
+              .timestamp()
+              .not_null(),
+          )
+          .col(ColumnDef::new(HypeTrain::EndTimestamp).timestamp().null())
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-hype_train-channel_id")
+              .from(HypeTrain::Table, HypeTrain::ChannelId)
+              .to(TwitchUser::Table, TwitchUser::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-hype_train-stream_id")
+              .from(HypeTrain::Table, HypeTrain::StreamId)
+              .to(Stream::Table, Stream::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(HypeTrainContributor::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(HypeTrainContributor::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(HypeTrainContributor::HypeTrainId)
+              .integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(HypeTrainContributor::TwitchUserId)
+              .integer()
+              .not_null(),
+          )
+          .col(
+            enumeration(
+              HypeTrainContributor::ContributionType,
+              HypeTrainContributor::ContributionType,
+              [
+                HypeTrainContributionType::Bits,
+                HypeTrainContributionType::Subscription,
+                HypeTrainContributionType::Other,
+              ],
+            )
+            .not_null(),
+          )
+          .col(
+            ColumnDef::new(HypeTrainContributor::Total)
+              .integer()
+              .not_null(),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-hype_train_contributor-hype_train_id")
+              .from(
+                HypeTrainContributor::Table,
+                HypeTrainContributor::HypeTrainId,
+              )
+              .to(HypeTrain::Table, HypeTrain::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-hype_train_contributor-twitch_user_id")
+              .from(
+                HypeTrainContributor::Table,
+                HypeTrainContributor::TwitchUserId,
+              )
+              .to(TwitchUser::Table, TwitchUser::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(DonationEvent::Table)
+          .add_column(ColumnDef::new(DonationEvent::HypeTrainId).integer().null())
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk-donation_event-hype_train_id")
+              .from_tbl(DonationEvent::Table)
+              .from_col(DonationEvent::HypeTrainId)
+              .to_tbl(HypeTrain::Table)
+              .to_col(HypeTrain::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(DonationEvent::Table)
+          .drop_foreign_key(Alias::new("fk-donation_event-hype_train_id"))
+          .drop_column(DonationEvent::HypeTrainId)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(HypeTrainContributor::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(HypeTrain::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum TwitchUser {
+  Table,
+  Id,
+  _TwitchId,
+  _DisplayName,
+  _LoginName,
+}
+
+#[derive(Iden)]
+enum Stream {
+  Table,
+  Id,
+  _TwitchStreamId,
+  _StartTimestamp,
+  _EndTimestamp,
+  _TwitchUserId,
+}
+
+#[derive(Iden)]
+enum DonationEvent {
+  Table,
+  _Id,
+  _DonatorTwitchUserId,
+  _DonationReceiverTwitchUserId,
+  _StreamId,
+  _EventType,
+  _Amount,
+  _Timestamp,
+  _SubscriptionTier,
+  _UnknownUserId,
+  _OriginId,
+  _SourceId,
+  HypeTrainId,
+}
+
+#[derive(Iden)]
+enum HypeTrain {
+  Table,
+  Id,
+  TwitchHypeTrainId,
+  ChannelId,
+  StreamId,
+  Level,
+  Total,
+  StartTimestamp,
+  EndTimestamp,
+}
+
+#[derive(Iden)]
+enum HypeTrainContributor {
+  Table,
+  Id,
+  HypeTrainId,
+  TwitchUserId,
+  ContributionType,
+  Total,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Iden, EnumIter, DeriveActiveEnum, DeriveDisplay)]
+#[sea_orm(
+  rs_type = "String",
+  db_type = "Enum",
+  enum_name = "hype_train_contribution_type"
+)]
+enum HypeTrainContributionType {
+  #[sea_orm(string_value = "bits")]
+  Bits,
+  #[sea_orm(string_value = "subscription")]
+  Subscription,
+  #[sea_orm(string_value = "other")]
+  Other,
+}
@@ -0,0 +1,114 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(ChannelNotice::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(ChannelNotice::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(ChannelNotice::Timestamp)
+              .timestamp()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(ChannelNotice::ChannelId)
+              .integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(ChannelNotice::TwitchUserId)
+              .integer()
+              .not_null(),
+          )
+          .col(ColumnDef::new(ChannelNotice::StreamId).integer().null())
+          .col(
+            ColumnDef::new(ChannelNotice::NoticeType)
+              .string_len(64)
+              .not_null(),
+          )
+          .col(ColumnDef::new(ChannelNotice::Parameters).json().null())
+          .col(ColumnDef::new(ChannelNotice::Contents).text().null())
+          .col(
+            ColumnDef::new(ChannelNotice::OriginId)
+              .char_len(64)
+              .null()
+              .unique_key(),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-channel_notice-channel_id")
+              .from(ChannelNotice::Table, ChannelNotice::ChannelId)
+              .to(TwitchUser::Table, TwitchUser::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-channel_notice-twitch_user_id")
+              .from(ChannelNotice::Table, ChannelNotice::TwitchUserId)
+              .to(TwitchUser::Table, TwitchUser::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-channel_notice-stream_id")
+              .from(ChannelNotice::Table, ChannelNotice::StreamId)
+              .to(Stream::Table, Stream::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(ChannelNotice::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum TwitchUser {
+  Table,
+  Id,
+  _TwitchId,
+  _DisplayName,
+  _LoginName,
+}
+
+#[derive(Iden)]
+enum Stream {
+  Table,
+  Id,
+  _TwitchStreamId,
+  _StartTimestamp,
+  _EndTimestamp,
+  _TwitchUserId,
+}
+
+#[derive(Iden)]
+enum ChannelNotice {
+  Table,
+  Id,
+  Timestamp,
+  ChannelId,
+  TwitchUserId,
+  StreamId,
+  NoticeType,
+  Parameters,
+  Contents,
+  OriginId,
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+/// Every donation stored before this migration was parsed from a `£` amount.
+const PREVIOUS_STREAMLABS_CURRENCY: &str = "GBP";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(DonationEvent::Table)
+          .add_column(ColumnDef::new(DonationEvent::Currency).char_len(3).null())
+          .add_column(ColumnDef::new(DonationEvent::OriginalAmount).float().null())
+          .to_owned(),
+      )
+      .await?;
+
+    let existing_donation_currency = Query::update()
+      .table(DonationEvent::Table)
+      .value(DonationEvent::Currency, PREVIOUS_STREAMLABS_CURRENCY)
+      .value(
+        DonationEvent::OriginalAmount,
+        Expr::col(DonationEvent::Amount),
+      )
+      .and_where(Expr::col(DonationEvent::EventType).eq("streamlabs_donation"))
+      .to_owned();
+
+    manager.exec_stmt(existing_donation_currency).await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(DonationEvent::Table)
+          .drop_column(DonationEvent::Currency)
+          .drop_column(DonationEvent::OriginalAmount)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum DonationEvent {
+  Table,
+  _Id,
+  EventType,
+  Amount,
+  _Timestamp,
+  _DonatorTwitchUserId,
+  _DonationReceiverTwitchUserId,
+  _StreamId,
+  _SubscriptionTier,
+  _UnknownUserId,
+  _OriginId,
+  _SourceId,
+  _HypeTrainId,
+  Currency,
+  OriginalAmount,
+}
@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(ExchangeRate::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(ExchangeRate::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(ColumnDef::new(ExchangeRate::Date).date().not_null())
+          .col(
+            ColumnDef::new(ExchangeRate::FromCurrency)
+              .char_len(3)
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(ExchangeRate::ToCurrency)
+              .char_len(3)
+              .not_null(),
+          )
+          .col(ColumnDef::new(ExchangeRate::Rate).double().not_null())
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-exchange_rate-date-from_currency-to_currency")
+          .table(ExchangeRate::Table)
+          .col(ExchangeRate::Date)
+          .col(ExchangeRate::FromCurrency)
+          .col(ExchangeRate::ToCurrency)
+          .unique()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(ExchangeRate::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum ExchangeRate {
+  Table,
+  Id,
+  Date,
+  FromCurrency,
+  ToCurrency,
+  Rate,
+}
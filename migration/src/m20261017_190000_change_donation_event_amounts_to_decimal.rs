@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+/// Large enough for any bit count, with room for currencies that use more than 2 decimal places.
+const AMOUNT_PRECISION: u32 = 19;
+const AMOUNT_SCALE: u32 = 4;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(DonationEvent::Table)
+          .modify_column(
+            ColumnDef::new(DonationEvent::Amount)
+              .decimal_len(AMOUNT_PRECISION, AMOUNT_SCALE)
+              .not_null(),
+          )
+          .modify_column(
+            ColumnDef::new(DonationEvent::OriginalAmount)
+              .decimal_len(AMOUNT_PRECISION, AMOUNT_SCALE)
+              .null(),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Rounds away any float error carried over by the conversion, as donations are parsed to the cent.
+    let round_currency_amounts = Query::update()
+      .table(DonationEvent::Table)
+      .value(
+        DonationEvent::Amount,
+        Func::round_with_precision(Expr::col(DonationEvent::Amount), 2),
+      )
+      .value(
+        DonationEvent::OriginalAmount,
+        Func::round_with_precision(Expr::col(DonationEvent::OriginalAmount), 2),
+      )
+      .and_where(Expr::col(DonationEvent::EventType).eq("streamlabs_donation"))
+      .to_owned();
+    // Bits and gift sub counts are always whole numbers.
+    let round_counts = Query::update()
+      .table(DonationEvent::Table)
+      .value(
+        DonationEvent::Amount,
+        Func::round(Expr::col(DonationEvent::Amount)),
+      )
+      .and_where(Expr::col(DonationEvent::EventType).ne("streamlabs_donation"))
+      .to_owned();
+
+    manager.exec_stmt(round_currency_amounts).await?;
+    manager.exec_stmt(round_counts).await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(DonationEvent::Table)
+          .modify_column(ColumnDef::new(DonationEvent::Amount).float().not_null())
+          .modify_column(ColumnDef::new(DonationEvent::OriginalAmount).float().null())
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum DonationEvent {
+  Table,
+  _Id,
+  EventType,
+  Amount,
+  _Timestamp,
+  _DonatorTwitchUserId,
+  _DonationReceiverTwitchUserId,
+  _StreamId,
+  _SubscriptionTier,
+  _UnknownUserId,
+  _OriginId,
+  _SourceId,
+  _HypeTrainId,
+  _Currency,
+  OriginalAmount,
+}
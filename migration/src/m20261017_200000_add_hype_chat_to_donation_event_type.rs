@@ -0,0 +1,79 @@
+use sea_orm::{DeriveActiveEnum, DeriveDisplay, EnumIter};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(DonationEvent::Table)
+          .modify_column(
+            ColumnDef::new(DonationEvent::EventType)
+              .enumeration(
+                Alias::new("donation_event"),
+                [
+                  DonationTypeEnum::Bits,
+                  DonationTypeEnum::GiftSubs,
+                  DonationTypeEnum::StreamlabsDonation,
+                  DonationTypeEnum::HypeChat,
+                ],
+              )
+              .not_null(),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let hype_chat_deletion = Query::delete()
+      .from_table(DonationEvent::Table)
+      .cond_where(Expr::col(DonationEvent::EventType).eq(DonationTypeEnum::HypeChat))
+      .to_owned();
+
+    manager.exec_stmt(hype_chat_deletion).await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(DonationEvent::Table)
+          .modify_column(
+            ColumnDef::new(DonationEvent::EventType)
+              .enumeration(
+                Alias::new("donation_event"),
+                [
+                  DonationTypeEnum::Bits,
+                  DonationTypeEnum::GiftSubs,
+                  DonationTypeEnum::StreamlabsDonation,
+                ],
+              )
+              .not_null(),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum DonationEvent {
+  Table,
+  EventType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Iden, EnumIter, DeriveActiveEnum, DeriveDisplay)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "donation_type")]
+enum DonationTypeEnum {
+  #[sea_orm(string_value = "bits")]
+  Bits,
+  #[sea_orm(string_value = "gift_subs")]
+  GiftSubs,
+  #[sea_orm(string_value = "streamlabs_donation")]
+  StreamlabsDonation,
+  #[sea_orm(string_value = "hype_chat")]
+  HypeChat,
+}
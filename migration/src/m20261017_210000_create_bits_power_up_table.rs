@@ -0,0 +1,96 @@
+use sea_orm::{DeriveActiveEnum, DeriveDisplay, EnumIter};
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(BitsPowerUp::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(BitsPowerUp::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(BitsPowerUp::DonationEventId)
+              .integer()
+              .not_null()
+              .unique_key(),
+          )
+          .col(
+            enumeration(
+              BitsPowerUp::PowerUpType,
+              BitsPowerUp::PowerUpType,
+              [
+                BitsPowerUpType::GigantifiedEmote,
+                BitsPowerUpType::MessageEffect,
+              ],
+            )
+            .not_null(),
+          )
+          .col(ColumnDef::new(BitsPowerUp::EmoteId).integer().null())
+          .col(ColumnDef::new(BitsPowerUp::AnimationId).string().null())
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-bits_power_up-donation_event_id")
+              .from(BitsPowerUp::Table, BitsPowerUp::DonationEventId)
+              .to(DonationEvent::Table, DonationEvent::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-bits_power_up-emote_id")
+              .from(BitsPowerUp::Table, BitsPowerUp::EmoteId)
+              .to(Emote::Table, Emote::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(BitsPowerUp::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum BitsPowerUp {
+  Table,
+  Id,
+  DonationEventId,
+  PowerUpType,
+  EmoteId,
+  AnimationId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Iden, EnumIter, DeriveActiveEnum, DeriveDisplay)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "bits_power_up_type")]
+enum BitsPowerUpType {
+  #[sea_orm(string_value = "gigantified_emote")]
+  GigantifiedEmote,
+  #[sea_orm(string_value = "message_effect")]
+  MessageEffect,
+}
+
+#[derive(Iden)]
+enum DonationEvent {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum Emote {
+  Table,
+  Id,
+}
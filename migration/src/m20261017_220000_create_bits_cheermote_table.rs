@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(BitsCheermote::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(BitsCheermote::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(BitsCheermote::DonationEventId)
+              .integer()
+              .not_null(),
+          )
+          .col(ColumnDef::new(BitsCheermote::Prefix).string().not_null())
+          .col(ColumnDef::new(BitsCheermote::Amount).integer().not_null())
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-bits_cheermote-donation_event_id")
+              .from(BitsCheermote::Table, BitsCheermote::DonationEventId)
+              .to(DonationEvent::Table, DonationEvent::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-bits_cheermote-prefix")
+          .table(BitsCheermote::Table)
+          .col(BitsCheermote::Prefix)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(BitsCheermote::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum BitsCheermote {
+  Table,
+  Id,
+  DonationEventId,
+  Prefix,
+  Amount,
+}
+
+#[derive(Iden)]
+enum DonationEvent {
+  Table,
+  Id,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(GeneratedReport::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(GeneratedReport::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(GeneratedReport::StreamId)
+              .integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(GeneratedReport::ReportName)
+              .string()
+              .not_null(),
+          )
+          .col(ColumnDef::new(GeneratedReport::Sink).string().not_null())
+          .col(ColumnDef::new(GeneratedReport::Location).text().null())
+          .col(ColumnDef::new(GeneratedReport::Error).text().null())
+          .col(
+            ColumnDef::new(GeneratedReport::Timestamp)
+              .timestamp()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-generated_report-stream_id")
+              .from(GeneratedReport::Table, GeneratedReport::StreamId)
+              .to(Stream::Table, Stream::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(GeneratedReport::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum GeneratedReport {
+  Table,
+  Id,
+  StreamId,
+  ReportName,
+  Sink,
+  Location,
+  Error,
+  Timestamp,
+}
+
+#[derive(Iden)]
+enum Stream {
+  Table,
+  Id,
+}
@@ -0,0 +1,103 @@
+use sea_orm::{DeriveActiveEnum, DeriveDisplay, EnumIter};
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(ReportRun::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(ReportRun::Id)
+              .integer()
+              .not_null()
+              .primary_key()
+              .auto_increment(),
+          )
+          .col(
+            ColumnDef::new(ReportRun::StreamId)
+              .integer()
+              .not_null()
+              .unique_key(),
+          )
+          .col(
+            enumeration(
+              ReportRun::Status,
+              ReportRun::Status,
+              [
+                ReportRunStatus::Pending,
+                ReportRunStatus::Done,
+                ReportRunStatus::Failed,
+              ],
+            )
+            .not_null(),
+          )
+          .col(
+            ColumnDef::new(ReportRun::StartedAt)
+              .timestamp()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(ColumnDef::new(ReportRun::FinishedAt).timestamp().null())
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-report_run-stream_id")
+              .from(ReportRun::Table, ReportRun::StreamId)
+              .to(Stream::Table, Stream::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Streams that already had a report sent are finished runs.
+    manager
+      .get_connection()
+      .execute_unprepared(
+        "INSERT INTO report_run (stream_id, status, finished_at)
+        SELECT stream_id, 'done', MAX(timestamp) FROM generated_report
+        WHERE location IS NOT NULL GROUP BY stream_id",
+      )
+      .await?;
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(ReportRun::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum ReportRun {
+  Table,
+  Id,
+  StreamId,
+  Status,
+  StartedAt,
+  FinishedAt,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Iden, EnumIter, DeriveActiveEnum, DeriveDisplay)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "report_run_status")]
+enum ReportRunStatus {
+  #[sea_orm(string_value = "pending")]
+  Pending,
+  #[sea_orm(string_value = "done")]
+  Done,
+  #[sea_orm(string_value = "failed")]
+  Failed,
+}
+
+#[derive(Iden)]
+enum Stream {
+  Table,
+  Id,
+}
@@ -0,0 +1,346 @@
+use crate::conditions::query_conditions_builder::AppQueryConditionsBuilder;
+use crate::errors::AppError;
+use crate::generated_reports::{claim_report_run, finish_report_run, record_sent_reports};
+use crate::report_formats::ReportFormat;
+use crate::report_sinks::ReportSink;
+use crate::reports::chosen_report::ChosenReport;
+use crate::reports::report_options::ReportOptions;
+use crate::reports::{basic_reports, subathon_reports};
+use crate::upload_reports::{send_reports, SentReport};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeDelta, Utc};
+use database_connection::get_database_connection;
+use entities::sea_orm_active_enums::ReportRunStatus;
+use entities::{stream, twitch_user};
+use futures::stream::StreamExt;
+use sea_orm::*;
+
+/// Narrows down which streams are backfilled. Every ended stream is backfilled when nothing is set.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BackfillFilter {
+  /// The first day a backfilled stream can start on.
+  pub date_start: Option<NaiveDate>,
+  /// The last day a backfilled stream can start on.
+  pub date_end: Option<NaiveDate>,
+  /// The login names of the streamers to backfill.
+  pub streamer_names: Vec<String>,
+}
+
+/// The streams handled by a backfill, by what happened to them.
+#[derive(Debug, Default)]
+pub struct BackfillSummary {
+  pub generated: Vec<i32>,
+  /// Streams that already had reports, or were claimed by another report run.
+  pub skipped: Vec<i32>,
+  pub failed: Vec<(i32, AppError)>,
+}
+
+enum BackfillResult {
+  Generated,
+  Skipped,
+  Failed(AppError),
+}
+
+/// Generates and sends the reports for every ended stream matching the filter that has no generated reports yet.
+///
+/// Up to `concurrency` streams are generated at once. Reports are generated for the month each stream started in,
+/// unless a month or year is set in the report options.
+pub async fn backfill_reports(
+  chosen_report: ChosenReport,
+  filter: &BackfillFilter,
+  report_options: &ReportOptions,
+  report_format: ReportFormat,
+  report_sinks: &[Box<dyn ReportSink>],
+  concurrency: usize,
+) -> Result<BackfillSummary, AppError> {
+  let database_connection = get_database_connection().await;
+  let streams = get_streams_to_backfill(filter, database_connection).await?;
+
+  tracing::info!("Backfilling reports for {} streams.", streams.len());
+
+  let backfill_results: Vec<(i32, BackfillResult)> = futures::stream::iter(streams)
+    .map(|stream| async move {
+      let stream_id = stream.id;
+      let backfill_result = backfill_stream(
+        stream,
+        chosen_report,
+        report_options,
+        report_format,
+        report_sinks,
+        database_connection,
+      )
+      .await;
+
+      (stream_id, backfill_result)
+    })
+    .buffer_unordered(concurrency.max(1))
+    .collect()
+    .await;
+
+  let mut summary = BackfillSummary::default();
+
+  for (stream_id, backfill_result) in backfill_results {
+    match backfill_result {
+      BackfillResult::Generated => summary.generated.push(stream_id),
+      BackfillResult::Skipped => summary.skipped.push(stream_id),
+      BackfillResult::Failed(error) => summary.failed.push((stream_id, error)),
+    }
+  }
+
+  summary.generated.sort();
+  summary.skipped.sort();
+  summary.failed.sort_by_key(|(stream_id, _)| *stream_id);
+
+  Ok(summary)
+}
+
+async fn backfill_stream(
+  stream: stream::Model,
+  chosen_report: ChosenReport,
+  report_options: &ReportOptions,
+  report_format: ReportFormat,
+  report_sinks: &[Box<dyn ReportSink>],
+  database_connection: &DatabaseConnection,
+) -> BackfillResult {
+  match claim_report_run(stream.id, database_connection).await {
+    Ok(true) => (),
+    Ok(false) => return BackfillResult::Skipped,
+    Err(error) => return BackfillResult::Failed(error),
+  }
+
+  tracing::info!("Backfilling reports for stream {}.", stream.id);
+
+  let result = generate_and_send_reports(
+    &stream,
+    chosen_report,
+    report_options,
+    report_format,
+    report_sinks,
+    database_connection,
+  )
+  .await;
+  let report_run_status = match result {
+    Ok(()) => ReportRunStatus::Done,
+    Err(_) => ReportRunStatus::Failed,
+  };
+
+  if let Err(error) = finish_report_run(stream.id, report_run_status, database_connection).await {
+    tracing::error!(
+      "Failed to record the report run for stream {}. Reason: {}",
+      stream.id,
+      error
+    );
+  }
+
+  match result {
+    Ok(()) => BackfillResult::Generated,
+    Err(error) => {
+      tracing::error!(
+        "Failed to backfill the reports for stream {}. Reason: {}",
+        stream.id,
+        error
+      );
+
+      BackfillResult::Failed(error)
+    }
+  }
+}
+
+async fn generate_and_send_reports(
+  stream: &stream::Model,
+  chosen_report: ChosenReport,
+  report_options: &ReportOptions,
+  report_format: ReportFormat,
+  report_sinks: &[Box<dyn ReportSink>],
+  database_connection: &DatabaseConnection,
+) -> Result<(), AppError> {
+  let query_conditions = AppQueryConditionsBuilder::new()
+    .set_stream_id(stream.id)
+    .set_streamer_twitch_user_id(stream.twitch_user_id)
+    .build()?;
+  let report_options = report_options_for_stream(report_options, stream);
+
+  let reports = match chosen_report {
+    ChosenReport::Basic => {
+      basic_reports::generate_reports(query_conditions, stream.twitch_user_id, &report_options)
+        .await?
+    }
+    ChosenReport::Subathon => {
+      subathon_reports::generate_reports(query_conditions, stream.twitch_user_id, &report_options)
+        .await?
+    }
+    ChosenReport::CalculateSubathonPoints | ChosenReport::UserRecap => {
+      return Err(AppError::ReportCannotBeBackfilled(chosen_report));
+    }
+  };
+  let sent_reports = send_reports(stream, &reports, report_format, report_sinks).await?;
+
+  record_sent_reports(stream.id, &sent_reports, database_connection).await?;
+
+  if !sent_reports.iter().any(SentReport::was_sent) {
+    return Err(AppError::NoReportsWereSent(stream.id));
+  }
+
+  Ok(())
+}
+
+/// Sets the monthly reports to the month the stream started in, if no month or year were chosen.
+fn report_options_for_stream(
+  report_options: &ReportOptions,
+  stream: &stream::Model,
+) -> ReportOptions {
+  let mut report_options = report_options.clone();
+
+  if let (None, None, Some(start_timestamp)) = (
+    report_options.year,
+    report_options.month,
+    stream.start_timestamp,
+  ) {
+    report_options.year = Some(start_timestamp.year());
+    report_options.month = Some(start_timestamp.month() as usize);
+  }
+
+  report_options
+}
+
+/// Returns the ended streams matching the filter, oldest first.
+async fn get_streams_to_backfill(
+  filter: &BackfillFilter,
+  database_connection: &DatabaseConnection,
+) -> Result<Vec<stream::Model>, AppError> {
+  let mut condition = Condition::all()
+    .add(stream::Column::StartTimestamp.is_not_null())
+    .add(stream::Column::EndTimestamp.is_not_null());
+
+  let date_start = filter.date_start.map(start_of_day);
+  let date_end = filter
+    .date_end
+    .map(|date_end| start_of_day(date_end) + TimeDelta::days(1));
+
+  if let (Some(start_time), Some(end_time)) = (date_start, date_end) {
+    if start_time >= end_time {
+      return Err(AppError::EndTimeIsOlderThanStartTime {
+        start_time,
+        end_time,
+      });
+    }
+  }
+
+  if let Some(date_start) = date_start {
+    condition = condition.add(stream::Column::StartTimestamp.gte(date_start));
+  }
+
+  if let Some(date_end) = date_end {
+    condition = condition.add(stream::Column::StartTimestamp.lt(date_end));
+  }
+
+  if !filter.streamer_names.is_empty() {
+    let streamers = twitch_user::Entity::find()
+      .filter(
+        twitch_user::Column::LoginName.is_in(filter.streamer_names.iter().map(String::as_str)),
+      )
+      .all(database_connection)
+      .await?;
+
+    for streamer_name in &filter.streamer_names {
+      if !streamers
+        .iter()
+        .any(|streamer| streamer.login_name.eq_ignore_ascii_case(streamer_name))
+      {
+        tracing::warn!("Could not find streamer {} to backfill.", streamer_name);
+      }
+    }
+
+    condition = condition
+      .add(stream::Column::TwitchUserId.is_in(streamers.into_iter().map(|streamer| streamer.id)));
+  }
+
+  Ok(
+    stream::Entity::find()
+      .filter(condition)
+      .order_by_asc(stream::Column::Id)
+      .all(database_connection)
+      .await?,
+  )
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+  date.and_time(NaiveTime::MIN).and_utc()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn streams_are_selected_for_the_given_streamers() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([vec![twitch_user::Model {
+        id: 1,
+        twitch_id: 578762718,
+        login_name: "fallenshadow".into(),
+        display_name: "fallenshadow".into(),
+      }]])
+      .append_query_results([vec![stream::Model {
+        id: 1,
+        twitch_stream_id: 1,
+        start_timestamp: Some("2025-05-08T00:00:00Z".parse().unwrap()),
+        end_timestamp: Some("2025-05-08T08:00:00Z".parse().unwrap()),
+        twitch_user_id: 1,
+      }]])
+      .into_connection();
+    let filter = BackfillFilter {
+      streamer_names: vec!["fallenshadow".into()],
+      ..Default::default()
+    };
+
+    let streams = get_streams_to_backfill(&filter, &mock_database)
+      .await
+      .unwrap();
+
+    assert_eq!(
+      streams.iter().map(|stream| stream.id).collect::<Vec<i32>>(),
+      vec![1]
+    );
+  }
+
+  #[tokio::test]
+  async fn reversed_date_ranges_are_rejected() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql).into_connection();
+    let filter = BackfillFilter {
+      date_start: NaiveDate::from_ymd_opt(2025, 5, 8),
+      date_end: NaiveDate::from_ymd_opt(2025, 5, 7),
+      ..Default::default()
+    };
+
+    let result = get_streams_to_backfill(&filter, &mock_database).await;
+
+    assert!(matches!(
+      result,
+      Err(AppError::EndTimeIsOlderThanStartTime { .. })
+    ));
+  }
+
+  #[test]
+  fn monthly_reports_use_the_month_the_stream_started_in() {
+    let stream = stream::Model {
+      id: 1,
+      twitch_stream_id: 1,
+      start_timestamp: Some("2024-12-31T23:00:00Z".parse().unwrap()),
+      end_timestamp: Some("2025-01-01T04:00:00Z".parse().unwrap()),
+      twitch_user_id: 1,
+    };
+    let chosen_month = ReportOptions {
+      month: Some(3),
+      ..Default::default()
+    };
+
+    let report_options = report_options_for_stream(&ReportOptions::default(), &stream);
+
+    assert_eq!(report_options.year, Some(2024));
+    assert_eq!(report_options.month, Some(12));
+    assert_eq!(
+      report_options_for_stream(&chosen_month, &stream),
+      chosen_month
+    );
+  }
+}
@@ -0,0 +1,68 @@
+use clap::Parser;
+use database_connection::{get_owned_database_connection, get_owned_database_connection_for};
+use entities::exchange_rate;
+use entity_extensions::prelude::*;
+use report_generator::errors::AppError;
+use report_generator::exchange_rate_file::read_exchange_rate_file;
+use std::path::PathBuf;
+
+/// How many rates are inserted per query.
+const INSERT_CHUNK_SIZE: usize = 1000;
+
+/// Stores historical exchange rates so reports can convert donations without network access.
+#[derive(Parser, Debug)]
+#[command(name = "import_exchange_rates")]
+struct ImportArgs {
+  /// A `.csv` file with a `date,from,to,rate` header, or a `.json` array of objects with the same fields.
+  #[arg(short = 'i', long, required = true)]
+  file: PathBuf,
+
+  /// The database to store the rates in. Defaults to the database in the config.
+  #[arg(short = 'd', long)]
+  database: Option<String>,
+}
+
+#[tokio::main]
+async fn main() {
+  report_generator::logging::setup_logging_config().unwrap();
+
+  let args = ImportArgs::parse();
+
+  match import_exchange_rates(&args).await {
+    Ok(imported_rates) => println!("Imported {imported_rates} exchange rates."),
+    Err(error) => {
+      tracing::error!(
+        "Failed to import the exchange rates from {:?}. Reason: {}",
+        args.file,
+        error
+      );
+
+      std::process::exit(1);
+    }
+  }
+}
+
+/// Stores every rate in the file, replacing any already stored for the same date and currencies.
+///
+/// Nothing is stored if any of the rates are invalid.
+async fn import_exchange_rates(args: &ImportArgs) -> Result<usize, AppError> {
+  let exchange_rate_active_models = read_exchange_rate_file(&args.file)?
+    .into_iter()
+    .map(|record| record.into_active_model())
+    .collect::<Result<Vec<exchange_rate::ActiveModel>, AppError>>()?;
+  let imported_rates = exchange_rate_active_models.len();
+
+  let database_connection = match &args.database {
+    Some(database_name) => get_owned_database_connection_for(database_name).await,
+    None => get_owned_database_connection().await,
+  };
+
+  tracing::info!("Importing {imported_rates} exchange rates.");
+
+  for exchange_rate_chunk in exchange_rate_active_models.chunks(INSERT_CHUNK_SIZE) {
+    exchange_rate::Model::insert_or_update_many(exchange_rate_chunk.to_vec(), &database_connection)
+      .await?;
+  }
+
+  Ok(imported_rates)
+}
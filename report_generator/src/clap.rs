@@ -1,7 +1,9 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
 use clap::Parser;
 use std::sync::OnceLock;
 
+use crate::backfill::BackfillFilter;
+use crate::report_formats::ReportFormat;
 use crate::reports::chosen_report::ChosenReport;
 
 static ARGS: OnceLock<Args> = OnceLock::new();
@@ -13,10 +15,14 @@ pub struct Args {
   #[arg(short = 's', long)]
   report_stream_id: Option<i32>,
 
-  /// With this flag active, the reports generated by the `report_generator` will be exported to files instead of pastebin.
+  /// With this flag active, the reports generated by the `report_generator` will be exported to files in `file_reports` instead of the configured report sinks.
   #[arg(short = 'f', long = "file_export")]
   generate_file_reports: bool,
 
+  /// The format to write reports in. One of `text`, `json`, `markdown`, `html`, or `csv`. CSV creates a file for each table in a report.
+  #[arg(long = "format", default_value = "text")]
+  report_format: ReportFormat,
+
   /// Sets which month to generate the donator ranking report for.
   #[arg(short = 'm', long)]
   donation_rank_month: Option<usize>,
@@ -24,11 +30,12 @@ pub struct Args {
   #[arg(short = 'y', long)]
   donation_rank_year: Option<usize>,
 
-  /// Sets how long in a stream to generate a report for. Takes a duration like `1:30:00` for 1.5 hours into a stream to gather the data in a report to. (WIP. Doesn't do anything at the moment.)
-  #[arg(short = 't', long)]
-  stream_report_time: Option<DateTime<Utc>>,
+  /// Sets how long in a stream to generate a report for. Takes a duration like `1:30:00` for 1.5 hours into a stream to gather the data in a report to,
+  /// or a time like `2025-10-15T18:00:00Z` to gather the data up to.
+  #[arg(short = 't', long, value_parser = parse_stream_report_time)]
+  stream_report_time: Option<StreamReportTime>,
 
-  /// Creates additional files that reports on all data in the database.
+  /// Creates additional reports on all of the streamer's data in the database, both all-time and for each year.
   #[arg(long = "report_totals")]
   generate_report_totals: bool,
 
@@ -42,6 +49,28 @@ pub struct Args {
   #[arg(short = 'r', long)]
   chosen_report: ChosenReport,
 
+  /// Generates reports for every ended stream that doesn't have any yet, instead of a single stream.
+  /// Can be narrowed down with `--backfill_start`, `--backfill_end`, and `--backfill_streamers`.
+  #[arg(long)]
+  backfill: bool,
+  /// The first day streams being backfilled can start on, such as `2025-10-01`.
+  #[arg(long = "backfill_start")]
+  backfill_start_date: Option<NaiveDate>,
+  /// The last day streams being backfilled can start on, such as `2025-10-31`.
+  #[arg(long = "backfill_end")]
+  backfill_end_date: Option<NaiveDate>,
+  /// The streamers to backfill reports for, separated by commas.
+  #[arg(long, value_delimiter = ',')]
+  backfill_streamers: Vec<String>,
+  /// How many streams have their reports generated at once when backfilling.
+  #[arg(long, default_value_t = 4)]
+  backfill_concurrency: usize,
+
+  /// The login name of the chatter to generate a `user_recap` report for.
+  /// The recap covers the month from `-m`, or the year from `-y` if there's no month.
+  #[arg(long = "recap_user")]
+  recap_user: Option<String>,
+
   #[arg(long, value_parser = parse_date)]
   subathon_start_date: Option<DateTime<Utc>>,
   #[arg(long, value_parser = parse_date)]
@@ -65,6 +94,10 @@ impl Args {
     Self::get_or_set().generate_file_reports
   }
 
+  pub fn report_format() -> ReportFormat {
+    Self::get_or_set().report_format
+  }
+
   pub fn get_month() -> Option<usize> {
     Self::get_or_set().donation_rank_month
   }
@@ -73,7 +106,7 @@ impl Args {
     Self::get_or_set().donation_rank_year
   }
 
-  pub fn stream_report_time() -> Option<&'static DateTime<Utc>> {
+  pub fn stream_report_time() -> Option<&'static StreamReportTime> {
     Self::get_or_set().stream_report_time.as_ref()
   }
 
@@ -89,6 +122,28 @@ impl Args {
     Self::get_or_set().run_monthly_chat_ranking
   }
 
+  pub fn backfill() -> bool {
+    Self::get_or_set().backfill
+  }
+
+  pub fn backfill_filter() -> BackfillFilter {
+    let args = Self::get_or_set();
+
+    BackfillFilter {
+      date_start: args.backfill_start_date,
+      date_end: args.backfill_end_date,
+      streamer_names: args.backfill_streamers.clone(),
+    }
+  }
+
+  pub fn backfill_concurrency() -> usize {
+    Self::get_or_set().backfill_concurrency
+  }
+
+  pub fn recap_user() -> Option<&'static String> {
+    Self::get_or_set().recap_user.as_ref()
+  }
+
   pub fn subathon_start_date() -> Option<&'static DateTime<Utc>> {
     Self::get_or_set().subathon_start_date.as_ref()
   }
@@ -98,9 +153,82 @@ impl Args {
   }
 }
 
+/// How far into a stream a report is generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamReportTime {
+  /// The time since the stream started.
+  Offset(TimeDelta),
+  Cutoff(DateTime<Utc>),
+}
+
+impl StreamReportTime {
+  /// Returns the time the report stops at for a stream that started at the given time.
+  ///
+  /// None is returned for an offset if the stream's start time is unknown.
+  pub fn cutoff_time(&self, stream_start: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+    match self {
+      Self::Offset(offset) => stream_start.map(|stream_start| stream_start + *offset),
+      Self::Cutoff(cutoff_time) => Some(*cutoff_time),
+    }
+  }
+}
+
+/// Parses either a duration formatted as `h:mm:ss`, or an RFC 3339 time.
+fn parse_stream_report_time(s: &str) -> Result<StreamReportTime, String> {
+  if let Ok(cutoff_time) = DateTime::parse_from_rfc3339(s) {
+    return Ok(StreamReportTime::Cutoff(cutoff_time.to_utc()));
+  }
+
+  let invalid_time = || {
+    format!("Expected a duration like `1:30:00`, or a time like `2025-10-15T18:00:00Z`. Got `{s}`.")
+  };
+  let [hours, minutes, seconds] = s
+    .split(':')
+    .map(|value| value.parse::<i64>().ok().filter(|value| *value >= 0))
+    .collect::<Option<Vec<i64>>>()
+    .and_then(|values| values.try_into().ok())
+    .ok_or_else(invalid_time)?;
+
+  if minutes >= 60 || seconds >= 60 {
+    return Err(invalid_time());
+  }
+
+  TimeDelta::try_seconds(hours * 3600 + minutes * 60 + seconds)
+    .map(StreamReportTime::Offset)
+    .ok_or_else(invalid_time)
+}
+
 /// Custom parser for to convert "yyyy-mm-dd" string to DateTime<Utc> at midnight.
 fn parse_date(s: &str) -> Result<DateTime<Utc>, String> {
   NaiveDate::parse_from_str(s, "%Y-%m-%d")
     .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc())
     .map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::TimeZone;
+
+  #[test]
+  fn stream_report_time_accepts_durations_and_times() {
+    let stream_start = Utc.with_ymd_and_hms(2025, 10, 15, 16, 0, 0).unwrap();
+
+    let offset = parse_stream_report_time("1:30:00").unwrap();
+    let cutoff = parse_stream_report_time("2025-10-15T18:00:00Z").unwrap();
+
+    assert_eq!(offset, StreamReportTime::Offset(TimeDelta::minutes(90)));
+    assert_eq!(
+      offset.cutoff_time(Some(stream_start)),
+      Some(Utc.with_ymd_and_hms(2025, 10, 15, 17, 30, 0).unwrap())
+    );
+    assert_eq!(offset.cutoff_time(None), None);
+    assert_eq!(
+      cutoff.cutoff_time(None),
+      Some(Utc.with_ymd_and_hms(2025, 10, 15, 18, 0, 0).unwrap())
+    );
+    assert!(parse_stream_report_time("1:90:00").is_err());
+    assert!(parse_stream_report_time("90").is_err());
+    assert!(parse_stream_report_time("-1:00:00").is_err());
+  }
+}
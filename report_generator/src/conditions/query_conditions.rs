@@ -12,10 +12,14 @@ pub struct AppQueryConditions {
   pub subscriptions: Condition,
   pub raids: Condition,
   pub streams: Condition,
+  pub message_deletions: Condition,
+  pub hype_trains: Condition,
+  pub channel_notices: Condition,
 
   pub stream_id: Option<i32>,
   pub date_start: Option<DateTime<Utc>>,
   pub date_end: Option<DateTime<Utc>>,
+  pub date_cutoff: Option<DateTime<Utc>>,
   pub streamer_twitch_user_id: Option<i32>,
 }
 
@@ -28,16 +32,25 @@ impl AppQueryConditions {
       subscriptions: Condition::all().add(subscription_event::Column::StreamId.eq(Some(stream_id))),
       raids: Condition::all().add(raid::Column::StreamId.eq(Some(stream_id))),
       streams: Condition::all().add(stream::Column::Id.eq(stream_id)),
+      message_deletions: Condition::all()
+        .add(message_deletion::Column::StreamId.eq(Some(stream_id))),
+      hype_trains: Condition::all().add(hype_train::Column::StreamId.eq(Some(stream_id))),
+      channel_notices: Condition::all().add(channel_notice::Column::StreamId.eq(Some(stream_id))),
 
       stream_id: Some(stream_id),
       date_start: None,
       date_end: None,
+      date_cutoff: None,
       streamer_twitch_user_id: None,
     }
   }
 
-  pub fn from_month(month: Option<usize>, streamer_twitch_user_id: i32) -> Result<Self, AppError> {
-    let (start_date, end_date) = get_month_range(month)?;
+  pub fn from_month(
+    year: Option<i32>,
+    month: Option<usize>,
+    streamer_twitch_user_id: i32,
+  ) -> Result<Self, AppError> {
+    let (start_date, end_date) = get_month_range(year, month)?;
 
     Ok(Self {
       messages: Condition::all()
@@ -64,9 +77,22 @@ impl AppQueryConditions {
         .add(stream::Column::StartTimestamp.gte(start_date))
         .add(stream::Column::EndTimestamp.gte(end_date)),
 
+      message_deletions: Condition::all()
+        .add(message_deletion::Column::Timestamp.between(start_date, end_date))
+        .add(message_deletion::Column::ChannelId.eq(streamer_twitch_user_id)),
+
+      hype_trains: Condition::all()
+        .add(hype_train::Column::StartTimestamp.between(start_date, end_date))
+        .add(hype_train::Column::ChannelId.eq(streamer_twitch_user_id)),
+
+      channel_notices: Condition::all()
+        .add(channel_notice::Column::Timestamp.between(start_date, end_date))
+        .add(channel_notice::Column::ChannelId.eq(streamer_twitch_user_id)),
+
       stream_id: None,
       date_start: Some(start_date),
       date_end: Some(end_date),
+      date_cutoff: None,
       streamer_twitch_user_id: None,
     })
   }
@@ -94,11 +120,28 @@ impl AppQueryConditions {
   pub fn streams(&self) -> &Condition {
     &self.streams
   }
+
+  pub fn message_deletions(&self) -> &Condition {
+    &self.message_deletions
+  }
+
+  pub fn hype_trains(&self) -> &Condition {
+    &self.hype_trains
+  }
+
+  pub fn channel_notices(&self) -> &Condition {
+    &self.channel_notices
+  }
 }
 
-/// Returns the start and end times for the given month. The current month is used if `None` is passed in.
-pub fn get_month_range(month: Option<usize>) -> Result<(DateTime<Utc>, DateTime<Utc>), AppError> {
+/// Returns the start and end times for the given month of the given year.
+/// The current year and month are used if `None` is passed in for either.
+pub fn get_month_range(
+  year: Option<i32>,
+  month: Option<usize>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), AppError> {
   let current_time = Utc::now();
+  let year = year.unwrap_or(current_time.year());
 
   let month = if let Some(month) = month {
     if month == 0 || month > 12 {
@@ -110,7 +153,7 @@ pub fn get_month_range(month: Option<usize>) -> Result<(DateTime<Utc>, DateTime<
     current_time.month() as usize
   };
 
-  let Some(start_date) = NaiveDate::from_ymd_opt(current_time.year(), month as u32, 1) else {
+  let Some(start_date) = NaiveDate::from_ymd_opt(year, month as u32, 1) else {
     return Err(AppError::InvalidMonthValue(month as i32));
   };
   let Some(first_of_next_month) = start_date.checked_add_months(Months::new(1)) else {
@@ -125,6 +168,23 @@ pub fn get_month_range(month: Option<usize>) -> Result<(DateTime<Utc>, DateTime<
   Ok((start_date, first_of_next_month))
 }
 
+/// Returns the start of the given year, and the start of the year after it.
+pub fn get_year_range(year: i32) -> Result<(DateTime<Utc>, DateTime<Utc>), AppError> {
+  let (Some(start_date), Some(first_of_next_year)) = (
+    NaiveDate::from_ymd_opt(year, 1, 1),
+    NaiveDate::from_ymd_opt(year + 1, 1, 1),
+  ) else {
+    return Err(AppError::InvalidYearValue(year));
+  };
+
+  let start_date =
+    DateTime::<Utc>::from_naive_utc_and_offset(start_date.and_time(NaiveTime::MIN), Utc);
+  let first_of_next_year =
+    DateTime::<Utc>::from_naive_utc_and_offset(first_of_next_year.and_time(NaiveTime::MIN), Utc);
+
+  Ok((start_date, first_of_next_year))
+}
+
 #[cfg(test)]
 mod tests {
   use crate::conditions::query_conditions_builder::AppQueryConditionsBuilder;
@@ -139,18 +199,96 @@ mod tests {
     let expected_start = Utc.with_ymd_and_hms(now.year(), 2, 1, 0, 0, 0).unwrap();
     let expected_end = Utc.with_ymd_and_hms(now.year(), 3, 1, 0, 0, 0).unwrap();
 
-    let (start, end) = get_month_range(Some(month)).unwrap();
+    let (start, end) = get_month_range(None, Some(month)).unwrap();
 
     assert_eq!(start, expected_start);
     assert_eq!(end, expected_end);
   }
 
+  #[test]
+  fn get_month_range_crosses_into_the_next_year() {
+    let expected_start = Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap();
+    let expected_end = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+
+    let (start, end) = get_month_range(Some(2025), Some(12)).unwrap();
+
+    assert_eq!(start, expected_start);
+    assert_eq!(end, expected_end);
+  }
+
+  #[test]
+  fn get_year_range_ends_at_the_start_of_the_next_year() {
+    let expected_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let expected_end = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+
+    let (start, end) = get_year_range(2025).unwrap();
+
+    assert_eq!(start, expected_start);
+    assert_eq!(end, expected_end);
+  }
+
+  #[test]
+  fn year_conditions_cover_the_whole_year() {
+    let expected_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let expected_end = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let builder = AppQueryConditionsBuilder::new()
+      .set_year(2025)
+      .unwrap()
+      .set_streamer_twitch_user_id(400);
+
+    let expected_condition = Condition::all()
+      .add(donation_event::Column::Timestamp.between(expected_start, expected_end))
+      .add(donation_event::Column::DonationReceiverTwitchUserId.eq(400));
+
+    assert_eq!(builder.donation_event(), expected_condition);
+  }
+
+  #[test]
+  fn month_range_uses_the_given_year() {
+    let expected_start = Utc.with_ymd_and_hms(2024, 11, 1, 0, 0, 0).unwrap();
+    let expected_end = Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap();
+    let builder = AppQueryConditionsBuilder::new()
+      .set_month_range_in_year(2024, 11, 12)
+      .unwrap();
+
+    let expected_condition =
+      Condition::all().add(stream_message::Column::Timestamp.between(expected_start, expected_end));
+
+    assert_eq!(builder.stream_message(), expected_condition);
+    assert!(AppQueryConditionsBuilder::new()
+      .set_month_range_in_year(2024, 12, 13)
+      .is_err());
+  }
+
+  #[test]
+  fn date_range_includes_the_whole_end_date() {
+    let expected_start = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+    let expected_end = Utc.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap();
+    let builder = AppQueryConditionsBuilder::new()
+      .set_date_range(
+        NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+        NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+      )
+      .unwrap();
+
+    let expected_condition =
+      Condition::all().add(raid::Column::Timestamp.between(expected_start, expected_end));
+
+    assert_eq!(builder.raid(), expected_condition);
+    assert!(AppQueryConditionsBuilder::new()
+      .set_date_range(
+        NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+      )
+      .is_err());
+  }
+
   fn get_expected_datetime_range(
     start_month: usize,
     end_month: usize,
   ) -> (DateTime<Utc>, DateTime<Utc>) {
-    let (start_date, _) = get_month_range(Some(start_month)).unwrap();
-    let (end_date, _) = get_month_range(Some(end_month)).unwrap();
+    let (start_date, _) = get_month_range(None, Some(start_month)).unwrap();
+    let (end_date, _) = get_month_range(None, Some(end_month)).unwrap();
     (start_date, end_date)
   }
 
@@ -346,6 +484,61 @@ mod tests {
     assert_eq!(condition, expected_condition);
   }
 
+  #[test]
+  fn cutoff_time_excludes_everything_after_it() {
+    let cutoff_time = Utc.with_ymd_and_hms(2025, 10, 15, 18, 0, 0).unwrap();
+    let builder = AppQueryConditionsBuilder::new()
+      .set_stream_id(900)
+      .set_cutoff_time(cutoff_time);
+
+    assert_eq!(
+      builder.stream_message(),
+      Condition::all()
+        .add(stream_message::Column::StreamId.eq(Some(900)))
+        .add(stream_message::Column::Timestamp.lte(cutoff_time))
+    );
+    assert_eq!(
+      builder.donation_event(),
+      Condition::all()
+        .add(donation_event::Column::StreamId.eq(Some(900)))
+        .add(donation_event::Column::Timestamp.lte(cutoff_time))
+    );
+    assert_eq!(
+      builder.user_timeout(),
+      Condition::all()
+        .add(user_timeout::Column::StreamId.eq(Some(900)))
+        .add(user_timeout::Column::Timestamp.lte(cutoff_time))
+    );
+    assert_eq!(
+      builder.raid(),
+      Condition::all()
+        .add(raid::Column::StreamId.eq(Some(900)))
+        .add(raid::Column::Timestamp.lte(cutoff_time))
+    );
+  }
+
+  #[test]
+  fn cutoff_time_is_kept_when_copying_conditions() {
+    let cutoff_time = Utc.with_ymd_and_hms(2025, 10, 15, 18, 0, 0).unwrap();
+    let query_conditions = AppQueryConditionsBuilder::new()
+      .set_stream_id(900)
+      .set_cutoff_time(cutoff_time)
+      .build()
+      .unwrap();
+
+    let copied_query_conditions =
+      AppQueryConditionsBuilder::copy_from_existing_query_conditions(&query_conditions)
+        .wipe_stream_id()
+        .build()
+        .unwrap();
+
+    assert_eq!(copied_query_conditions.date_cutoff, Some(cutoff_time));
+    assert_eq!(
+      copied_query_conditions.raids,
+      Condition::all().add(raid::Column::Timestamp.lte(cutoff_time))
+    );
+  }
+
   #[test]
   fn test_raid_no_conditions() {
     let builder = AppQueryConditionsBuilder::new();
@@ -1,4 +1,4 @@
-use crate::conditions::query_conditions::AppQueryConditions;
+use crate::conditions::query_conditions::{get_year_range, AppQueryConditions};
 use crate::errors::AppError;
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
 use entities::*;
@@ -39,6 +39,11 @@ macro_rules! generate_condition_getter {
           condition =
             condition.add($condition_module::Column::$timestamp_column_definition.between(start_time, end_time))
         }
+
+        if let Some(cutoff_time) = self.cutoff_time {
+          condition =
+            condition.add($condition_module::Column::$timestamp_column_definition.lte(cutoff_time))
+        }
       )?
 
       $(
@@ -58,6 +63,8 @@ pub struct AppQueryConditionsBuilder {
   stream_id: Option<i32>,
   start_time: Option<DateTime<Utc>>,
   end_time: Option<DateTime<Utc>>,
+  /// Nothing after this time is included, regardless of the time range.
+  cutoff_time: Option<DateTime<Utc>>,
   streamer_twitch_user_id: Option<i32>,
 }
 
@@ -71,6 +78,7 @@ impl AppQueryConditionsBuilder {
       stream_id: query_conditions.stream_id,
       start_time: query_conditions.date_start,
       end_time: query_conditions.date_end,
+      cutoff_time: query_conditions.date_cutoff,
       streamer_twitch_user_id: query_conditions.streamer_twitch_user_id,
     }
   }
@@ -105,7 +113,25 @@ impl AppQueryConditionsBuilder {
     Ok(self)
   }
 
-  pub fn set_month_range(mut self, start_month: i32, end_month: i32) -> Result<Self, AppError> {
+  /// Narrows every condition to only include what happened up to the given time.
+  pub fn set_cutoff_time(mut self, cutoff_time: DateTime<Utc>) -> Self {
+    self.cutoff_time = Some(cutoff_time);
+
+    self
+  }
+
+  /// Sets the time range to the start of both months in the current year.
+  pub fn set_month_range(self, start_month: i32, end_month: i32) -> Result<Self, AppError> {
+    self.set_month_range_in_year(Utc::now().year(), start_month, end_month)
+  }
+
+  /// Sets the time range to the start of both months in the given year.
+  pub fn set_month_range_in_year(
+    mut self,
+    year: i32,
+    start_month: i32,
+    end_month: i32,
+  ) -> Result<Self, AppError> {
     if start_month > end_month {
       return Err(AppError::InvalidQueryDateConditions {
         start: start_month,
@@ -120,16 +146,13 @@ impl AppQueryConditionsBuilder {
       return Err(AppError::InvalidMonthValue(end_month));
     }
 
-    let current_time = Utc::now();
-
-    let Some(start_date) = NaiveDate::from_ymd_opt(current_time.year(), start_month as u32, 1)
-    else {
+    let Some(start_date) = NaiveDate::from_ymd_opt(year, start_month as u32, 1) else {
       return Err(AppError::InvalidMonthValue(start_month));
     };
     let start_date =
       DateTime::<Utc>::from_naive_utc_and_offset(start_date.and_time(NaiveTime::MIN), Utc);
 
-    let Some(end_date) = NaiveDate::from_ymd_opt(current_time.year(), end_month as u32, 1) else {
+    let Some(end_date) = NaiveDate::from_ymd_opt(year, end_month as u32, 1) else {
       return Err(AppError::InvalidMonthValue(end_month));
     };
     let end_date =
@@ -141,6 +164,40 @@ impl AppQueryConditionsBuilder {
     Ok(self)
   }
 
+  /// Sets the time range to cover the entirety of the given year.
+  pub fn set_year(self, year: i32) -> Result<Self, AppError> {
+    let (start_time, end_time) = get_year_range(year)?;
+
+    self.set_time_range(start_time, end_time)
+  }
+
+  /// Sets the time range to cover every day from the start date through to the end date.
+  pub fn set_date_range(
+    self,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+  ) -> Result<Self, AppError> {
+    let start_time =
+      DateTime::<Utc>::from_naive_utc_and_offset(start_date.and_time(NaiveTime::MIN), Utc);
+    let end_time =
+      DateTime::<Utc>::from_naive_utc_and_offset(end_date.and_time(NaiveTime::MIN), Utc);
+
+    if start_time > end_time {
+      return Err(AppError::EndTimeIsOlderThanStartTime {
+        start_time,
+        end_time,
+      });
+    }
+
+    let Some(day_after_end_date) = end_date.succ_opt() else {
+      return Err(AppError::InvalidYearValue(end_date.year()));
+    };
+    let end_time =
+      DateTime::<Utc>::from_naive_utc_and_offset(day_after_end_date.and_time(NaiveTime::MIN), Utc);
+
+    self.set_time_range(start_time, end_time)
+  }
+
   pub fn set_streamer_twitch_user_id(mut self, id: i32) -> Self {
     self.streamer_twitch_user_id = Some(id);
 
@@ -155,10 +212,14 @@ impl AppQueryConditionsBuilder {
       subscriptions: self.subscription_event(),
       raids: self.raid(),
       streams: self.stream(),
+      message_deletions: self.message_deletion(),
+      hype_trains: self.hype_train(),
+      channel_notices: self.channel_notice(),
 
       stream_id: self.stream_id,
       date_start: self.start_time,
       date_end: self.end_time,
+      date_cutoff: self.cutoff_time,
       streamer_twitch_user_id: self.streamer_twitch_user_id,
     })
   }
@@ -198,6 +259,27 @@ impl AppQueryConditionsBuilder {
     get_user_column: TwitchUserId,
   }
 
+  generate_condition_getter! {
+    module: message_deletion,
+    get_stream_column: StreamId,
+    get_timestamp_column: Timestamp,
+    get_user_column: ChannelId,
+  }
+
+  generate_condition_getter! {
+    module: hype_train,
+    get_stream_column: StreamId,
+    get_timestamp_column: StartTimestamp,
+    get_user_column: ChannelId,
+  }
+
+  generate_condition_getter! {
+    module: channel_notice,
+    get_stream_column: StreamId,
+    get_timestamp_column: Timestamp,
+    get_user_column: ChannelId,
+  }
+
   fn stream(&self) -> sea_orm::Condition {
     let mut condition = sea_orm::Condition::all();
 
@@ -207,6 +289,10 @@ impl AppQueryConditionsBuilder {
         .add(stream::Column::EndTimestamp.lte(end_time))
     }
 
+    if let Some(cutoff_time) = self.cutoff_time {
+      condition = condition.add(stream::Column::StartTimestamp.lte(cutoff_time));
+    }
+
     if let Some(streamer_twitch_user_id) = self.streamer_twitch_user_id {
       condition = condition.add(stream::Column::TwitchUserId.eq(streamer_twitch_user_id));
     }
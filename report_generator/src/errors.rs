@@ -23,31 +23,15 @@ pub enum AppError {
   #[error("Failed to generate a pastebin. Reason: {:?}", .0)]
   IncorrectPastebinResponse(String),
 
-  #[error(
-    "Could not convert currency rates. Missing API key for https://app.exchangerate-api.com/"
-  )]
-  MissingEchangeRateApiKey,
-
   #[error("Received an unknown response body structure when querying. Body location: {:?}", .0)]
   UnknownResponseBody(&'static str),
 
-  #[error("Attempted to retrieve currency exchange rates, but received an errored response. Error code: {:?}", .0)]
-  FailedToRetrieveCurrenyExchangeRates(StatusCode),
-
-  #[error(
-    "Failed to convert currency from {} to {} because {} didn't exist.",
-    from,
-    to,
-    to
-  )]
-  FailedToFindCurrencyValueInConversionRates { from: String, to: String },
-
-  #[error("Failed to convert json number. Value: {:?}", .0)]
-  FailedToConvertJsonNumber(serde_json::Number),
-
   #[error("Attempted to generate a report for donation rankings with an invalid month of {:?}", .0)]
   InvalidMonthValue(i32),
 
+  #[error("Attempted to generate a report for an invalid year of {:?}", .0)]
+  InvalidYearValue(i32),
+
   #[error("Found no donations between given date range {start_date}-{end_date}")]
   NoDonationsRankings {
     start_date: chrono::DateTime<chrono::Utc>,
@@ -60,6 +44,22 @@ pub enum AppError {
   #[error("Attempted to upload to pastebin without an API key.")]
   MissingPastebinApiKey,
 
+  #[error("{:?} reports can't be backfilled.", .0)]
+  ReportCannotBeBackfilled(crate::reports::chosen_report::ChosenReport),
+
+  #[error("None of the reports for stream {} could be sent.", .0)]
+  NoReportsWereSent(i32),
+
+  #[error("Failed to send a report to {sink}. Status: {status} | Body: {body}")]
+  ReportSinkRequestFailed {
+    sink: &'static str,
+    status: StatusCode,
+    body: String,
+  },
+
+  #[error("Invalid URL for a report sink: {:?}", .0)]
+  InvalidReportSinkUrl(String),
+
   #[error("Invalid query date range conditions. start: {} | end: {}", start, end)]
   InvalidQueryDateConditions { start: i32, end: i32 },
 
@@ -74,4 +74,19 @@ pub enum AppError {
 
   #[error("Tried to generate subathon report without a subathon start time.")]
   MissingSubathonStartTime,
+
+  #[error("Tried to generate a user recap without a user. Pass one in with `--recap_user`.")]
+  MissingRecapUser,
+
+  #[error("Could not find the user {:?}.", .0)]
+  UserNotFound(String),
+
+  #[error("{}", .0)]
+  EntityExtensionError(#[from] entity_extensions::errors::EntityExtensionError),
+
+  #[error("Invalid exchange rate `{record}`. Reason: {reason}")]
+  InvalidExchangeRateRecord { record: String, reason: String },
+
+  #[error("Exchange rates can only be imported from .csv or .json files. Got {:?}", .0)]
+  UnknownExchangeRateFileFormat(std::path::PathBuf),
 }
@@ -0,0 +1,170 @@
+use crate::errors::AppError;
+use chrono::NaiveDate;
+use entities::exchange_rate;
+use sea_orm::ActiveValue::Set;
+use serde::Deserialize;
+use std::path::Path;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const CSV_HEADER: [&str; 4] = ["date", "from", "to", "rate"];
+
+/// A rate from one currency to another on a date, as read from an import file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ExchangeRateRecord {
+  /// Formatted as `YYYY-MM-DD`.
+  pub date: String,
+  pub from: String,
+  pub to: String,
+  pub rate: f64,
+}
+
+impl ExchangeRateRecord {
+  pub fn into_active_model(self) -> Result<exchange_rate::ActiveModel, AppError> {
+    let invalid_record = |reason: String| AppError::InvalidExchangeRateRecord {
+      record: format!("{self:?}"),
+      reason,
+    };
+
+    let date = NaiveDate::parse_from_str(&self.date, DATE_FORMAT)
+      .map_err(|error| invalid_record(error.to_string()))?;
+
+    for currency in [&self.from, &self.to] {
+      if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(invalid_record(format!(
+          "{currency:?} is not a 3 letter currency code."
+        )));
+      }
+    }
+
+    if !self.rate.is_finite() || self.rate <= 0.0 {
+      return Err(invalid_record("The rate must be above 0.".to_string()));
+    }
+
+    Ok(exchange_rate::ActiveModel {
+      date: Set(date),
+      from_currency: Set(self.from.to_uppercase()),
+      to_currency: Set(self.to.to_uppercase()),
+      rate: Set(self.rate),
+      ..Default::default()
+    })
+  }
+}
+
+/// Reads the rates from a `.csv` or `.json` file.
+///
+/// CSV files have a `date,from,to,rate` header. JSON files are an array of objects with the same fields.
+pub fn read_exchange_rate_file(file_path: &Path) -> Result<Vec<ExchangeRateRecord>, AppError> {
+  let file_contents = std::fs::read_to_string(file_path)?;
+  let extension = file_path
+    .extension()
+    .and_then(|extension| extension.to_str())
+    .map(str::to_lowercase);
+
+  match extension.as_deref() {
+    Some("csv") => parse_csv(&file_contents),
+    Some("json") => serde_json::from_str(&file_contents).map_err(Into::into),
+    _ => Err(AppError::UnknownExchangeRateFileFormat(
+      file_path.to_path_buf(),
+    )),
+  }
+}
+
+fn parse_csv(file_contents: &str) -> Result<Vec<ExchangeRateRecord>, AppError> {
+  let mut lines = file_contents
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty());
+
+  let Some((_, header)) = lines.next() else {
+    return Ok(vec![]);
+  };
+  let header: Vec<String> = header
+    .split(',')
+    .map(|column| column.trim().to_lowercase())
+    .collect();
+
+  if header != CSV_HEADER {
+    return Err(AppError::InvalidExchangeRateRecord {
+      record: header.join(","),
+      reason: format!("Expected the header `{}`.", CSV_HEADER.join(",")),
+    });
+  }
+
+  lines
+    .map(|(line_index, line)| {
+      let invalid_line = |reason: String| AppError::InvalidExchangeRateRecord {
+        record: format!("line {}: {line}", line_index + 1),
+        reason,
+      };
+
+      let [date, from, to, rate] = line
+        .split(',')
+        .map(str::trim)
+        .collect::<Vec<&str>>()
+        .try_into()
+        .map_err(|_| invalid_line("Expected 4 columns.".to_string()))?;
+      let rate = rate
+        .parse()
+        .map_err(|error: std::num::ParseFloatError| invalid_line(error.to_string()))?;
+
+      Ok(ExchangeRateRecord {
+        date: date.to_owned(),
+        from: from.to_owned(),
+        to: to.to_owned(),
+        rate,
+      })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn record(date: &str, from: &str, to: &str, rate: f64) -> ExchangeRateRecord {
+    ExchangeRateRecord {
+      date: date.into(),
+      from: from.into(),
+      to: to.into(),
+      rate,
+    }
+  }
+
+  #[test]
+  fn csv_files_are_read_by_header() {
+    let file_contents =
+      "date,from,to,rate\n2025-03-01,USD,GBP,0.79\n\n2025-03-02, eur , gbp , 0.83\n";
+
+    assert_eq!(
+      parse_csv(file_contents).unwrap(),
+      vec![
+        record("2025-03-01", "USD", "GBP", 0.79),
+        record("2025-03-02", "eur", "gbp", 0.83),
+      ]
+    );
+    assert!(parse_csv("day,from,to,rate\n2025-03-01,USD,GBP,0.79").is_err());
+    assert!(parse_csv("date,from,to,rate\n2025-03-01,USD,GBP").is_err());
+  }
+
+  #[test]
+  fn records_are_validated() {
+    let active_model = record("2025-03-01", "usd", "gbp", 0.79)
+      .into_active_model()
+      .unwrap();
+
+    assert_eq!(active_model.from_currency, Set("USD".to_string()));
+    assert_eq!(
+      active_model.date,
+      Set(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap())
+    );
+    assert!(record("01/03/2025", "USD", "GBP", 0.79)
+      .into_active_model()
+      .is_err());
+    assert!(record("2025-03-01", "US$", "GBP", 0.79)
+      .into_active_model()
+      .is_err());
+    assert!(record("2025-03-01", "USD", "GBP", 0.0)
+      .into_active_model()
+      .is_err());
+  }
+}
@@ -0,0 +1,158 @@
+use crate::errors::AppError;
+use crate::upload_reports::SentReport;
+use chrono::{DateTime, TimeDelta, Utc};
+use entities::sea_orm_active_enums::ReportRunStatus;
+use entities::{generated_report, report_run};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+
+/// How long a run can be pending before it's treated as abandoned, such as by the tracker restarting mid run.
+pub const STALE_REPORT_RUN_TIMEOUT: TimeDelta = TimeDelta::hours(1);
+
+/// Claims the stream for a report run, returning false if another run already has it.
+///
+/// Only one claim can succeed since the run is stored with a unique stream ID.
+/// Streams whose last run failed, or that have been pending for longer than
+/// [`STALE_REPORT_RUN_TIMEOUT`], are claimed again.
+pub async fn claim_report_run(
+  stream_id: i32,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let report_run = report_run::ActiveModel {
+    stream_id: Set(stream_id),
+    status: Set(ReportRunStatus::Pending),
+    started_at: Set(Utc::now()),
+    ..Default::default()
+  };
+
+  match report_run::Entity::insert(report_run)
+    .exec_without_returning(database_connection)
+    .await
+  {
+    Ok(_) => Ok(true),
+    Err(error) if matches!(error.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+      reclaim_report_run(stream_id, database_connection).await
+    }
+    Err(error) => Err(error.into()),
+  }
+}
+
+/// Records how the stream's claimed report run ended.
+pub async fn finish_report_run(
+  stream_id: i32,
+  status: ReportRunStatus,
+  database_connection: &DatabaseConnection,
+) -> Result<(), AppError> {
+  report_run::Entity::update_many()
+    .col_expr(report_run::Column::Status, Expr::value(status))
+    .col_expr(report_run::Column::FinishedAt, Expr::value(Utc::now()))
+    .filter(report_run::Column::StreamId.eq(stream_id))
+    .exec(database_connection)
+    .await?;
+
+  Ok(())
+}
+
+/// Restarts a failed or stale run, which only one of any runs racing for it can do.
+async fn reclaim_report_run(
+  stream_id: i32,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let update_result = report_run::Entity::update_many()
+    .col_expr(
+      report_run::Column::Status,
+      Expr::value(ReportRunStatus::Pending),
+    )
+    .col_expr(report_run::Column::StartedAt, Expr::value(Utc::now()))
+    .col_expr(
+      report_run::Column::FinishedAt,
+      Expr::value(Option::<DateTime<Utc>>::None),
+    )
+    .filter(report_run::Column::StreamId.eq(stream_id))
+    .filter(
+      Condition::any()
+        .add(report_run::Column::Status.eq(ReportRunStatus::Failed))
+        .add(
+          Condition::all()
+            .add(report_run::Column::Status.eq(ReportRunStatus::Pending))
+            .add(report_run::Column::StartedAt.lt(Utc::now() - STALE_REPORT_RUN_TIMEOUT)),
+        ),
+    )
+    .exec(database_connection)
+    .await?;
+
+  Ok(update_result.rows_affected == 1)
+}
+
+/// Records where each report for a stream was sent, or why it couldn't be sent to a sink.
+pub async fn record_sent_reports(
+  stream_id: i32,
+  sent_reports: &[SentReport],
+  database_connection: &DatabaseConnection,
+) -> Result<(), AppError> {
+  if sent_reports.is_empty() {
+    return Ok(());
+  }
+
+  let generated_report_active_models =
+    sent_reports
+      .iter()
+      .map(|sent_report| generated_report::ActiveModel {
+        stream_id: Set(stream_id),
+        report_name: Set(sent_report.report_name.clone()),
+        sink: Set(sent_report.sink.to_string()),
+        location: Set(sent_report.result.as_ref().ok().cloned()),
+        error: Set(sent_report.result.as_ref().err().map(ToString::to_string)),
+        timestamp: Set(Utc::now()),
+        ..Default::default()
+      });
+
+  generated_report::Entity::insert_many(generated_report_active_models)
+    .exec(database_connection)
+    .await?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn unclaimed_streams_are_claimed() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_exec_results([MockExecResult {
+        last_insert_id: 1,
+        rows_affected: 1,
+      }])
+      .into_connection();
+
+    assert!(claim_report_run(1, &mock_database).await.unwrap());
+  }
+
+  #[tokio::test]
+  async fn only_failed_or_stale_runs_are_reclaimed() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_exec_results([
+        MockExecResult {
+          last_insert_id: 0,
+          rows_affected: 1,
+        },
+        MockExecResult {
+          last_insert_id: 0,
+          rows_affected: 0,
+        },
+      ])
+      .into_connection();
+
+    assert!(reclaim_report_run(1, &mock_database).await.unwrap());
+    assert!(!reclaim_report_run(1, &mock_database).await.unwrap());
+
+    let transaction_log = mock_database.into_transaction_log();
+    let reclaim_query = &transaction_log[0].statements()[0].sql;
+
+    assert!(reclaim_query.contains(
+      "`report_run`.`status` = (?) OR (`report_run`.`status` = (?) AND `report_run`.`started_at` < ?)"
+    ));
+  }
+}
@@ -1,11 +1,15 @@
+pub mod backfill;
 pub mod clap;
 pub mod conditions;
-pub mod currency_exchangerate;
 pub mod errors;
+pub mod exchange_rate_file;
+pub mod generated_reports;
 pub mod logging;
 pub mod pastebin;
 pub mod query_result_models;
 pub mod report_builders;
+pub mod report_formats;
+pub mod report_sinks;
 pub mod reports;
 #[cfg(test)]
 pub mod testing_helper_methods;
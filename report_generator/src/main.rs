@@ -1,9 +1,13 @@
 use database_connection::*;
 use entities::{stream, twitch_user};
 use entity_extensions::twitch_user::*;
+use report_generator::backfill::backfill_reports;
 use report_generator::clap::Args;
 use report_generator::conditions::query_conditions_builder::AppQueryConditionsBuilder;
+use report_generator::errors::AppError;
+use report_generator::report_sinks::get_report_sinks;
 use report_generator::reports::chosen_report::ChosenReport;
+use report_generator::reports::report_options::ReportOptions;
 use report_generator::reports::subathon_points::get_points_for_subathon;
 use report_generator::reports::*;
 use report_generator::upload_reports::upload_reports;
@@ -13,19 +17,43 @@ use sea_orm::*;
 async fn main() {
   report_generator::logging::setup_logging_config().unwrap();
 
+  if Args::backfill() {
+    run_backfill().await;
+
+    return;
+  }
+
   let database_connection = get_database_connection().await;
   let stream = get_stream(database_connection).await;
 
-  let condition = AppQueryConditionsBuilder::new()
+  let mut condition_builder = AppQueryConditionsBuilder::new()
     .set_stream_id(stream.id)
-    .set_streamer_twitch_user_id(stream.twitch_user_id)
-    .build()
-    .unwrap();
+    .set_streamer_twitch_user_id(stream.twitch_user_id);
+
+  if let Some(stream_report_time) = Args::stream_report_time() {
+    let Some(cutoff_time) = stream_report_time.cutoff_time(stream.start_timestamp) else {
+      tracing::error!(
+        "Stream {} has no start time to offset the report time from.",
+        stream.id
+      );
+
+      std::process::exit(1);
+    };
+
+    tracing::info!("Only reporting on data up to {cutoff_time}.");
+
+    condition_builder = condition_builder.set_cutoff_time(cutoff_time);
+  }
+
+  let condition = condition_builder.build().unwrap();
+  let report_options = ReportOptions::from_args();
 
   let generate_reports_result = match Args::chosen_report() {
-    ChosenReport::Basic => basic_reports::generate_reports(condition, stream.twitch_user_id).await,
+    ChosenReport::Basic => {
+      basic_reports::generate_reports(condition, stream.twitch_user_id, &report_options).await
+    }
     ChosenReport::Subathon => {
-      subathon_reports::generate_reports(condition, stream.twitch_user_id).await
+      subathon_reports::generate_reports(condition, stream.twitch_user_id, &report_options).await
     }
     ChosenReport::CalculateSubathonPoints => {
       let points = get_points_for_subathon(stream.twitch_user_id)
@@ -36,10 +64,26 @@ async fn main() {
 
       std::process::exit(0);
     }
+    ChosenReport::UserRecap => match Args::recap_user() {
+      Some(recap_user) => {
+        user_recap_reports::generate_reports(stream.twitch_user_id, recap_user, &report_options)
+          .await
+      }
+      None => Err(AppError::MissingRecapUser),
+    },
   };
 
   match generate_reports_result {
-    Ok(reports) => {
+    Ok(mut reports) => {
+      if Args::generate_report_totals() {
+        match total_reports::generate_reports(stream.twitch_user_id).await {
+          Ok(total_reports) => reports.add_reports(total_reports),
+          Err(error) => {
+            tracing::error!("Failed to generate the total reports. Reason: {:?}", error)
+          }
+        }
+      }
+
       println!("\n\n");
 
       if let Err(error) = upload_reports(stream, reports).await {
@@ -52,6 +96,43 @@ async fn main() {
   }
 }
 
+/// Generates reports for each stream without any, then prints which streams succeeded and failed.
+async fn run_backfill() {
+  let backfill_result = backfill_reports(
+    Args::chosen_report(),
+    &Args::backfill_filter(),
+    &ReportOptions::from_args(),
+    Args::report_format(),
+    &get_report_sinks(),
+    Args::backfill_concurrency(),
+  )
+  .await;
+
+  let summary = match backfill_result {
+    Ok(summary) => summary,
+    Err(error) => {
+      tracing::error!("Failed to backfill reports. Reason: {:?}", error);
+
+      std::process::exit(1);
+    }
+  };
+
+  println!(
+    "\n\nBackfill finished. Generated: {} | Skipped: {} | Failed: {}",
+    summary.generated.len(),
+    summary.skipped.len(),
+    summary.failed.len()
+  );
+
+  for (stream_id, error) in &summary.failed {
+    println!("  Stream {stream_id} failed: {error}");
+  }
+
+  if !summary.failed.is_empty() {
+    std::process::exit(1);
+  }
+}
+
 /// Returns the latest stream for the streamer based on arguments given to the program.
 ///
 /// The stream id will take priority, then a streamer name will be checked.
@@ -0,0 +1,166 @@
+use crate::reports::report_section::{rows_to_lines, ReportSection, ReportTable};
+use crate::{conditions::query_conditions::AppQueryConditions, errors::AppError};
+use entities::{channel_notice, twitch_user};
+use sea_orm::*;
+
+const ANNOUNCEMENTS_HEADER: &str = "= Announcements =";
+const VIEWER_MILESTONES_HEADER: &str = "= Viewer Milestones =";
+const BITS_BADGE_TIERS_HEADER: &str = "= Bits Badge Tiers =";
+const UNKNOWN_USER_NAME: &str = "unknown user";
+
+const ANNOUNCEMENT_NOTICE_TYPE: &str = "announcement";
+const VIEWER_MILESTONE_NOTICE_TYPE: &str = "viewermilestone";
+const BITS_BADGE_TIER_NOTICE_TYPE: &str = "bitsbadgetier";
+
+pub async fn get_channel_notices_table(
+  query_conditions: &AppQueryConditions,
+  database_connection: &DatabaseConnection,
+) -> Result<ReportSection, AppError> {
+  tracing::info!("Building channel notices table.");
+
+  let channel_notices = channel_notice::Entity::find()
+    .join(
+      JoinType::LeftJoin,
+      channel_notice::Relation::TwitchUser1.def(),
+    )
+    .filter(query_conditions.channel_notices().clone())
+    .order_by_asc(channel_notice::Column::Timestamp)
+    .select_also(twitch_user::Entity)
+    .all(database_connection)
+    .await?;
+
+  Ok(generate_channel_notices_section(&channel_notices))
+}
+
+/// Builds a table for each notice type that has any notices.
+fn generate_channel_notices_section(
+  channel_notices: &[(channel_notice::Model, Option<twitch_user::Model>)],
+) -> ReportSection {
+  let sections = [
+    (
+      ANNOUNCEMENTS_HEADER,
+      ANNOUNCEMENT_NOTICE_TYPE,
+      announcement_line as fn(&channel_notice::Model) -> String,
+    ),
+    (
+      VIEWER_MILESTONES_HEADER,
+      VIEWER_MILESTONE_NOTICE_TYPE,
+      viewer_milestone_line,
+    ),
+    (
+      BITS_BADGE_TIERS_HEADER,
+      BITS_BADGE_TIER_NOTICE_TYPE,
+      bits_badge_tier_line,
+    ),
+  ];
+  let mut channel_notices_string = String::new();
+  let mut channel_notices_tables = vec![];
+
+  for (header, notice_type, notice_line) in sections {
+    let rows = channel_notices
+      .iter()
+      .filter(|(channel_notice, _)| channel_notice.notice_type == notice_type)
+      .map(|(channel_notice, user)| {
+        let login_name = user
+          .as_ref()
+          .map(|user| user.login_name.clone())
+          .unwrap_or(UNKNOWN_USER_NAME.to_string());
+
+        vec![login_name, notice_line(channel_notice)]
+      })
+      .collect::<Vec<Vec<String>>>();
+
+    if !rows.is_empty() {
+      channel_notices_string.push_str(&format!("{header}\n{}\n", rows_to_lines(&rows)));
+      channel_notices_tables.push(ReportTable::new(header, &["user", "notice"], rows));
+    }
+  }
+
+  ReportSection::new(channel_notices_string, channel_notices_tables)
+}
+
+fn announcement_line(channel_notice: &channel_notice::Model) -> String {
+  channel_notice.contents.clone().unwrap_or_default()
+}
+
+/// Formatted as `value category`, such as `5 watch-streak`.
+fn viewer_milestone_line(channel_notice: &channel_notice::Model) -> String {
+  format!(
+    "{} {}",
+    parameter(channel_notice, "value"),
+    parameter(channel_notice, "category")
+  )
+}
+
+fn bits_badge_tier_line(channel_notice: &channel_notice::Model) -> String {
+  format!("{} bits", parameter(channel_notice, "threshold"))
+}
+
+fn parameter<'a>(channel_notice: &'a channel_notice::Model, name: &str) -> &'a str {
+  channel_notice
+    .parameters
+    .as_ref()
+    .and_then(|parameters| parameters.get(name))
+    .and_then(|value| value.as_str())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::testing_helper_methods::timestamp_from_string;
+
+  fn notice(
+    notice_type: &str,
+    parameters: serde_json::Value,
+    contents: Option<&str>,
+  ) -> (channel_notice::Model, Option<twitch_user::Model>) {
+    (
+      channel_notice::Model {
+        id: 1,
+        timestamp: timestamp_from_string("1740956922774"),
+        channel_id: 1,
+        twitch_user_id: 3,
+        stream_id: Some(1),
+        notice_type: notice_type.into(),
+        parameters: Some(parameters),
+        contents: contents.map(Into::into),
+        origin_id: None,
+      },
+      Some(twitch_user::Model {
+        id: 3,
+        twitch_id: 128831052,
+        login_name: "linkthedot".into(),
+        display_name: "LinkTheDot".into(),
+      }),
+    )
+  }
+
+  #[test]
+  fn notices_are_grouped_by_type() {
+    let channel_notices = vec![
+      notice(
+        VIEWER_MILESTONE_NOTICE_TYPE,
+        serde_json::json!({ "category": "watch-streak", "value": "5" }),
+        None,
+      ),
+      notice(
+        ANNOUNCEMENT_NOTICE_TYPE,
+        serde_json::json!({ "color": "PRIMARY" }),
+        Some("glorp"),
+      ),
+      notice(
+        BITS_BADGE_TIER_NOTICE_TYPE,
+        serde_json::json!({ "threshold": "1000" }),
+        None,
+      ),
+    ];
+
+    let channel_notices_section = generate_channel_notices_section(&channel_notices);
+
+    assert_eq!(
+      channel_notices_section.text(),
+      "= Announcements =\nlinkthedot - glorp\n= Viewer Milestones =\nlinkthedot - 5 watch-streak\n= Bits Badge Tiers =\nlinkthedot - 1000 bits\n"
+    );
+  }
+}
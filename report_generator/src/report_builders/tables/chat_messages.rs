@@ -1,9 +1,10 @@
 use crate::conditions::query_conditions::AppQueryConditions;
 use crate::errors::AppError;
+use crate::reports::report_section::{ReportSection, ReportTable};
 use crate::EMOTE_DOMINANCE;
 use database_connection::get_database_connection;
-use entities::{emote_usage, stream_message, twitch_user};
-use messages_with_word_counts::{MessageWithWordCount, UserMessages};
+use entities::{bits_cheermote, donation_event, emote_usage, stream_message, twitch_user};
+use messages_with_word_counts::{count_words, MessageWithWordCount, UserMessages};
 use num_traits::cast::ToPrimitive;
 use ranking_table::*;
 use sea_orm::entity::prelude::Decimal;
@@ -30,7 +31,7 @@ const USER_TAG_INFO: &str = r#"After a user's ranking will be indicators for bot
 pub async fn get_messages_sent_ranking(
   query_conditions: &AppQueryConditions,
   ranking_row_limit: Option<usize>,
-) -> Result<(String, String), AppError> {
+) -> Result<(ReportSection, ReportSection), AppError> {
   let database_connection = get_database_connection().await;
   tracing::info!("Getting messages for message rankings.");
   let messages = stream_message::Entity::find()
@@ -38,13 +39,23 @@ pub async fn get_messages_sent_ranking(
     .all(database_connection)
     .await?;
   let messages: Vec<&stream_message::Model> = messages.iter().collect();
+  let cheermote_prefixes = get_cheermote_prefixes(query_conditions, database_connection).await?;
 
-  let rankings = calculate_rankings(messages, database_connection, ranking_row_limit).await?;
+  let rankings = calculate_rankings(
+    messages,
+    &cheermote_prefixes,
+    database_connection,
+    ranking_row_limit,
+  )
+  .await?;
 
   tracing::info!("Building chat ranking table strings.");
 
   let mut unfiltered_table = Table::new(rankings.all_messages);
   let mut filtered_table = Table::new(rankings.emote_filtered_messages);
+  let unfiltered_report_table = ReportTable::from_tabled("Chat Rankings", &unfiltered_table);
+  let filtered_report_table =
+    ReportTable::from_tabled("Emote Filtered Chat Rankings", &filtered_table);
 
   unfiltered_table.with(Style::markdown());
   filtered_table.with(Style::markdown());
@@ -60,11 +71,46 @@ pub async fn get_messages_sent_ranking(
     "{emote_dominance_info}\n{WORD_PERCENTAGE_INFO}\n\n{USER_TAG_INFO}\n\n{filtered_table}",
   );
 
-  Ok((unfiltered_table, filtered_table))
+  Ok((
+    ReportSection::new(unfiltered_table, vec![unfiltered_report_table]),
+    ReportSection::new(filtered_table, vec![filtered_report_table]),
+  ))
+}
+
+/// The cheermote prefixes used in each message, keyed by the message's origin ID.
+type CheermotePrefixes = HashMap<String, Vec<String>>;
+
+/// Gets the prefixes of the cheermotes used in bit donations, so the cheers can be left out of word counts.
+async fn get_cheermote_prefixes(
+  query_conditions: &AppQueryConditions,
+  database_connection: &DatabaseConnection,
+) -> Result<CheermotePrefixes, AppError> {
+  tracing::info!("Getting cheermotes for message rankings.");
+
+  let cheermotes = bits_cheermote::Entity::find()
+    .find_also_related(donation_event::Entity)
+    .filter(query_conditions.donations().clone())
+    .all(database_connection)
+    .await?;
+
+  Ok(cheermotes.into_iter().fold(
+    CheermotePrefixes::new(),
+    |mut cheermote_prefixes, (cheermote, donation)| {
+      if let Some(origin_id) = donation.and_then(|donation| donation.origin_id) {
+        cheermote_prefixes
+          .entry(origin_id)
+          .or_default()
+          .push(cheermote.prefix);
+      }
+
+      cheermote_prefixes
+    },
+  ))
 }
 
 async fn calculate_rankings(
   messages: Vec<&stream_message::Model>,
+  cheermote_prefixes: &CheermotePrefixes,
   database_connection: &DatabaseConnection,
   ranking_row_limit: Option<usize>,
 ) -> Result<ChatRankings, AppError> {
@@ -81,8 +127,14 @@ async fn calculate_rankings(
   for message in messages {
     let user_messages = chats_sent.entry(message.twitch_user_id).or_default();
 
+    let message_cheermote_prefixes = message
+      .origin_id
+      .as_ref()
+      .and_then(|origin_id| cheermote_prefixes.get(origin_id))
+      .map(Vec::as_slice)
+      .unwrap_or_default();
     let (word_count, is_emote_dominant_message) =
-      match is_emote_message(message, database_connection).await {
+      match is_emote_message(message, message_cheermote_prefixes, database_connection).await {
         Ok(Some(results)) => results,
         Ok(None) => continue,
         Err(error) => {
@@ -117,7 +169,7 @@ async fn calculate_rankings(
   tracing::info!("Sorting unfiltered chats sent.");
 
   unfiltered_chats_sent
-    .sort_by(|(_, lhs), (_, rhs)| rhs.all_messages.len().cmp(&lhs.all_messages.len()));
+    .sort_by_key(|(_, user_messages)| std::cmp::Reverse(user_messages.all_messages.len()));
 
   tracing::info!("Sorting and removing users with no messages from emote filtered chats sent.");
 
@@ -216,11 +268,12 @@ async fn calculate_rankings(
 }
 
 // Returns the real word count of the message and a bool if the message is emote dominant or not.
-// "Real word count" excludes the count of emotes used.
+// "Real word count" excludes the count of emotes used, and any bits cheered.
 //
 // Otherwise None is returned
 async fn is_emote_message(
   message: &stream_message::Model,
+  cheermote_prefixes: &[String],
   database_connection: &DatabaseConnection,
 ) -> Result<Option<(usize, bool)>, AppError> {
   let Some(contents) = &message.contents else {
@@ -231,10 +284,7 @@ async fn is_emote_message(
 
     return Ok(None);
   };
-  let word_count = contents
-    .split_whitespace()
-    .filter(|word| !word.is_empty())
-    .count() as f32;
+  let word_count = count_words(contents, cheermote_prefixes) as f32;
 
   let sum_usage_query = format!(
     "SELECT COALESCE(SUM({}), 0) AS total FROM {} WHERE {} = {}",
@@ -336,7 +386,7 @@ mod tests {
 
     let expected_chat_rankings = get_expected_chat_rankings();
 
-    let chat_rankings = calculate_rankings(messages, &mock_database, None)
+    let chat_rankings = calculate_rankings(messages, &HashMap::new(), &mock_database, None)
       .await
       .unwrap();
 
@@ -40,3 +40,38 @@ impl<'a> UserMessages<'a> {
     }
   }
 }
+
+/// Counts the words in a message, leaving out the bits cheered with any of the cheermote prefixes.
+///
+/// Prefixes are expected to be lowercase.
+pub fn count_words(contents: &str, cheermote_prefixes: &[String]) -> usize {
+  contents
+    .split_whitespace()
+    .filter(|word| !is_cheer(word, cheermote_prefixes))
+    .count()
+}
+
+fn is_cheer(word: &str, cheermote_prefixes: &[String]) -> bool {
+  let prefix = word.trim_end_matches(|character: char| character.is_ascii_digit());
+
+  prefix.len() != word.len()
+    && cheermote_prefixes
+      .iter()
+      .any(|cheermote_prefix| cheermote_prefix.eq_ignore_ascii_case(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cheers_are_not_counted_as_words() {
+    let cheermote_prefixes = vec!["cheer".to_string(), "corgo".to_string()];
+
+    assert_eq!(
+      count_words("Cheer100 great stream corgo50 Corgo", &cheermote_prefixes),
+      3
+    );
+    assert_eq!(count_words("Cheer100 great stream", &[]), 3);
+  }
+}
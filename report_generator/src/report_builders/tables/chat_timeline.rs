@@ -0,0 +1,154 @@
+use crate::reports::report_section::{rows_to_lines, ReportSection, ReportTable};
+use crate::{conditions::query_conditions::AppQueryConditions, errors::AppError};
+use entities::stream;
+use entity_extensions::chat_timeline::{ChatMomentKind, ChatTimeline};
+use sea_orm::*;
+
+const MOMENTS_HEADER: &str = "= Chat Moments =";
+const TIMELINE_HEADER: &str = "= Chat Timeline =";
+
+/// Builds the chat moments and per-minute timeline for the stream in the query conditions.
+///
+/// Returns an empty section if the conditions aren't for a stream, or the stream never started.
+pub async fn get_chat_timeline_table(
+  query_conditions: &AppQueryConditions,
+  database_connection: &DatabaseConnection,
+) -> Result<ReportSection, AppError> {
+  let Some(stream_id) = query_conditions.stream_id else {
+    return Ok(ReportSection::default());
+  };
+
+  tracing::info!("Building chat timeline.");
+
+  let Some(stream) = stream::Entity::find_by_id(stream_id)
+    .one(database_connection)
+    .await?
+  else {
+    return Err(AppError::FailedToFindStream(stream_id));
+  };
+
+  if stream.start_timestamp.is_none() {
+    return Ok(ReportSection::default());
+  }
+
+  let chat_timeline =
+    ChatTimeline::new(&stream, query_conditions.date_cutoff, database_connection).await?;
+
+  tracing::info!("Finished.");
+
+  Ok(build_chat_timeline_section(&chat_timeline))
+}
+
+fn build_chat_timeline_section(chat_timeline: &ChatTimeline) -> ReportSection {
+  if chat_timeline.minutes.is_empty() {
+    return ReportSection::default();
+  }
+
+  let moment_rows: Vec<Vec<String>> = chat_timeline
+    .moments
+    .iter()
+    .map(|moment| {
+      let kind = match &moment.kind {
+        ChatMomentKind::Messages => "Messages".to_string(),
+        ChatMomentKind::Emote(emote_name) => emote_name.clone(),
+        ChatMomentKind::Donations => "Donations".to_string(),
+      };
+
+      vec![
+        moment.offset(),
+        kind,
+        format!("{} (usual {:.0})", moment.peak, moment.baseline),
+      ]
+    })
+    .collect();
+  let timeline_rows = chat_timeline
+    .minutes
+    .iter()
+    .map(|minute| {
+      vec![
+        minute.minute.to_string(),
+        minute.messages.to_string(),
+        minute.unique_chatters.to_string(),
+        minute.emote_uses.to_string(),
+        minute.donation_events.to_string(),
+      ]
+    })
+    .collect();
+
+  let moments_list = if moment_rows.is_empty() {
+    "No spikes in chat activity.".to_string()
+  } else {
+    rows_to_lines(&moment_rows)
+  };
+
+  ReportSection::new(
+    format!("{MOMENTS_HEADER}\n{moments_list}"),
+    vec![
+      ReportTable::new(MOMENTS_HEADER, &["vod_offset", "kind", "peak"], moment_rows),
+      ReportTable::new(
+        TIMELINE_HEADER,
+        &[
+          "minute",
+          "messages",
+          "unique_chatters",
+          "emote_uses",
+          "donation_events",
+        ],
+        timeline_rows,
+      ),
+    ],
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use entity_extensions::chat_timeline::{ChatMoment, TimelineMinute};
+
+  #[test]
+  fn moments_are_listed_with_their_vod_offsets() {
+    let chat_timeline = ChatTimeline {
+      stream_id: 1,
+      stream_start: "2025-05-08T00:00:00Z".parse().unwrap(),
+      minutes: vec![
+        TimelineMinute {
+          minute: 0,
+          messages: 4,
+          unique_chatters: 2,
+          emote_uses: 1,
+          donation_events: 0,
+        },
+        TimelineMinute {
+          minute: 1,
+          messages: 60,
+          unique_chatters: 30,
+          emote_uses: 45,
+          donation_events: 0,
+        },
+      ],
+      moments: vec![
+        ChatMoment {
+          offset_seconds: 60,
+          kind: ChatMomentKind::Messages,
+          peak: 60,
+          baseline: 4.0,
+        },
+        ChatMoment {
+          offset_seconds: 60,
+          kind: ChatMomentKind::Emote("Kappa".into()),
+          peak: 45,
+          baseline: 1.0,
+        },
+      ],
+    };
+
+    let section = build_chat_timeline_section(&chat_timeline);
+
+    assert_eq!(
+      section.text(),
+      "= Chat Moments =\n0:01:00 - Messages - 60 (usual 4)\n0:01:00 - Kappa - 45 (usual 1)"
+    );
+    assert_eq!(section.tables()[0].title, "Chat Moments");
+    assert_eq!(section.tables()[1].rows.len(), 2);
+  }
+}
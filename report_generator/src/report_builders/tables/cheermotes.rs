@@ -0,0 +1,108 @@
+use crate::reports::report_section::{ReportSection, ReportTable};
+use crate::{conditions::query_conditions::AppQueryConditions, errors::AppError};
+use entities::{bits_cheermote, donation_event};
+use sea_orm::*;
+use std::collections::HashMap;
+
+const HEADER: &str = "= Most Used Cheermotes =";
+
+pub async fn get_top_cheermotes_table(
+  query_conditions: &AppQueryConditions,
+  database_connection: &DatabaseConnection,
+) -> Result<ReportSection, AppError> {
+  tracing::info!("Building most used cheermotes table.");
+
+  let cheermotes = bits_cheermote::Entity::find()
+    .inner_join(donation_event::Entity)
+    .filter(query_conditions.donations().clone())
+    .all(database_connection)
+    .await?;
+
+  if cheermotes.is_empty() {
+    return Ok(ReportSection::default());
+  }
+
+  let cheermote_rows = get_top_cheermotes_rows(&cheermotes);
+
+  Ok(ReportSection::new(
+    generate_top_cheermotes_string(&cheermote_rows),
+    vec![ReportTable::new(
+      HEADER,
+      &["rank", "prefix", "uses", "bits"],
+      cheermote_rows,
+    )],
+  ))
+}
+
+/// Ranks each cheermote prefix by how many times it was used, as rows of `rank, prefix, uses, bits`.
+fn get_top_cheermotes_rows(cheermotes: &[bits_cheermote::Model]) -> Vec<Vec<String>> {
+  // Contains the (uses, bits) of each prefix
+  let cheermote_totals: HashMap<&str, (usize, i64)> =
+    cheermotes
+      .iter()
+      .fold(HashMap::new(), |mut cheermote_totals, cheermote| {
+        let (uses, bits) = cheermote_totals.entry(&cheermote.prefix).or_default();
+        *uses += 1;
+        *bits += cheermote.amount as i64;
+
+        cheermote_totals
+      });
+  let mut cheermote_totals: Vec<(&str, (usize, i64))> = cheermote_totals.into_iter().collect();
+
+  cheermote_totals.sort_by(|(lhs_prefix, lhs_totals), (rhs_prefix, rhs_totals)| {
+    rhs_totals.cmp(lhs_totals).then(lhs_prefix.cmp(rhs_prefix))
+  });
+
+  cheermote_totals
+    .into_iter()
+    .enumerate()
+    .map(|(rank, (prefix, (uses, bits)))| {
+      vec![
+        (rank + 1).to_string(),
+        prefix.to_string(),
+        uses.to_string(),
+        bits.to_string(),
+      ]
+    })
+    .collect()
+}
+
+/// Formats each row of the cheermotes table as `rank: prefix - uses - bits`.
+fn generate_top_cheermotes_string(cheermote_rows: &[Vec<String>]) -> String {
+  let cheermote_lines = cheermote_rows
+    .iter()
+    .map(|row| format!("{}: {} - {} uses - {} bits", row[0], row[1], row[2], row[3]))
+    .collect::<Vec<String>>()
+    .join("\n");
+
+  format!("{HEADER}\n{cheermote_lines}\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn cheermote(prefix: &str, amount: i32) -> bits_cheermote::Model {
+    bits_cheermote::Model {
+      id: 1,
+      donation_event_id: 1,
+      prefix: prefix.into(),
+      amount,
+    }
+  }
+
+  #[test]
+  fn cheermotes_are_ranked_by_uses() {
+    let cheermotes = vec![
+      cheermote("corgo", 500),
+      cheermote("cheer", 100),
+      cheermote("cheer", 1),
+      cheermote("party", 100),
+    ];
+
+    assert_eq!(
+      generate_top_cheermotes_string(&get_top_cheermotes_rows(&cheermotes)),
+      "= Most Used Cheermotes =\n1: cheer - 2 uses - 101 bits\n2: corgo - 1 uses - 500 bits\n3: party - 1 uses - 100 bits\n"
+    );
+  }
+}
@@ -0,0 +1,193 @@
+use crate::reports::report_section::{rows_to_lines, ReportSection, ReportTable};
+use crate::{conditions::query_conditions::AppQueryConditions, errors::AppError};
+use entities::{message_deletion, stream_message, twitch_user};
+use sea_orm::*;
+use std::collections::HashMap;
+
+const DELETED_MESSAGES_HEADER: &str = "= Deleted Messages =";
+const CHAT_CLEARS_HEADER: &str = "= Chat Clears =";
+const UNKNOWN_USER_NAME: &str = "unknown user";
+
+pub async fn get_deleted_messages_table(
+  query_conditions: &AppQueryConditions,
+  database_connection: &DatabaseConnection,
+) -> Result<ReportSection, AppError> {
+  tracing::info!("Building deleted messages table.");
+
+  let (chat_clears, message_deletions): (Vec<_>, Vec<_>) = message_deletion::Entity::find()
+    .filter(query_conditions.message_deletions().clone())
+    .order_by_asc(message_deletion::Column::Timestamp)
+    .all(database_connection)
+    .await?
+    .into_iter()
+    .partition(|message_deletion| message_deletion.is_chat_clear == 1);
+
+  let deleted_messages = get_deleted_messages(&message_deletions, database_connection).await?;
+
+  let deleted_messages_rows = generate_deleted_messages_rows(message_deletions, deleted_messages);
+  let chat_clears_rows = generate_chat_clears_rows(chat_clears);
+
+  let mut deleted_messages_table_string = String::new();
+  let mut deleted_messages_tables = vec![];
+
+  if !deleted_messages_rows.is_empty() {
+    let deleted_messages_string = rows_to_lines(&deleted_messages_rows);
+
+    deleted_messages_table_string.push_str(&format!(
+      "{DELETED_MESSAGES_HEADER}\n{deleted_messages_string}\n"
+    ));
+    deleted_messages_tables.push(ReportTable::new(
+      DELETED_MESSAGES_HEADER,
+      &["user", "message"],
+      deleted_messages_rows,
+    ));
+  }
+
+  if !chat_clears_rows.is_empty() {
+    let chat_clears_string = rows_to_lines(&chat_clears_rows);
+
+    deleted_messages_table_string
+      .push_str(&format!("{CHAT_CLEARS_HEADER}\n{chat_clears_string}\n"));
+    deleted_messages_tables.push(ReportTable::new(
+      CHAT_CLEARS_HEADER,
+      &["time"],
+      chat_clears_rows,
+    ));
+  }
+
+  Ok(ReportSection::new(
+    deleted_messages_table_string,
+    deleted_messages_tables,
+  ))
+}
+
+/// Lists each deleted message as rows of `user, contents`.
+///
+/// The stored message is used where it exists. Otherwise the values sent with the deletion are used.
+fn generate_deleted_messages_rows(
+  message_deletions: Vec<message_deletion::Model>,
+  mut deleted_messages: HashMap<String, (stream_message::Model, twitch_user::Model)>,
+) -> Vec<Vec<String>> {
+  tracing::info!("Building deleted messages rows.");
+
+  message_deletions
+    .into_iter()
+    .map(|message_deletion| {
+      let stored_message = message_deletion
+        .target_origin_id
+        .as_ref()
+        .and_then(|target_origin_id| deleted_messages.remove(target_origin_id));
+
+      let (login_name, contents) = match stored_message {
+        Some((message, sender)) => (Some(sender.login_name), message.contents),
+        None => (message_deletion.login_name, message_deletion.contents),
+      };
+
+      vec![
+        login_name.unwrap_or(UNKNOWN_USER_NAME.to_string()),
+        contents.unwrap_or_default(),
+      ]
+    })
+    .collect()
+}
+
+fn generate_chat_clears_rows(chat_clears: Vec<message_deletion::Model>) -> Vec<Vec<String>> {
+  tracing::info!("Building chat clears rows.");
+
+  chat_clears
+    .into_iter()
+    .map(|chat_clear| {
+      vec![chat_clear
+        .timestamp
+        .format("%Y-%m-%d %H:%M:%S UTC")
+        .to_string()]
+    })
+    .collect()
+}
+
+/// Returns the stored messages for each deletion along with their sender, keyed by origin id.
+async fn get_deleted_messages(
+  message_deletions: &[message_deletion::Model],
+  database_connection: &DatabaseConnection,
+) -> Result<HashMap<String, (stream_message::Model, twitch_user::Model)>, AppError> {
+  tracing::info!("Getting deleted messages.");
+
+  let target_origin_ids: Vec<&str> = message_deletions
+    .iter()
+    .filter_map(|message_deletion| message_deletion.target_origin_id.as_deref())
+    .collect();
+
+  if target_origin_ids.is_empty() {
+    return Ok(HashMap::new());
+  }
+
+  let deleted_messages = stream_message::Entity::find()
+    .join(
+      JoinType::LeftJoin,
+      stream_message::Relation::TwitchUser1.def(),
+    )
+    .filter(stream_message::Column::OriginId.is_in(target_origin_ids))
+    .select_also(twitch_user::Entity)
+    .all(database_connection)
+    .await?;
+
+  Ok(
+    deleted_messages
+      .into_iter()
+      .filter_map(|(message, maybe_sender)| {
+        let Some(sender) = maybe_sender else {
+          tracing::error!("Failed to find the sender of message of ID {}", message.id);
+          return None;
+        };
+
+        Some((message.origin_id.clone()?, (message, sender)))
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::testing_helper_methods::{generate_message, timestamp_from_string};
+
+  fn deletion(target_origin_id: &str, login_name: &str, contents: &str) -> message_deletion::Model {
+    message_deletion::Model {
+      id: 1,
+      timestamp: timestamp_from_string("1740956922774"),
+      channel_id: 1,
+      stream_id: Some(1),
+      target_origin_id: Some(target_origin_id.into()),
+      login_name: Some(login_name.into()),
+      contents: Some(contents.into()),
+      is_chat_clear: 0,
+    }
+  }
+
+  #[test]
+  fn stored_messages_are_preferred_over_deletion_values() {
+    let sender = twitch_user::Model {
+      id: 3,
+      twitch_id: 128831052,
+      login_name: "linkthedot".into(),
+      display_name: "LinkTheDot".into(),
+    };
+    let mut stored_message = generate_message(1, 3, "glorp");
+    stored_message.origin_id = Some("stored".into());
+    let deleted_messages = HashMap::from([("stored".to_string(), (stored_message, sender))]);
+    let message_deletions = vec![
+      deletion("stored", "oldname", "waaa"),
+      deletion("missing", "fallenshadow", "glorpass"),
+    ];
+
+    let deleted_messages_string = rows_to_lines(&generate_deleted_messages_rows(
+      message_deletions,
+      deleted_messages,
+    ));
+
+    assert_eq!(
+      deleted_messages_string,
+      "linkthedot - glorp\nfallenshadow - glorpass"
+    );
+  }
+}
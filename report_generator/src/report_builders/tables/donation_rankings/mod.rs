@@ -1,8 +1,12 @@
 use crate::errors::AppError;
+use crate::reports::report_section::ReportSection;
+use app_config::AppConfig;
 use chrono::*;
 use database_connection::get_database_connection;
 use donator_identifier::DonatorIdentifier;
 use entities::*;
+use entity_extensions::exchange_rate::CurrencyConverter;
+use sea_orm::prelude::Decimal;
 use sea_orm::*;
 use sea_orm_active_enums::EventType;
 use top_donators::*;
@@ -16,16 +20,20 @@ mod top_donators_entry;
 mod top_donators_tables;
 
 /// The value of each sub tier in order of tier, and in USD.
-const SUB_TIER_VALUE: [f32; 3] = [5.99, 9.99, 24.99];
-const REPORT_INFO: &str =
-  r#"This report contains the donation rankings for streamer {STREAMER} from {START} to {END}."#;
+const SUB_TIER_VALUE: [Decimal; 3] = [
+  Decimal::from_parts(599, 0, 0, false, 2),
+  Decimal::from_parts(999, 0, 0, false, 2),
+  Decimal::from_parts(2499, 0, 0, false, 2),
+];
+const REPORT_INFO: &str = r#"This report contains the donation rankings for streamer {STREAMER} from {START} to {END}.
+Donation amounts are in {CURRENCY}."#;
 
 #[instrument(skip_all)]
 pub async fn get_donation_rankings_for_streamer_and_date(
   streamer_id: i32,
   start_date: DateTime<Utc>,
   end_date: DateTime<Utc>,
-) -> Result<String, AppError> {
+) -> Result<ReportSection, AppError> {
   let database_connection = get_database_connection().await;
 
   tracing::info!("Generating donation rankings from {start_date} to {end_date}.");
@@ -50,12 +58,16 @@ pub async fn get_donation_rankings_for_streamer_and_date(
   let mut report_string = REPORT_INFO
     .replace("{STREAMER}", &streamer_name)
     .replace("{START}", &start_date.to_string())
-    .replace("{END}", &end_date.to_string());
+    .replace("{END}", &end_date.to_string())
+    .replace("{CURRENCY}", AppConfig::reporting_currency());
 
   report_string.push_str("\n\n");
   report_string.push_str(&donator_ranking_tables.to_string());
 
-  Ok(report_string)
+  Ok(ReportSection::new(
+    report_string,
+    donator_ranking_tables.report_tables().to_vec(),
+  ))
 }
 
 async fn get_top_donators(
@@ -78,6 +90,7 @@ async fn get_top_donators(
     return Ok(None);
   }
 
+  let mut currency_converter = CurrencyConverter::new(AppConfig::reporting_currency());
   let mut donators = TopDonators::default();
 
   tracing::info!("Building top donators list.");
@@ -118,13 +131,26 @@ async fn get_top_donators(
         }
       }
 
-      EventType::StreamlabsDonation => {
-        let amount = donators
-          .streamlabs_donations
-          .entry(donator_identifier)
-          .or_default();
+      EventType::StreamlabsDonation | EventType::HypeChat => {
+        let Some(converted_amount) = currency_converter
+          .convert_donation(&donation, database_connection)
+          .await?
+        else {
+          tracing::error!(
+            "Failed to convert donation of ID {} from {:?}. Leaving it out of the rankings.",
+            donation.id,
+            donation.currency
+          );
+          continue;
+        };
+        let donations = if donation.event_type == EventType::HypeChat {
+          &mut donators.hype_chats
+        } else {
+          &mut donators.streamlabs_donations
+        };
+        let amount = donations.entry(donator_identifier).or_default();
 
-        *amount += donation.amount;
+        *amount += converted_amount;
       }
     };
   }
@@ -1,13 +1,14 @@
 use super::donator_identifier::DonatorIdentifier;
 use super::BitsEntry;
+use super::CurrencyDonationEntry;
 use super::GiftSubsEntry;
-use super::StreamlabsDonationEntry;
 use super::TopDonatorsTables;
 use super::SUB_TIER_VALUE;
 use crate::errors::AppError;
 use database_connection::get_database_connection;
 use entities::twitch_user;
 use entities::unknown_user;
+use sea_orm::prelude::Decimal;
 use sea_orm::*;
 use std::collections::HashMap;
 use tabled::Table;
@@ -15,9 +16,10 @@ use tabled::Table;
 /// The amount of each donation event type for (user_id, amount).
 #[derive(Default)]
 pub struct TopDonators {
-  pub streamlabs_donations: HashMap<DonatorIdentifier, f32>,
-  pub bits: HashMap<DonatorIdentifier, f32>,
-  pub gift_subs: HashMap<DonatorIdentifier, [f32; 3]>,
+  pub streamlabs_donations: HashMap<DonatorIdentifier, Decimal>,
+  pub hype_chats: HashMap<DonatorIdentifier, Decimal>,
+  pub bits: HashMap<DonatorIdentifier, Decimal>,
+  pub gift_subs: HashMap<DonatorIdentifier, [Decimal; 3]>,
 }
 
 impl TopDonators {
@@ -26,12 +28,14 @@ impl TopDonators {
 
     let donators = self.get_donator_name_list().await?;
 
-    let streamlabs_table = self.streamlabs_table(&donators);
+    let streamlabs_table = Self::currency_table(&self.streamlabs_donations, &donators);
+    let hype_chats_table = Self::currency_table(&self.hype_chats, &donators);
     let bits_table = self.bits_table(&donators);
     let gift_subs_table = self.gift_subs_table(&donators);
 
     Ok(TopDonatorsTables::new(
       streamlabs_table,
+      hype_chats_table,
       bits_table,
       gift_subs_table,
     ))
@@ -42,6 +46,7 @@ impl TopDonators {
     let donator_ids: Vec<DonatorIdentifier> = self
       .streamlabs_donations
       .keys()
+      .chain(self.hype_chats.keys())
       .chain(self.bits.keys())
       .chain(self.gift_subs.keys())
       .cloned()
@@ -84,11 +89,15 @@ impl TopDonators {
     Ok(donator_list)
   }
 
-  fn streamlabs_table(&self, donators: &HashMap<DonatorIdentifier, String>) -> Table {
+  /// Builds the rankings for donations made in the reporting currency.
+  fn currency_table(
+    donations: &HashMap<DonatorIdentifier, Decimal>,
+    donators: &HashMap<DonatorIdentifier, String>,
+  ) -> Table {
     // Contains the (login_name, amount)
-    let mut rankings: Vec<(String, f32)> = vec![];
+    let mut rankings: Vec<(String, Decimal)> = vec![];
 
-    for (donator_id, donation_amount) in &self.streamlabs_donations {
+    for (donator_id, donation_amount) in donations {
       let Some(donator) = donators.get(donator_id) else {
         tracing::error!("Failed to retrieve donator of ID {:?}", donator_id);
         continue;
@@ -99,13 +108,13 @@ impl TopDonators {
 
     // let average_donation = rankings.iter().map(|(_, amount)| amount).sum::<f32>() / rankings.len() as f32;
 
-    rankings.sort_by_key(|(_, rank)| (rank * 100.0) as isize);
+    rankings.sort_by_key(|(_, rank)| *rank);
     rankings.reverse(); // Sort to lowest in front.
 
-    let rankings: Vec<StreamlabsDonationEntry> = rankings
+    let rankings: Vec<CurrencyDonationEntry> = rankings
       .into_iter()
       .enumerate()
-      .map(|(place, (name, donation_amount))| StreamlabsDonationEntry {
+      .map(|(place, (name, donation_amount))| CurrencyDonationEntry {
         place: place + 1,
         name,
         amount: format!("{:.2}", donation_amount),
@@ -118,7 +127,7 @@ impl TopDonators {
 
   fn bits_table(&self, donators: &HashMap<DonatorIdentifier, String>) -> Table {
     // Contains the (login_name, amount)
-    let mut rankings: Vec<(String, f32)> = vec![];
+    let mut rankings: Vec<(String, Decimal)> = vec![];
 
     for (donator_id, donation_amount) in &self.bits {
       let Some(donator) = donators.get(donator_id) else {
@@ -131,7 +140,7 @@ impl TopDonators {
 
     // let average_donation = rankings.iter().sum() / rankings.len() as f32;
 
-    rankings.sort_by_key(|(_, rank)| *rank as isize);
+    rankings.sort_by_key(|(_, rank)| *rank);
     rankings.reverse(); // Sort to lowest in front.
 
     let rankings: Vec<BitsEntry> = rankings
@@ -150,7 +159,7 @@ impl TopDonators {
 
   fn gift_subs_table(&self, donators: &HashMap<DonatorIdentifier, String>) -> Table {
     // Contains the (login_name, [sub_tier_gift_counts])
-    let mut rankings: Vec<(String, [f32; 3])> = vec![];
+    let mut rankings: Vec<(String, [Decimal; 3])> = vec![];
 
     for (donator_id, donation_amount) in &self.gift_subs {
       let Some(donator) = donators.get(donator_id) else {
@@ -161,7 +170,7 @@ impl TopDonators {
       rankings.push((donator.to_owned(), *donation_amount));
     }
 
-    rankings.sort_by_key(|(_, rank)| Self::gift_subs_to_value(rank) as usize);
+    rankings.sort_by_key(|(_, rank)| Self::gift_subs_to_value(rank));
     rankings.reverse(); // Sort to lowest in front.
 
     let rankings: Vec<GiftSubsEntry> = rankings
@@ -170,7 +179,7 @@ impl TopDonators {
       .map(|(place, (name, donation_amount))| GiftSubsEntry {
         place: place + 1,
         name,
-        amount: format!("{:?}", donation_amount),
+        amount: format!("{:?}", donation_amount.map(|gift_count| gift_count.trunc())),
       })
       .collect();
 
@@ -179,7 +188,7 @@ impl TopDonators {
 
   /// Takes a list of subscriptions in order of tier [tier1, tier2, tier3].
   /// Returns the sum of each tier multiplied by their cost in USD.
-  fn gift_subs_to_value(subs: &[f32; 3]) -> f32 {
+  fn gift_subs_to_value(subs: &[Decimal; 3]) -> Decimal {
     (subs[0] * SUB_TIER_VALUE[0]) + (subs[1] * SUB_TIER_VALUE[1]) + (subs[2] * SUB_TIER_VALUE[2])
   }
 }
@@ -16,8 +16,9 @@ pub struct BitsEntry {
   // pub average_donation: String,
 }
 
+/// An entry for donations made with money, such as Streamlabs donations and Hype Chats.
 #[derive(Tabled)]
-pub struct StreamlabsDonationEntry {
+pub struct CurrencyDonationEntry {
   pub place: usize,
   pub name: String,
   pub amount: String,
@@ -1,20 +1,39 @@
+use crate::reports::report_section::ReportTable;
 use tabled::settings::{Panel, Style};
 use tabled::Table;
 
 pub struct TopDonatorsTables {
   streamlabs_donations: Table,
+  hype_chats: Table,
   bits: Table,
   gift_subs: Table,
+  report_tables: Vec<ReportTable>,
 }
 
 impl TopDonatorsTables {
-  pub fn new(mut streamlabs_donations: Table, mut bits: Table, mut gift_subs: Table) -> Self {
+  pub fn new(
+    mut streamlabs_donations: Table,
+    mut hype_chats: Table,
+    mut bits: Table,
+    mut gift_subs: Table,
+  ) -> Self {
     let style = Style::markdown();
+    // Taken before the panels are added, in the same order the tables are displayed.
+    let report_tables = vec![
+      ReportTable::from_tabled("Gift Subs", &gift_subs),
+      ReportTable::from_tabled("Bit Donations", &bits),
+      ReportTable::from_tabled("Hype Chats", &hype_chats),
+      ReportTable::from_tabled("Streamlabs Donations", &streamlabs_donations),
+    ];
 
     streamlabs_donations
       .with(style.clone())
       .with(Panel::header("== Streamlabs Donations =="));
 
+    hype_chats
+      .with(style.clone())
+      .with(Panel::header("== Hype Chats =="));
+
     bits
       .with(style.clone())
       .with(Panel::header("== Bit Donations =="));
@@ -25,10 +44,16 @@ impl TopDonatorsTables {
 
     Self {
       streamlabs_donations,
+      hype_chats,
       bits,
       gift_subs,
+      report_tables,
     }
   }
+
+  pub fn report_tables(&self) -> &[ReportTable] {
+    &self.report_tables
+  }
 }
 
 impl std::fmt::Display for TopDonatorsTables {
@@ -37,8 +62,8 @@ impl std::fmt::Display for TopDonatorsTables {
 
     write!(
       formatter,
-      "{}{spacing}{}{spacing}{}",
-      self.gift_subs, self.bits, self.streamlabs_donations
+      "{}{spacing}{}{spacing}{}{spacing}{}",
+      self.gift_subs, self.bits, self.hype_chats, self.streamlabs_donations
     )
   }
 }
@@ -0,0 +1,227 @@
+use crate::reports::report_section::{ReportSection, ReportTable};
+use crate::{conditions::query_conditions::AppQueryConditions, errors::AppError};
+use entities::sea_orm_active_enums::HypeTrainContributionType;
+use entities::{donation_event, hype_train, hype_train_contributor, twitch_user};
+use human_time::ToHumanTimeString;
+use sea_orm::*;
+use std::collections::HashMap;
+
+const HEADER: &str = "= Hype Trains =";
+
+pub async fn get_hype_trains_table(
+  query_conditions: &AppQueryConditions,
+  database_connection: &DatabaseConnection,
+) -> Result<ReportSection, AppError> {
+  tracing::info!("Building hype trains table.");
+
+  let hype_trains = hype_train::Entity::find()
+    .filter(query_conditions.hype_trains().clone())
+    .order_by_asc(hype_train::Column::StartTimestamp)
+    .all(database_connection)
+    .await?;
+
+  if hype_trains.is_empty() {
+    return Ok(ReportSection::default());
+  }
+
+  let hype_train_ids: Vec<i32> = hype_trains.iter().map(|hype_train| hype_train.id).collect();
+  let mut contributors = get_contributors(&hype_train_ids, database_connection).await?;
+  let donation_counts = get_donation_counts(&hype_train_ids, database_connection).await?;
+  let mut hype_trains_rows = vec![];
+
+  let hype_trains_list = hype_trains
+    .iter()
+    .map(|hype_train| {
+      let contributors = contributors.remove(&hype_train.id).unwrap_or_default();
+      let donation_count = donation_counts.get(&hype_train.id).copied().unwrap_or(0);
+
+      hype_trains_rows.push(generate_hype_train_row(
+        hype_train,
+        &contributors,
+        donation_count,
+      ));
+
+      generate_hype_train_string(hype_train, &contributors, donation_count)
+    })
+    .collect::<Vec<String>>()
+    .join("\n");
+
+  Ok(ReportSection::new(
+    format!("{HEADER}\n{hype_trains_list}\n"),
+    vec![ReportTable::new(
+      HEADER,
+      &[
+        "level",
+        "points",
+        "duration",
+        "donations",
+        "top_contributors",
+      ],
+      hype_trains_rows,
+    )],
+  ))
+}
+
+/// Formats the hype train as `Level - total - duration - donations`, followed by each top contributor on their own line.
+fn generate_hype_train_string(
+  hype_train: &hype_train::Model,
+  contributors: &[(hype_train_contributor::Model, twitch_user::Model)],
+  donation_count: usize,
+) -> String {
+  let mut hype_train_string = format!(
+    "Level {} - {} points - {} - {} donations",
+    hype_train.level,
+    hype_train.total,
+    hype_train_duration(hype_train),
+    donation_count
+  );
+
+  for (contributor, user) in contributors {
+    hype_train_string.push_str(&format!("\n  {}", contributor_string(contributor, user)));
+  }
+
+  hype_train_string
+}
+
+/// The hype train's values, with each top contributor joined into the last column.
+fn generate_hype_train_row(
+  hype_train: &hype_train::Model,
+  contributors: &[(hype_train_contributor::Model, twitch_user::Model)],
+  donation_count: usize,
+) -> Vec<String> {
+  let top_contributors = contributors
+    .iter()
+    .map(|(contributor, user)| contributor_string(contributor, user))
+    .collect::<Vec<String>>()
+    .join(", ");
+
+  vec![
+    hype_train.level.to_string(),
+    hype_train.total.to_string(),
+    hype_train_duration(hype_train),
+    donation_count.to_string(),
+    top_contributors,
+  ]
+}
+
+fn hype_train_duration(hype_train: &hype_train::Model) -> String {
+  match hype_train.end_timestamp {
+    Some(end_timestamp) => (end_timestamp - hype_train.start_timestamp)
+      .to_std()
+      .map(|duration| duration.to_human_time_string())
+      .unwrap_or_default(),
+    None => "unfinished".to_string(),
+  }
+}
+
+/// Formatted as `user - total contribution`, such as `linkthedot - 500 bits`.
+fn contributor_string(
+  contributor: &hype_train_contributor::Model,
+  user: &twitch_user::Model,
+) -> String {
+  let contribution_name = match contributor.contribution_type {
+    HypeTrainContributionType::Bits => "bits",
+    HypeTrainContributionType::Subscription => "sub points",
+    HypeTrainContributionType::Other => "points",
+  };
+
+  format!(
+    "{} - {} {}",
+    user.login_name, contributor.total, contribution_name
+  )
+}
+
+/// Returns the top contributors for each hype train, highest total first.
+async fn get_contributors(
+  hype_train_ids: &[i32],
+  database_connection: &DatabaseConnection,
+) -> Result<HashMap<i32, Vec<(hype_train_contributor::Model, twitch_user::Model)>>, AppError> {
+  tracing::info!("Getting hype train contributors.");
+
+  let contributors = hype_train_contributor::Entity::find()
+    .find_also_related(twitch_user::Entity)
+    .filter(hype_train_contributor::Column::HypeTrainId.is_in(hype_train_ids.to_vec()))
+    .order_by_desc(hype_train_contributor::Column::Total)
+    .all(database_connection)
+    .await?;
+  let mut contributors_by_hype_train: HashMap<i32, Vec<_>> = HashMap::new();
+
+  for (contributor, maybe_user) in contributors {
+    let Some(user) = maybe_user else {
+      tracing::error!(
+        "Failed to find the user for hype train contributor of ID {}",
+        contributor.id
+      );
+      continue;
+    };
+
+    contributors_by_hype_train
+      .entry(contributor.hype_train_id)
+      .or_default()
+      .push((contributor, user));
+  }
+
+  Ok(contributors_by_hype_train)
+}
+
+async fn get_donation_counts(
+  hype_train_ids: &[i32],
+  database_connection: &DatabaseConnection,
+) -> Result<HashMap<i32, usize>, AppError> {
+  let donations = donation_event::Entity::find()
+    .filter(donation_event::Column::HypeTrainId.is_in(hype_train_ids.to_vec()))
+    .all(database_connection)
+    .await?;
+  let mut donation_counts = HashMap::new();
+
+  for hype_train_id in donations
+    .iter()
+    .filter_map(|donation| donation.hype_train_id)
+  {
+    *donation_counts.entry(hype_train_id).or_insert(0) += 1;
+  }
+
+  Ok(donation_counts)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::testing_helper_methods::timestamp_from_string;
+
+  #[test]
+  fn hype_train_string_lists_contributors() {
+    let hype_train = hype_train::Model {
+      id: 1,
+      twitch_hype_train_id: "1b0AsbInCHZW2SQFQkCzqN07Ib2".into(),
+      channel_id: 1,
+      stream_id: Some(1),
+      level: 3,
+      total: 1500,
+      start_timestamp: timestamp_from_string("1740956922774"),
+      end_timestamp: None,
+    };
+    let contributors = vec![(
+      hype_train_contributor::Model {
+        id: 1,
+        hype_train_id: 1,
+        twitch_user_id: 3,
+        contribution_type: HypeTrainContributionType::Bits,
+        total: 500,
+      },
+      twitch_user::Model {
+        id: 3,
+        twitch_id: 128831052,
+        login_name: "linkthedot".into(),
+        display_name: "LinkTheDot".into(),
+      },
+    )];
+
+    let hype_train_string = generate_hype_train_string(&hype_train, &contributors, 4);
+
+    assert_eq!(
+      hype_train_string,
+      "Level 3 - 1500 points - unfinished - 4 donations\n  linkthedot - 500 bits"
+    );
+  }
+}
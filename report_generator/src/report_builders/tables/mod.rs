@@ -1,5 +1,10 @@
+pub mod channel_notices;
 pub mod chat_messages;
+pub mod chat_timeline;
+pub mod cheermotes;
+pub mod deleted_messages;
 pub mod donation_rankings;
+pub mod hype_trains;
 pub mod raids;
 pub mod timeouts;
 pub mod top_emotes;
@@ -1,3 +1,4 @@
+use crate::reports::report_section::{ReportSection, ReportTable};
 use crate::{conditions::query_conditions::AppQueryConditions, errors::AppError};
 use entities::{raid, twitch_user};
 use sea_orm::*;
@@ -7,7 +8,7 @@ const HEADER: &str = "= Raids =";
 pub async fn get_raids_table(
   query_conditions: &AppQueryConditions,
   database_connection: &DatabaseConnection,
-) -> Result<String, AppError> {
+) -> Result<ReportSection, AppError> {
   tracing::info!("Building raids table.");
 
   let raids = get_raids(query_conditions, database_connection).await?;
@@ -19,14 +20,21 @@ pub async fn get_raids_table(
     .map(|(raid, raider)| format!("{} - {} viewers", raider.login_name, raid.size))
     .collect::<Vec<String>>()
     .join("\n");
+  let raids_rows = raids
+    .iter()
+    .map(|(raid, raider)| vec![raider.login_name.clone(), raid.size.to_string()])
+    .collect();
 
   tracing::info!("Finished.");
 
   if raids_list.is_empty() {
-    return Ok(String::default());
+    return Ok(ReportSection::default());
   }
 
-  Ok(format!("{HEADER}\n{raids_list}\n"))
+  Ok(ReportSection::new(
+    format!("{HEADER}\n{raids_list}\n"),
+    vec![ReportTable::new(HEADER, &["raider", "viewers"], raids_rows)],
+  ))
 }
 
 async fn get_raids(
@@ -1,3 +1,4 @@
+use crate::reports::report_section::{rows_to_lines, ReportSection, ReportTable};
 use crate::{conditions::query_conditions::AppQueryConditions, errors::AppError};
 use entities::{twitch_user, user_timeout};
 use human_time::ToHumanTimeString;
@@ -10,33 +11,45 @@ const BANS_HEADER: &str = "= Bans =";
 pub async fn get_timeouts_table(
   query_conditions: &AppQueryConditions,
   database_connection: &DatabaseConnection,
-) -> Result<String, AppError> {
+) -> Result<ReportSection, AppError> {
   tracing::info!("Building timeouts table");
 
   let (mut banned_users, timedout_users) =
     get_timeouts(query_conditions, database_connection).await?;
 
-  let timedout_users_string = generate_timedout_users_string(timedout_users, &mut banned_users);
-  let banned_users_string = generate_banned_users_string(banned_users);
+  let timedout_users_rows = generate_timedout_users_rows(timedout_users, &mut banned_users);
+  let banned_users_rows = generate_banned_users_rows(banned_users);
 
   let mut timeout_table_string = String::new();
+  let mut timeout_tables = vec![];
+
+  if !timedout_users_rows.is_empty() {
+    let timedout_users_string = rows_to_lines(&timedout_users_rows);
 
-  if !timedout_users_string.is_empty() {
     timeout_table_string.push_str(&format!("{TIMEOUT_HEADER}\n{timedout_users_string}\n"));
+    timeout_tables.push(ReportTable::new(
+      TIMEOUT_HEADER,
+      &["user", "duration"],
+      timedout_users_rows,
+    ));
   }
 
-  if !banned_users_string.is_empty() {
+  if !banned_users_rows.is_empty() {
+    let banned_users_string = rows_to_lines(&banned_users_rows);
+
     timeout_table_string.push_str(&format!("{BANS_HEADER}\n{banned_users_string}\n"));
+    timeout_tables.push(ReportTable::new(BANS_HEADER, &["user"], banned_users_rows));
   }
 
-  Ok(timeout_table_string)
+  Ok(ReportSection::new(timeout_table_string, timeout_tables))
 }
 
-fn generate_timedout_users_string(
+/// Returns rows of `user, duration` for each timeout, moving any bans found into the banned users.
+fn generate_timedout_users_rows(
   timedout_users: Vec<(user_timeout::Model, twitch_user::Model)>,
   banned_users: &mut Vec<(user_timeout::Model, twitch_user::Model)>,
-) -> String {
-  tracing::info!("Building timed-out users string.");
+) -> Vec<Vec<String>> {
+  tracing::info!("Building timed-out users rows.");
 
   timedout_users
     .into_iter()
@@ -50,28 +63,23 @@ fn generate_timedout_users_string(
         return None;
       };
 
-      let timeout_string = format!(
-        "{} - {}",
+      Some(vec![
         user.login_name,
-        Duration::from_secs(timeout_duration as u64).to_human_time_string()
-      );
-
-      Some(timeout_string)
+        Duration::from_secs(timeout_duration as u64).to_human_time_string(),
+      ])
     })
-    .collect::<Vec<String>>()
-    .join("\n")
+    .collect()
 }
 
-fn generate_banned_users_string(
+fn generate_banned_users_rows(
   banned_users: Vec<(user_timeout::Model, twitch_user::Model)>,
-) -> String {
-  tracing::info!("Building banned users string.");
+) -> Vec<Vec<String>> {
+  tracing::info!("Building banned users rows.");
 
   banned_users
     .into_iter()
-    .map(|(_timeout, user)| user.login_name)
-    .collect::<Vec<String>>()
-    .join("\n")
+    .map(|(_timeout, user)| vec![user.login_name])
+    .collect()
 }
 
 async fn get_timeouts(
@@ -1,3 +1,4 @@
+use crate::reports::report_section::{ReportSection, ReportTable};
 use crate::{conditions::query_conditions::AppQueryConditions, errors::AppError};
 use entities::{emote, emote_usage};
 use sea_orm::*;
@@ -7,7 +8,7 @@ pub async fn get_top_n_emotes_table(
   query_conditions: &AppQueryConditions,
   database_connection: &DatabaseConnection,
   amount: Option<usize>,
-) -> Result<String, AppError> {
+) -> Result<ReportSection, AppError> {
   tracing::info!(
     "Getting top {} emotes.",
     amount.map(|a| a.to_string()).unwrap_or("all".into())
@@ -65,7 +66,7 @@ async fn get_top_n_emotes(
   Ok(emote_uses.into_iter().take(amount).collect())
 }
 
-fn build_emote_ranking_table(top_emotes: Vec<(String, usize)>) -> String {
+fn build_emote_ranking_table(top_emotes: Vec<(String, usize)>) -> ReportSection {
   tracing::info!("Building emote ranking table.");
 
   let longest_emote_name = top_emotes
@@ -75,6 +76,17 @@ fn build_emote_ranking_table(top_emotes: Vec<(String, usize)>) -> String {
     .unwrap();
   let title = format!("= Top {} Emotes Used =", top_emotes.len());
   let emote_rankings_max_digits = number_of_digits(top_emotes.len());
+  let top_emotes_rows = top_emotes
+    .iter()
+    .enumerate()
+    .map(|(rank, (emote_name, use_count))| {
+      vec![
+        (rank + 1).to_string(),
+        emote_name.clone(),
+        use_count.to_string(),
+      ]
+    })
+    .collect();
 
   tracing::info!("Building...");
   let top_emotes_string = top_emotes
@@ -92,7 +104,14 @@ fn build_emote_ranking_table(top_emotes: Vec<(String, usize)>) -> String {
     .join("\n");
   tracing::info!("Finished.");
 
-  format!("{title}\n{top_emotes_string}")
+  ReportSection::new(
+    format!("{title}\n{top_emotes_string}"),
+    vec![ReportTable::new(
+      &title,
+      &["rank", "emote", "uses"],
+      top_emotes_rows,
+    )],
+  )
 }
 
 /// Counts the amount of digits in the passed in number.
@@ -1,10 +1,15 @@
 use crate::conditions::query_conditions::AppQueryConditions;
 use crate::errors::AppError;
 use crate::query_result_models::emote_usage_contents::EmoteUsageWithContents;
+use crate::reports::report_section::ReportTable;
 use crate::EMOTE_DOMINANCE;
+use app_config::AppConfig;
 use database_connection::get_database_connection;
 use entities::sea_orm_active_enums::EventType;
 use entities::*;
+use entity_extensions::exchange_rate::CurrencyConverter;
+use num_traits::cast::ToPrimitive;
+use sea_orm::prelude::Decimal;
 use sea_orm::*;
 use std::collections::HashMap;
 use subscriptions::Subscriptions;
@@ -18,8 +23,10 @@ pub struct ChatStatistics {
   pub average_words_per_message: f32,
   /// 0-100
   pub subscribed_chat_percentage: f32,
-  pub raw_donations: f32,
+  pub raw_donations: Decimal,
   pub bits: i32,
+  /// The part of `bits` spent on power-ups.
+  pub power_up_bits: i32,
   pub new_subscribers: i32,
   pub tier_1_subs: i32,
   pub tier_2_subs: i32,
@@ -60,7 +67,14 @@ impl ChatStatistics {
         EventType::StreamlabsDonation,
       )
       .await?,
-      bits: Self::get_donation_event_total_amount(query_conditions, EventType::Bits).await? as i32,
+      bits: Self::get_donation_event_total_amount(query_conditions, EventType::Bits)
+        .await?
+        .to_i32()
+        .unwrap_or_default(),
+      power_up_bits: Self::get_power_up_bits(query_conditions)
+        .await?
+        .to_i32()
+        .unwrap_or_default(),
       new_subscribers: Self::get_new_subscribers(query_conditions).await?,
       tier_1_subs: subscriptions.tier_1,
       tier_2_subs: subscriptions.tier_2,
@@ -72,6 +86,53 @@ impl ChatStatistics {
     })
   }
 
+  /// The values shown in the `general_chat_stats` template.
+  pub fn chat_table(&self) -> ReportTable {
+    ReportTable::from_values(
+      "Chat statistics",
+      vec![
+        ("first_time_chatters", self.first_time_chatters.to_string()),
+        ("total_chats", self.total_chats.to_string()),
+        (
+          "emote_message_threshold",
+          self.emote_message_threshold.to_string(),
+        ),
+        (
+          "non_emote_dominant_chats",
+          self.non_emote_dominant_chats.to_string(),
+        ),
+        (
+          "subscribed_chat_percentage",
+          format!("{:.2}", self.subscribed_chat_percentage),
+        ),
+        (
+          "average_words_per_message",
+          format!("{:.2}", self.average_words_per_message),
+        ),
+        ("new_subscribers", self.new_subscribers.to_string()),
+      ],
+    )
+  }
+
+  /// The values shown in the `donation_stats` template.
+  pub fn donation_table(&self) -> ReportTable {
+    ReportTable::from_values(
+      "Donation Statistics",
+      vec![
+        ("raw_donations", self.raw_donations.to_string()),
+        ("bits", self.bits.to_string()),
+        ("power_up_bits", self.power_up_bits.to_string()),
+        ("tier_1_subs", self.tier_1_subs.to_string()),
+        ("tier_2_subs", self.tier_2_subs.to_string()),
+        ("tier_3_subs", self.tier_3_subs.to_string()),
+        ("prime_subscriptions", self.prime_subscriptions.to_string()),
+        ("tier_1_gift_subs", self.tier_1_gift_subs.to_string()),
+        ("tier_2_gift_subs", self.tier_2_gift_subs.to_string()),
+        ("tier_3_gift_subs", self.tier_3_gift_subs.to_string()),
+      ],
+    )
+  }
+
   /// Pairs the values contained in self with the keys listed in `./chat_statistic_template`.
   pub fn to_key_value_pairs(self) -> HashMap<String, String> {
     let mut end_pairs = HashMap::new();
@@ -103,9 +164,10 @@ impl ChatStatistics {
     );
     end_pairs.insert(
       "{raw_donations}".into(),
-      self.raw_donations.max(0.0).to_string(),
+      self.raw_donations.max(Decimal::ZERO).to_string(),
     );
     end_pairs.insert("{bits}".into(), self.bits.to_string());
+    end_pairs.insert("{power_up_bits}".into(), self.power_up_bits.to_string());
     end_pairs.insert("{new_subscribers}".into(), self.new_subscribers.to_string());
     end_pairs.insert("{tier_1_subs}".into(), self.tier_1_subs.to_string());
     end_pairs.insert("{tier_2_subs}".into(), self.tier_2_subs.to_string());
@@ -219,26 +281,59 @@ impl ChatStatistics {
       / messages.len() as f32
   }
 
+  /// Donations made in a currency are converted to the reporting currency before being added up.
   async fn get_donation_event_total_amount(
     query_conditions: &AppQueryConditions,
     event_type: EventType,
-  ) -> Result<f32, AppError> {
+  ) -> Result<Decimal, AppError> {
     tracing::info!("Getting total {event_type:?} donation amount.");
 
     let database_connection = get_database_connection().await;
 
-    let streamlabs_donation_events = donation_event::Entity::find()
+    let donation_events = donation_event::Entity::find()
       .filter(query_conditions.donations().clone())
       .filter(donation_event::Column::EventType.eq(event_type))
       .all(database_connection)
       .await?;
+    let mut currency_converter = CurrencyConverter::new(AppConfig::reporting_currency());
+    let mut total_amount = Decimal::ZERO;
+
+    for donation in donation_events {
+      let Some(amount) = currency_converter
+        .convert_donation(&donation, database_connection)
+        .await?
+      else {
+        tracing::error!(
+          "Failed to convert donation of ID {} from {:?}. Leaving it out of the total.",
+          donation.id,
+          donation.currency
+        );
+        continue;
+      };
+
+      total_amount += amount;
+    }
+
+    Ok(total_amount.max(Decimal::ZERO).round_dp(2))
+  }
+
+  /// Power-ups are stored as bit donations with the power-up used attached.
+  async fn get_power_up_bits(query_conditions: &AppQueryConditions) -> Result<Decimal, AppError> {
+    tracing::info!("Getting total bits spent on power-ups.");
+
+    let database_connection = get_database_connection().await;
+
+    let power_up_donation_events = donation_event::Entity::find()
+      .filter(query_conditions.donations().clone())
+      .inner_join(bits_power_up::Entity)
+      .all(database_connection)
+      .await?;
 
     Ok(
-      streamlabs_donation_events
+      power_up_donation_events
         .iter()
         .map(|donation| donation.amount)
-        .sum::<f32>()
-        .max(0.0),
+        .sum::<Decimal>(),
     )
   }
 
@@ -326,9 +421,15 @@ mod subscriptions {
       gifted_subs.into_iter().for_each(|gifted_sub_event| {
         if let Some(sub_tier) = gifted_sub_event.subscription_tier {
           match sub_tier {
-            1 => subscriptions.tier_1_gifted += gifted_sub_event.amount as i32,
-            2 => subscriptions.tier_2_gifted += gifted_sub_event.amount as i32,
-            3 => subscriptions.tier_3_gifted += gifted_sub_event.amount as i32,
+            1 => {
+              subscriptions.tier_1_gifted += gifted_sub_event.amount.to_i32().unwrap_or_default()
+            }
+            2 => {
+              subscriptions.tier_2_gifted += gifted_sub_event.amount.to_i32().unwrap_or_default()
+            }
+            3 => {
+              subscriptions.tier_3_gifted += gifted_sub_event.amount.to_i32().unwrap_or_default()
+            }
             _ => tracing::warn!(
               "Encountered an unknown sub tier for gifted subscription event: {}.",
               gifted_sub_event.id
@@ -1,18 +1,24 @@
 use crate::{
   conditions::query_conditions::AppQueryConditions, errors::AppError,
   report_builders::templates::subathon_statistics::donation_sum::StrippedSubscriptionEvent,
+  reports::report_section::ReportTable,
 };
+use app_config::AppConfig;
 use chrono::{Duration as ChronoDuration, Utc};
-use donation_sum::DonationSum;
-use entities::{donation_event, sea_orm_active_enums::EventType, stream, subscription_event};
+use entities::{sea_orm_active_enums::EventType, stream, subscription_event};
+use entity_extensions::donation_sum::DonationSum;
+use entity_extensions::exchange_rate::CurrencyConverter;
+use num_traits::cast::ToPrimitive;
+use sea_orm::prelude::Decimal;
 use sea_orm::*;
 mod donation_sum;
 
-const POINTS_PER_BIT: f64 = 0.01;
-const POINTS_PER_TIER_1_SUB: f64 = 5.0;
-const POINTS_PER_TIER_2_SUB: f64 = 8.0;
-const POINTS_PER_TIER_3_SUB: f64 = 20.0;
-const POINTS_PER_DOLLAR: f64 = 1.0;
+const POINTS_PER_BIT: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+const POINTS_PER_TIER_1_SUB: Decimal = Decimal::from_parts(5, 0, 0, false, 0);
+const POINTS_PER_TIER_2_SUB: Decimal = Decimal::from_parts(8, 0, 0, false, 0);
+const POINTS_PER_TIER_3_SUB: Decimal = Decimal::from_parts(20, 0, 0, false, 0);
+/// Direct donations are counted in the reporting currency.
+const POINTS_PER_DOLLAR: Decimal = Decimal::ONE;
 
 const SECONDS_PER_POINT: f64 = 6.0;
 
@@ -37,15 +43,31 @@ impl SubathonStatistics {
     let total_stream_duration = total_stream_duration.as_seconds_f64() / 3600.0;
     let points_from_donations =
       Self::points_from_donations(query_conditions, database_connection).await?;
-    let hours_added_from_donations = (SECONDS_PER_POINT * points_from_donations) / 3600.0;
+    let hours_added_from_donations =
+      (SECONDS_PER_POINT * points_from_donations.to_f64().unwrap_or_default()) / 3600.0;
 
     Ok(Self {
       hours_streamed: total_stream_duration,
       hours_added_from_donations,
-      total_points: points_from_donations as i32,
+      total_points: points_from_donations.trunc().to_i32().unwrap_or_default(),
     })
   }
 
+  /// The values shown in the `subathon_stats` template.
+  pub fn table(&self) -> ReportTable {
+    ReportTable::from_values(
+      "Subathon statistics",
+      vec![
+        ("hours_streamed", format!("{:.2}", self.hours_streamed)),
+        (
+          "hours_added_from_donations",
+          format!("{:.2}", self.hours_added_from_donations),
+        ),
+        ("total_points", self.total_points.to_string()),
+      ],
+    )
+  }
+
   async fn get_duration_of_streams(
     query_conditions: &AppQueryConditions,
     database_connection: &DatabaseConnection,
@@ -80,20 +102,11 @@ impl SubathonStatistics {
   pub async fn points_from_donations(
     query_conditions: &AppQueryConditions,
     database_connection: &DatabaseConnection,
-  ) -> Result<f64, AppError> {
+  ) -> Result<Decimal, AppError> {
     tracing::info!("Calculating total points from all donations since start time.");
 
-    let mut all_donations = donation_event::Entity::find()
-      .filter(query_conditions.donations().clone())
-      .select_only()
-      .column(donation_event::Column::EventType)
-      .column(donation_event::Column::SubscriptionTier)
-      .column_as(donation_event::Column::Amount.sum(), "sum_amount")
-      .group_by(donation_event::Column::EventType)
-      .group_by(donation_event::Column::SubscriptionTier)
-      .into_model::<DonationSum>()
-      .all(database_connection)
-      .await?;
+    let mut all_donations =
+      DonationSum::get_sums(query_conditions.donations().clone(), database_connection).await?;
     let subscriptions = subscription_event::Entity::find()
       .filter(query_conditions.subscriptions().clone())
       .select_only()
@@ -108,18 +121,17 @@ impl SubathonStatistics {
 
     all_donations.extend(subscriptions);
 
-    let mut total_points = 0.0_f64;
+    let mut currency_converter = CurrencyConverter::new(AppConfig::reporting_currency());
+    let mut total_points = Decimal::ZERO;
 
-    for DonationSum {
-      event_type,
-      sum_amount,
-      subscription_tier,
-    } in all_donations
-    {
-      let points_per_amount = match event_type {
+    for donation_sum in all_donations {
+      let sum_amount = donation_sum
+        .amount(&mut currency_converter, database_connection)
+        .await?;
+      let points_per_amount = match donation_sum.event_type {
         EventType::Bits => POINTS_PER_BIT,
         EventType::GiftSubs => {
-          let Some(subscription_tier) = subscription_tier else {
+          let Some(subscription_tier) = donation_sum.subscription_tier else {
             tracing::error!("Missing subscription_tier for gift_sub donation event.");
             continue;
           };
@@ -134,7 +146,7 @@ impl SubathonStatistics {
             }
           }
         }
-        EventType::StreamlabsDonation => POINTS_PER_DOLLAR,
+        EventType::StreamlabsDonation | EventType::HypeChat => POINTS_PER_DOLLAR,
       };
 
       let points = points_per_amount * sum_amount;
@@ -1,13 +1,8 @@
 use entities::sea_orm_active_enums::EventType;
+use entity_extensions::donation_sum::DonationSum;
+use sea_orm::prelude::Decimal;
 use sea_orm::FromQueryResult;
 
-#[derive(Debug, FromQueryResult)]
-pub struct DonationSum {
-  pub event_type: EventType,
-  pub sum_amount: f64,
-  pub subscription_tier: Option<i32>,
-}
-
 #[derive(Debug, FromQueryResult)]
 pub struct StrippedSubscriptionEvent {
   pub subscription_tier: i32,
@@ -23,8 +18,10 @@ impl From<StrippedSubscriptionEvent> for DonationSum {
 
     DonationSum {
       event_type: EventType::GiftSubs,
-      sum_amount: 1.0,
+      sum_amount: Decimal::ONE,
       subscription_tier: Some(subscription_tier),
+      currency: None,
+      day: None,
     }
   }
 }
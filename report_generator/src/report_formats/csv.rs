@@ -0,0 +1,99 @@
+use crate::report_formats::RenderedReport;
+use crate::reports::report_section::ReportTable;
+use crate::reports::Report;
+use std::collections::HashMap;
+
+/// Creates a file for each table in the report, named after the table.
+///
+/// Sections without any tables are left out.
+pub fn render(report: &Report) -> Vec<RenderedReport> {
+  let mut file_name_uses: HashMap<String, usize> = HashMap::new();
+
+  report
+    .sections
+    .iter()
+    .flat_map(|section| section.tables())
+    .map(|table| {
+      let file_name = file_name(&table.title);
+      let uses = file_name_uses.entry(file_name.clone()).or_default();
+      *uses += 1;
+
+      let name_suffix = if *uses == 1 {
+        format!("-{file_name}.csv")
+      } else {
+        format!("-{file_name}_{uses}.csv")
+      };
+
+      RenderedReport::new(&name_suffix, render_table(table))
+    })
+    .collect()
+}
+
+fn render_table(table: &ReportTable) -> String {
+  std::iter::once(&table.headers)
+    .chain(&table.rows)
+    .map(|row| {
+      let values = row
+        .iter()
+        .map(|value| escape(value))
+        .collect::<Vec<String>>()
+        .join(",");
+
+      format!("{values}\n")
+    })
+    .collect()
+}
+
+/// Quotes the value if it contains a comma, quote, or new line.
+fn escape(value: &str) -> String {
+  if value.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", value.replace('"', "\"\""))
+  } else {
+    value.to_string()
+  }
+}
+
+/// Lowercases the title, replacing anything that isn't a letter or number with `_`.
+fn file_name(title: &str) -> String {
+  title
+    .split(|character: char| !character.is_alphanumeric())
+    .filter(|word| !word.is_empty())
+    .collect::<Vec<&str>>()
+    .join("_")
+    .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::report_formats::tests::test_report;
+  use crate::reports::report_section::ReportSection;
+
+  #[test]
+  fn each_table_is_its_own_file() {
+    let mut report = test_report();
+    report.sections.push(ReportSection::new(
+      String::new(),
+      vec![
+        ReportTable::new(
+          "= Top 1 Emotes Used =",
+          &["rank", "emote"],
+          vec![vec!["1".into(), "glorp, \"waaa\"".into()]],
+        ),
+        ReportTable::new("= Raids =", &["raider"], vec![]),
+      ],
+    ));
+
+    assert_eq!(
+      render(&report),
+      vec![
+        RenderedReport::new("-raids.csv", "raider,viewers\nlinkthedot,5\n".into()),
+        RenderedReport::new(
+          "-top_1_emotes_used.csv",
+          "rank,emote\n1,\"glorp, \"\"waaa\"\"\"\n".into()
+        ),
+        RenderedReport::new("-raids_2.csv", "raider\n".into()),
+      ]
+    );
+  }
+}
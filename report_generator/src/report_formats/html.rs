@@ -0,0 +1,89 @@
+use crate::reports::report_section::ReportTable;
+use crate::reports::Report;
+
+const STYLE: &str = r#"body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #999; padding: 0.25em 0.75em; text-align: left; }
+th { background: #eee; }
+pre { background: #f6f6f6; padding: 1em; }"#;
+
+/// Writes the report as a single page, with the styling included so it can be opened on its own.
+pub fn render(report: &Report) -> String {
+  let name = escape(report.name);
+  let mut body = format!("<h1>{name}</h1>\n");
+
+  for section in &report.sections {
+    if section.tables().is_empty() {
+      body.push_str(&format!("<pre>{}</pre>\n", escape(section.text())));
+
+      continue;
+    }
+
+    for table in section.tables() {
+      body.push_str(&render_table(table));
+    }
+  }
+
+  format!(
+    "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{name}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n{body}</body>\n</html>\n"
+  )
+}
+
+fn render_table(table: &ReportTable) -> String {
+  let headers = table
+    .headers
+    .iter()
+    .map(|header| format!("<th>{}</th>", escape(header)))
+    .collect::<String>();
+  let rows = table
+    .rows
+    .iter()
+    .map(|row| {
+      let values = row
+        .iter()
+        .map(|value| format!("<td>{}</td>", escape(value)))
+        .collect::<String>();
+
+      format!("<tr>{values}</tr>\n")
+    })
+    .collect::<String>();
+
+  format!(
+    "<h2>{}</h2>\n<table>\n<thead><tr>{headers}</tr></thead>\n<tbody>\n{rows}</tbody>\n</table>\n",
+    escape(&table.title)
+  )
+}
+
+fn escape(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::report_formats::tests::test_report;
+
+  #[test]
+  fn tables_and_text_are_written_as_html() {
+    let rendered_report = render(&test_report());
+
+    assert!(rendered_report.starts_with("<!DOCTYPE html>"));
+    assert!(rendered_report.contains(
+      "<h2>Raids</h2>\n<table>\n<thead><tr><th>raider</th><th>viewers</th></tr></thead>\n<tbody>\n<tr><td>linkthedot</td><td>5</td></tr>\n</tbody>\n</table>\n"
+    ));
+    assert!(rendered_report.contains("<pre>Messages sent: 10</pre>"));
+  }
+
+  #[test]
+  fn values_are_escaped() {
+    assert_eq!(
+      escape("<b>\"glorp\" & 'waaa'</b>"),
+      "&lt;b&gt;&quot;glorp&quot; &amp; &#39;waaa&#39;&lt;/b&gt;"
+    );
+  }
+}
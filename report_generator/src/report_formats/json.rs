@@ -0,0 +1,53 @@
+use crate::errors::AppError;
+use crate::reports::Report;
+use serde_json::json;
+
+/// Each section is written as its text, along with its tables if it has any.
+pub fn render(report: &Report) -> Result<String, AppError> {
+  let sections = report
+    .sections
+    .iter()
+    .map(|section| {
+      if section.tables().is_empty() {
+        json!({ "text": section.text() })
+      } else {
+        json!({ "text": section.text(), "tables": section.tables() })
+      }
+    })
+    .collect::<Vec<serde_json::Value>>();
+
+  Ok(serde_json::to_string_pretty(&json!({
+    "name": report.name,
+    "sections": sections,
+  }))?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::report_formats::tests::test_report;
+
+  #[test]
+  fn tables_are_written_as_data_alongside_the_text() {
+    let rendered_report: serde_json::Value =
+      serde_json::from_str(&render(&test_report()).unwrap()).unwrap();
+
+    assert_eq!(
+      rendered_report,
+      json!({
+        "name": "general_stats",
+        "sections": [
+          {
+            "text": "= Raids =\nlinkthedot - 5 viewers\n",
+            "tables": [{
+              "title": "Raids",
+              "headers": ["raider", "viewers"],
+              "rows": [["linkthedot", "5"]],
+            }]
+          },
+          { "text": "Messages sent: 10" },
+        ],
+      })
+    );
+  }
+}
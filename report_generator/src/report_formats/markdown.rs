@@ -0,0 +1,65 @@
+use crate::reports::report_section::ReportTable;
+use crate::reports::Report;
+
+/// Tables are written as Markdown tables, and any other section is written in a code block to keep its spacing.
+pub fn render(report: &Report) -> String {
+  let mut markdown = format!("# {}\n", report.name);
+
+  for section in &report.sections {
+    if section.tables().is_empty() {
+      markdown.push_str(&format!("\n```\n{}\n```\n", section.text().trim_end()));
+
+      continue;
+    }
+
+    for table in section.tables() {
+      markdown.push_str(&render_table(table));
+    }
+  }
+
+  markdown
+}
+
+fn render_table(table: &ReportTable) -> String {
+  let header = render_row(&table.headers);
+  let separator = render_row(&vec!["---".to_string(); table.headers.len()]);
+  let rows = table
+    .rows
+    .iter()
+    .map(|row| render_row(row))
+    .collect::<String>();
+
+  format!("\n## {}\n\n{header}{separator}{rows}", table.title)
+}
+
+fn render_row(values: &[String]) -> String {
+  let values = values
+    .iter()
+    .map(|value| value.replace('|', "\\|").replace('\n', "<br>"))
+    .collect::<Vec<String>>()
+    .join(" | ");
+
+  format!("| {values} |\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::report_formats::tests::test_report;
+
+  #[test]
+  fn tables_and_text_are_written_as_markdown() {
+    assert_eq!(
+      render(&test_report()),
+      "# general_stats\n\n## Raids\n\n| raider | viewers |\n| --- | --- |\n| linkthedot | 5 |\n\n```\nMessages sent: 10\n```\n"
+    );
+  }
+
+  #[test]
+  fn pipes_in_values_are_escaped() {
+    assert_eq!(
+      render_row(&["a|b".into(), "c\nd".into()]),
+      "| a\\|b | c<br>d |\n"
+    );
+  }
+}
@@ -0,0 +1,102 @@
+use crate::errors::AppError;
+use crate::reports::Report;
+use std::str::FromStr;
+
+mod csv;
+mod html;
+mod json;
+mod markdown;
+
+/// The format reports are written in when they're exported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+  /// The plain text reports, as they've always been generated.
+  #[default]
+  Text,
+  Json,
+  Markdown,
+  /// A single HTML page with its styling included.
+  Html,
+  /// A CSV file for each table in the report.
+  Csv,
+}
+
+/// A single file of a rendered report.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RenderedReport {
+  /// Appended to the name of the report, such as `.json`.
+  pub name_suffix: String,
+  pub contents: String,
+}
+
+impl ReportFormat {
+  /// Renders the report into each of the files it's made up of.
+  pub fn render(&self, report: &Report) -> Result<Vec<RenderedReport>, AppError> {
+    let rendered_report = match self {
+      Self::Text => RenderedReport::new("", report.body.clone()),
+      Self::Json => RenderedReport::new(".json", json::render(report)?),
+      Self::Markdown => RenderedReport::new(".md", markdown::render(report)),
+      Self::Html => RenderedReport::new(".html", html::render(report)),
+      Self::Csv => return Ok(csv::render(report)),
+    };
+
+    Ok(vec![rendered_report])
+  }
+}
+
+impl RenderedReport {
+  pub fn new(name_suffix: &str, contents: String) -> Self {
+    Self {
+      name_suffix: name_suffix.to_string(),
+      contents,
+    }
+  }
+}
+
+impl FromStr for ReportFormat {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.to_lowercase().trim() {
+      "text" | "txt" => Ok(Self::Text),
+      "json" => Ok(Self::Json),
+      "markdown" | "md" => Ok(Self::Markdown),
+      "html" => Ok(Self::Html),
+      "csv" => Ok(Self::Csv),
+      _ => Err(format!("Invalid variant: {}", s)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::reports::report_section::{ReportSection, ReportTable};
+
+  pub fn test_report() -> Report {
+    let raids = ReportSection::new(
+      "= Raids =\nlinkthedot - 5 viewers\n".into(),
+      vec![ReportTable::new(
+        "= Raids =",
+        &["raider", "viewers"],
+        vec![vec!["linkthedot".into(), "5".into()]],
+      )],
+    );
+    let statistics = ReportSection::from_text("Messages sent: 10".into());
+
+    Report::build_report_from_list("general_stats", &[&raids, &statistics], "\n\n")
+  }
+
+  #[test]
+  fn text_reports_are_the_report_body() {
+    let report = test_report();
+
+    assert_eq!(
+      ReportFormat::Text.render(&report).unwrap(),
+      vec![RenderedReport::new(
+        "",
+        "= Raids =\nlinkthedot - 5 viewers\n\n\nMessages sent: 10".into()
+      )]
+    );
+  }
+}
@@ -0,0 +1,67 @@
+use crate::errors::AppError;
+use crate::report_sinks::{ReportSink, ReportUpload};
+use std::path::PathBuf;
+use tokio::fs;
+
+/// Writes reports into files under a directory.
+pub struct FileSink {
+  directory: PathBuf,
+  name_template: String,
+}
+
+impl FileSink {
+  pub fn new(directory: PathBuf, name_template: String) -> Self {
+    Self {
+      directory,
+      name_template,
+    }
+  }
+}
+
+#[async_trait::async_trait]
+impl ReportSink for FileSink {
+  fn name(&self) -> &'static str {
+    "file"
+  }
+
+  async fn send(&self, upload: &ReportUpload<'_>) -> Result<String, AppError> {
+    let report_path = self
+      .directory
+      .join(upload.apply_name_template(&self.name_template));
+
+    if let Some(report_directory) = report_path.parent() {
+      fs::create_dir_all(report_directory).await?;
+    }
+
+    fs::write(&report_path, upload.contents).await?;
+
+    Ok(report_path.display().to_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn reports_are_written_to_the_templated_path() {
+    let directory = std::env::temp_dir().join(format!("file_sink_test_{}", std::process::id()));
+    let file_sink = FileSink::new(directory.clone(), "{stream_id}/{report_name}".into());
+    let upload = ReportUpload {
+      stream_id: 900,
+      date: "17-10-26",
+      report_name: "general_stats",
+      contents: "glorp",
+    };
+
+    let report_path = file_sink.send(&upload).await.unwrap();
+
+    assert_eq!(
+      report_path,
+      directory.join("900/general_stats").display().to_string()
+    );
+    assert_eq!(fs::read_to_string(&report_path).await.unwrap(), "glorp");
+
+    fs::remove_dir_all(directory).await.unwrap();
+  }
+}
@@ -0,0 +1,117 @@
+use crate::clap::Args;
+use crate::errors::AppError;
+use app_config::report_sink_config::ReportSinkConfig;
+use app_config::AppConfig;
+
+pub mod file;
+pub mod pastebin;
+pub mod s3;
+pub mod webhook;
+
+/// A rendered report being sent to a sink.
+#[derive(Debug)]
+pub struct ReportUpload<'a> {
+  pub stream_id: i32,
+  /// The day the stream started, formatted as `dd-mm-yy`.
+  pub date: &'a str,
+  /// The name of the report, including the suffix of the format it was rendered in.
+  pub report_name: &'a str,
+  pub contents: &'a str,
+}
+
+/// A destination that reports can be sent to.
+#[async_trait::async_trait]
+pub trait ReportSink: Send + Sync {
+  /// The name shown when reporting where a report was sent.
+  fn name(&self) -> &'static str;
+
+  /// Sends the report, returning where it can be found.
+  async fn send(&self, upload: &ReportUpload<'_>) -> Result<String, AppError>;
+}
+
+impl ReportUpload<'_> {
+  /// Fills in the `{stream_id}`, `{date}` and `{report_name}` placeholders of a name template.
+  pub fn apply_name_template(&self, name_template: &str) -> String {
+    name_template
+      .replace("{stream_id}", &self.stream_id.to_string())
+      .replace("{date}", self.date)
+      .replace("{report_name}", self.report_name)
+  }
+}
+
+/// Returns the sinks to send reports to.
+///
+/// With `-f` reports are only written to `file_reports`. Otherwise the configured sinks are used,
+/// or pastebin if there are none.
+pub fn get_report_sinks() -> Vec<Box<dyn ReportSink>> {
+  if Args::generate_file_reports() {
+    return vec![report_sink_from_config(&ReportSinkConfig::default_file())];
+  }
+
+  configured_report_sinks()
+}
+
+/// Returns the sinks in the config, or pastebin if there are none.
+pub fn configured_report_sinks() -> Vec<Box<dyn ReportSink>> {
+  let report_sink_configs = AppConfig::report_sinks();
+
+  if report_sink_configs.is_empty() {
+    return vec![Box::new(pastebin::PastebinSink)];
+  }
+
+  report_sink_configs
+    .iter()
+    .map(report_sink_from_config)
+    .collect()
+}
+
+pub fn report_sink_from_config(report_sink_config: &ReportSinkConfig) -> Box<dyn ReportSink> {
+  match report_sink_config {
+    ReportSinkConfig::File {
+      directory,
+      name_template,
+    } => Box::new(file::FileSink::new(
+      directory.clone(),
+      name_template.clone(),
+    )),
+    ReportSinkConfig::S3 {
+      endpoint,
+      bucket,
+      region,
+      access_key_id,
+      secret_access_key,
+      key_template,
+    } => Box::new(s3::S3Sink {
+      endpoint: endpoint.clone(),
+      bucket: bucket.clone(),
+      region: region.clone(),
+      access_key_id: access_key_id.clone(),
+      secret_access_key: secret_access_key.clone(),
+      key_template: key_template.clone(),
+    }),
+    ReportSinkConfig::Webhook { url, format } => {
+      Box::new(webhook::WebhookSink::new(url.clone(), *format))
+    }
+    ReportSinkConfig::Pastebin => Box::new(pastebin::PastebinSink),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn name_templates_are_filled_in() {
+    let upload = ReportUpload {
+      stream_id: 900,
+      date: "17-10-26",
+      report_name: "general_stats.json",
+      contents: "",
+    };
+
+    assert_eq!(
+      upload.apply_name_template("{stream_id}/[{date}]|{report_name}"),
+      "900/[17-10-26]|general_stats.json"
+    );
+  }
+}
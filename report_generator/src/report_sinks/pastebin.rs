@@ -0,0 +1,19 @@
+use crate::errors::AppError;
+use crate::pastebin::generate_pastebin;
+use crate::report_sinks::{ReportSink, ReportUpload};
+
+/// Uploads reports to pastebin, named `[date]|report_name`.
+pub struct PastebinSink;
+
+#[async_trait::async_trait]
+impl ReportSink for PastebinSink {
+  fn name(&self) -> &'static str {
+    "pastebin"
+  }
+
+  async fn send(&self, upload: &ReportUpload<'_>) -> Result<String, AppError> {
+    let paste_name = format!("[{}]|{}", upload.date, upload.report_name);
+
+    generate_pastebin(paste_name, upload.contents).await
+  }
+}
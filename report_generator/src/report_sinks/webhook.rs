@@ -0,0 +1,164 @@
+use crate::errors::AppError;
+use crate::report_sinks::{ReportSink, ReportUpload};
+use app_config::report_sink_config::WebhookFormat;
+use app_config::secret_string::Secret;
+use chrono::Utc;
+
+/// Posts reports to a webhook, either as a Discord message with the report attached or as JSON.
+pub struct WebhookSink {
+  url: Secret,
+  format: WebhookFormat,
+}
+
+impl WebhookSink {
+  pub fn new(url: Secret, format: WebhookFormat) -> Self {
+    Self { url, format }
+  }
+}
+
+#[async_trait::async_trait]
+impl ReportSink for WebhookSink {
+  fn name(&self) -> &'static str {
+    match self.format {
+      WebhookFormat::Discord => "discord",
+      WebhookFormat::Generic => "webhook",
+    }
+  }
+
+  async fn send(&self, upload: &ReportUpload<'_>) -> Result<String, AppError> {
+    let url = Secret::read_secret_string(self.url.read_value());
+    let (content_type, body) = match self.format {
+      WebhookFormat::Discord => discord_message(upload),
+      WebhookFormat::Generic => (
+        "application/json".to_string(),
+        serde_json::to_vec(&serde_json::json!({
+          "stream_id": upload.stream_id,
+          "date": upload.date,
+          "name": upload.report_name,
+          "contents": upload.contents,
+        }))?,
+      ),
+    };
+
+    let response = reqwest::Client::new()
+      .post(url)
+      .header("content-type", content_type)
+      .body(body)
+      .send()
+      .await?;
+
+    if !response.status().is_success() {
+      return Err(AppError::ReportSinkRequestFailed {
+        sink: self.name(),
+        status: response.status(),
+        body: response.text().await?,
+      });
+    }
+
+    Ok(format!("Delivered with status {}", response.status()))
+  }
+}
+
+/// Builds a multipart body for a Discord message with the report attached as a file.
+///
+/// Returns the (content_type, body) of the message.
+fn discord_message(upload: &ReportUpload) -> (String, Vec<u8>) {
+  let boundary = format!(
+    "report-generator-{}",
+    Utc::now().timestamp_nanos_opt().unwrap_or_default()
+  );
+  let file_name = if upload.report_name.contains('.') {
+    upload.report_name.to_string()
+  } else {
+    format!("{}.txt", upload.report_name)
+  };
+  let payload = serde_json::json!({
+    "content": format!("[{}] {}", upload.date, upload.report_name),
+  });
+
+  let body = format!(
+    "--{boundary}\r\nContent-Disposition: form-data; name=\"payload_json\"\r\nContent-Type: application/json\r\n\r\n{payload}\r\n\
+     --{boundary}\r\nContent-Disposition: form-data; name=\"files[0]\"; filename=\"{file_name}\"\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n\
+     --{boundary}--\r\n",
+    upload.contents
+  );
+
+  (
+    format!("multipart/form-data; boundary={boundary}"),
+    body.into_bytes(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::testing_helper_methods::spawn_stand_in_server;
+
+  fn upload() -> ReportUpload<'static> {
+    ReportUpload {
+      stream_id: 900,
+      date: "17-10-26",
+      report_name: "general_stats",
+      contents: "glorp",
+    }
+  }
+
+  #[tokio::test]
+  async fn generic_webhooks_receive_json() {
+    let (address, received_request) = spawn_stand_in_server(204, "").await;
+    let webhook_sink = WebhookSink::new(
+      Secret::new(format!("http://{address}/hook")),
+      WebhookFormat::Generic,
+    );
+
+    webhook_sink.send(&upload()).await.unwrap();
+    let request = received_request.await.unwrap();
+
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/hook");
+    assert_eq!(
+      serde_json::from_slice::<serde_json::Value>(&request.body).unwrap(),
+      serde_json::json!({
+        "stream_id": 900,
+        "date": "17-10-26",
+        "name": "general_stats",
+        "contents": "glorp",
+      })
+    );
+  }
+
+  #[tokio::test]
+  async fn discord_webhooks_receive_the_report_as_a_file() {
+    let (address, received_request) = spawn_stand_in_server(200, "{}").await;
+    let webhook_sink = WebhookSink::new(
+      Secret::new(format!("http://{address}/api/webhooks/1/token")),
+      WebhookFormat::Discord,
+    );
+
+    webhook_sink.send(&upload()).await.unwrap();
+    let request = received_request.await.unwrap();
+    let body = String::from_utf8(request.body).unwrap();
+
+    assert!(request.headers["content-type"].starts_with("multipart/form-data; boundary="));
+    assert!(body.contains("{\"content\":\"[17-10-26] general_stats\"}"));
+    assert!(body.contains(
+      "name=\"files[0]\"; filename=\"general_stats.txt\"\r\nContent-Type: text/plain; charset=utf-8\r\n\r\nglorp\r\n"
+    ));
+  }
+
+  #[tokio::test]
+  async fn failed_deliveries_are_errors() {
+    let (address, _) = spawn_stand_in_server(404, "Unknown Webhook").await;
+    let webhook_sink = WebhookSink::new(
+      Secret::new(format!("http://{address}")),
+      WebhookFormat::Discord,
+    );
+
+    let result = webhook_sink.send(&upload()).await;
+
+    assert!(matches!(
+      result,
+      Err(AppError::ReportSinkRequestFailed { sink: "discord", body, .. }) if body == "Unknown Webhook"
+    ));
+  }
+}
@@ -1,15 +1,20 @@
-use crate::clap::Args;
-use crate::conditions::query_conditions::AppQueryConditions;
+use crate::conditions::query_conditions::{get_month_range, AppQueryConditions};
 use crate::errors::AppError;
+use crate::report_builders::tables::channel_notices::get_channel_notices_table;
 use crate::report_builders::tables::chat_messages::get_messages_sent_ranking;
+use crate::report_builders::tables::chat_timeline::get_chat_timeline_table;
+use crate::report_builders::tables::cheermotes::get_top_cheermotes_table;
+use crate::report_builders::tables::deleted_messages::get_deleted_messages_table;
 use crate::report_builders::tables::donation_rankings::get_donation_rankings_for_streamer_and_date;
+use crate::report_builders::tables::hype_trains::get_hype_trains_table;
 use crate::report_builders::tables::raids::get_raids_table;
 use crate::report_builders::tables::timeouts::get_timeouts_table;
 use crate::report_builders::tables::top_emotes::get_top_n_emotes_table;
 use crate::report_builders::templates::chat_statistics::ChatStatistics;
 use crate::report_builders::templates::template_renderer::TemplateRenderer;
+use crate::reports::report_options::ReportOptions;
+use crate::reports::report_section::ReportSection;
 use crate::reports::{Report, Reports};
-use chrono::{Datelike, NaiveDate, TimeZone, Utc};
 use database_connection::get_database_connection;
 
 const MONTHLY_RANKING_ROW_LIMIT: usize = 1000;
@@ -19,15 +24,19 @@ const REPORT_SECTION_SEPARATION: &str = "\n\n";
 pub async fn generate_reports(
   query_conditions: AppQueryConditions,
   streamer_twitch_user_id: i32,
+  report_options: &ReportOptions,
 ) -> Result<Reports, AppError> {
   let mut reports = Reports::default();
 
-  let monthly_conditions =
-    AppQueryConditions::from_month(Args::get_month(), streamer_twitch_user_id)?;
+  let monthly_conditions = AppQueryConditions::from_month(
+    report_options.year,
+    report_options.month,
+    streamer_twitch_user_id,
+  )?;
 
   let baseline_reports = get_baseline_reports(query_conditions, &monthly_conditions).await?;
   let conditional_reports =
-    get_conditional_reports(&monthly_conditions, streamer_twitch_user_id).await?;
+    get_conditional_reports(&monthly_conditions, streamer_twitch_user_id, report_options).await?;
 
   reports.add_reports(baseline_reports);
   reports.add_reports(conditional_reports);
@@ -59,25 +68,47 @@ async fn get_baseline_reports(
     )
     .await?;
 
-  let rendered_chat_statistics = template_renderer.render("general_stats")?;
-  let rendered_donation_statistics = template_renderer.render("donation_stats")?;
+  let rendered_chat_statistics = ReportSection::new(
+    template_renderer.render("general_stats")?,
+    vec![general_chat_statistics.chat_table()],
+  );
+  let rendered_donation_statistics = ReportSection::new(
+    template_renderer.render("donation_stats")?,
+    vec![general_chat_statistics.donation_table()],
+  );
   let top_emotes_table =
     get_top_n_emotes_table(&query_conditions, database_connection, Some(15)).await?;
   let raids = get_raids_table(&query_conditions, database_connection).await?;
+  let hype_trains = get_hype_trains_table(&query_conditions, database_connection).await?;
   let timeouts = get_timeouts_table(&query_conditions, database_connection).await?;
+  let deleted_messages = get_deleted_messages_table(&query_conditions, database_connection).await?;
+  let channel_notices = get_channel_notices_table(&query_conditions, database_connection).await?;
+  let top_cheermotes = get_top_cheermotes_table(&query_conditions, database_connection).await?;
+  let chat_timeline = get_chat_timeline_table(&query_conditions, database_connection).await?;
 
   template_renderer.add_context(ChatStatistics::NAME, &monthly_general_chat_statistics);
 
-  let monthly_rendered_chat_statistics = template_renderer.render("general_stats")?;
-  let monthly_rendered_donation_statistics = template_renderer.render("donation_stats")?;
+  let monthly_rendered_chat_statistics = ReportSection::new(
+    template_renderer.render("general_stats")?,
+    vec![monthly_general_chat_statistics.chat_table()],
+  );
+  let monthly_rendered_donation_statistics = ReportSection::new(
+    template_renderer.render("donation_stats")?,
+    vec![monthly_general_chat_statistics.donation_table()],
+  );
   let monthly_top_emotes_table =
     get_top_n_emotes_table(&query_conditions, database_connection, Some(15)).await?;
+  let monthly_top_cheermotes =
+    get_top_cheermotes_table(monthly_conditions, database_connection).await?;
 
   let general_stats_report = Report::build_report_from_list(
     "general_stats",
     &[
       &raids,
+      &hype_trains,
       &timeouts,
+      &deleted_messages,
+      &channel_notices,
       &top_emotes_table,
       &rendered_chat_statistics,
     ],
@@ -92,10 +123,14 @@ async fn get_baseline_reports(
     "general_stats_with_donations",
     &[
       &raids,
+      &hype_trains,
       &timeouts,
+      &deleted_messages,
+      &channel_notices,
       &top_emotes_table,
       &rendered_chat_statistics,
       &rendered_donation_statistics,
+      &top_cheermotes,
     ],
     REPORT_SECTION_SEPARATION,
   );
@@ -103,9 +138,11 @@ async fn get_baseline_reports(
     "monthly_general_stats_with_donations",
     &[
       &raids,
+      &hype_trains,
       &monthly_top_emotes_table,
       &monthly_rendered_chat_statistics,
       &monthly_rendered_donation_statistics,
+      &monthly_top_cheermotes,
     ],
     REPORT_SECTION_SEPARATION,
   );
@@ -113,15 +150,19 @@ async fn get_baseline_reports(
   let (unfiltered_chat_report, emote_filtered_chat_report) =
     get_messages_sent_ranking(&query_conditions, None).await?;
 
-  let reports = vec![
+  let mut reports = vec![
     general_stats_report,
     monthly_general_stats_report,
-    Report::new("unfiltered_chat_rankings", unfiltered_chat_report),
-    Report::new("filtered_chat_rankings", emote_filtered_chat_report),
+    Report::from_section("unfiltered_chat_rankings", unfiltered_chat_report),
+    Report::from_section("filtered_chat_rankings", emote_filtered_chat_report),
     general_stats_report_with_donations,
     monthly_general_stats_report_with_donations,
   ];
 
+  if !chat_timeline.is_empty() {
+    reports.push(Report::from_section("chat_timeline", chat_timeline));
+  }
+
   Ok(reports)
 }
 
@@ -129,25 +170,19 @@ async fn get_baseline_reports(
 async fn get_conditional_reports(
   monthly_conditions: &AppQueryConditions,
   streamer_twitch_user_id: i32,
+  report_options: &ReportOptions,
 ) -> Result<Vec<Report>, AppError> {
   let mut conditional_reports = vec![];
 
   tracing::info!("Generating donation rankings.");
-  let current_date = chrono::Local::now();
-  let year = Args::get_year().unwrap_or(current_date.year() as usize) as i32;
-  let month = Args::get_month().unwrap_or(current_date.month() as usize) as u32;
-
-  let date_start = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-  let date_start = Utc.from_utc_datetime(&date_start.and_hms_opt(0, 0, 0).unwrap());
-  let date_end = NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap();
-  let date_end = Utc.from_utc_datetime(&date_end.and_hms_opt(0, 0, 0).unwrap());
+  let (date_start, date_end) = get_month_range(report_options.year, report_options.month)?;
 
   let donator_monthly_rankings_result =
     get_donation_rankings_for_streamer_and_date(streamer_twitch_user_id, date_start, date_end)
       .await;
 
   match donator_monthly_rankings_result {
-    Ok(donator_monthly_rankings) => conditional_reports.push(Report::new(
+    Ok(donator_monthly_rankings) => conditional_reports.push(Report::from_section(
       "donator_monthly_rankings",
       donator_monthly_rankings,
     )),
@@ -157,16 +192,16 @@ async fn get_conditional_reports(
     ),
   }
 
-  if Args::run_monthly_chat_ranking() {
+  if report_options.run_monthly_chat_ranking {
     tracing::info!("Generating monthly chat message rankings.");
     let (monthly_unfiltered_chat_report, monthly_emote_filtered_chat_report) =
       get_messages_sent_ranking(monthly_conditions, Some(MONTHLY_RANKING_ROW_LIMIT)).await?;
 
-    conditional_reports.push(Report::new(
+    conditional_reports.push(Report::from_section(
       "monthly_unfiltered_chat_rankings",
       monthly_unfiltered_chat_report,
     ));
-    conditional_reports.push(Report::new(
+    conditional_reports.push(Report::from_section(
       "monthly_emote_filtered_chat_rankings",
       monthly_emote_filtered_chat_report,
     ));
@@ -6,6 +6,8 @@ pub enum ChosenReport {
   Basic,
   Subathon,
   CalculateSubathonPoints,
+  /// A recap of a single chatter's activity in the channel.
+  UserRecap,
 }
 
 impl FromStr for ChosenReport {
@@ -16,6 +18,7 @@ impl FromStr for ChosenReport {
       "basic" => Ok(Self::Basic),
       "subathon" => Ok(Self::Subathon),
       "calculate_subathon_points" => Ok(Self::CalculateSubathonPoints),
+      "user_recap" => Ok(Self::UserRecap),
       _ => Err(format!("Invalid variant: {}", s)),
     }
   }
@@ -1,7 +1,13 @@
 pub mod basic_reports;
 pub mod chosen_report;
+pub mod report_options;
+pub mod report_section;
 pub mod subathon_points;
 pub mod subathon_reports;
+pub mod total_reports;
+pub mod user_recap_reports;
+
+use report_section::ReportSection;
 
 #[derive(Debug, Default)]
 pub struct Reports {
@@ -12,6 +18,7 @@ pub struct Reports {
 pub struct Report {
   pub name: &'static str,
   pub body: String,
+  pub sections: Vec<ReportSection>,
 }
 
 impl Reports {
@@ -26,17 +33,38 @@ impl Reports {
 
 impl Report {
   pub fn new(name: &'static str, body: String) -> Self {
-    Self { name, body }
+    Self::from_section(name, ReportSection::from_text(body))
+  }
+
+  pub fn from_section(name: &'static str, section: ReportSection) -> Self {
+    Self {
+      name,
+      body: section.text().to_string(),
+      sections: vec![section],
+    }
   }
 
-  pub fn build_report_from_list(name: &'static str, reports: &[&str], join_string: &str) -> Self {
-    let report_body = reports
+  pub fn build_report_from_list(
+    name: &'static str,
+    sections: &[&ReportSection],
+    join_string: &str,
+  ) -> Self {
+    let sections = sections
       .iter()
-      .filter(|t| !t.is_empty())
+      .filter(|section| !section.is_empty())
       .copied()
+      .cloned()
+      .collect::<Vec<ReportSection>>();
+    let report_body = sections
+      .iter()
+      .map(ReportSection::text)
       .collect::<Vec<&str>>()
       .join(join_string);
 
-    Self::new(name, report_body)
+    Self {
+      name,
+      body: report_body,
+      sections,
+    }
   }
 }
@@ -0,0 +1,30 @@
+use crate::clap::Args;
+use chrono::{DateTime, Utc};
+
+/// The settings that change what goes into the generated reports.
+///
+/// The report generator takes these from its arguments, while reports generated by the tracker use the defaults.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReportOptions {
+  /// The year of the monthly reports. Defaults to the current year.
+  pub year: Option<i32>,
+  /// The month of the monthly reports. Defaults to the current month.
+  pub month: Option<usize>,
+  pub run_monthly_chat_ranking: bool,
+  /// Required for subathon reports.
+  pub subathon_start_date: Option<DateTime<Utc>>,
+  /// Defaults to the time the reports are generated.
+  pub subathon_end_date: Option<DateTime<Utc>>,
+}
+
+impl ReportOptions {
+  pub fn from_args() -> Self {
+    Self {
+      year: Args::get_year().map(|year| year as i32),
+      month: Args::get_month(),
+      run_monthly_chat_ranking: Args::run_monthly_chat_ranking(),
+      subathon_start_date: Args::subathon_start_date().cloned(),
+      subathon_end_date: Args::subathon_end_date().cloned(),
+    }
+  }
+}
@@ -0,0 +1,152 @@
+use serde::Serialize;
+use tabled::Table;
+
+/// A part of a report, holding both the text shown in plain text reports and the data it was built from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportSection {
+  text: String,
+  tables: Vec<ReportTable>,
+}
+
+/// A titled table of values within a report section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportTable {
+  pub title: String,
+  pub headers: Vec<String>,
+  pub rows: Vec<Vec<String>>,
+}
+
+impl ReportSection {
+  pub fn new(text: String, tables: Vec<ReportTable>) -> Self {
+    Self { text, tables }
+  }
+
+  /// Creates a section that has no tables, such as rendered templates.
+  pub fn from_text(text: String) -> Self {
+    Self {
+      text,
+      tables: vec![],
+    }
+  }
+
+  pub fn text(&self) -> &str {
+    &self.text
+  }
+
+  pub fn tables(&self) -> &[ReportTable] {
+    &self.tables
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.text.is_empty()
+  }
+}
+
+impl ReportTable {
+  /// Creates a table with the given title. Any `=` surrounding a text header such as `= Raids =` are removed.
+  pub fn new(title: &str, headers: &[&str], rows: Vec<Vec<String>>) -> Self {
+    Self {
+      title: title
+        .trim_matches(|character| character == '=' || character == ' ')
+        .to_string(),
+      headers: headers.iter().map(ToString::to_string).collect(),
+      rows,
+    }
+  }
+
+  /// Creates a table with a row for each named value, for sections that are a list of statistics.
+  pub fn from_values(title: &str, values: Vec<(&str, String)>) -> Self {
+    let rows = values
+      .into_iter()
+      .map(|(name, value)| vec![name.to_string(), value])
+      .collect();
+
+    Self::new(title, &["statistic", "value"], rows)
+  }
+
+  /// Takes the headers and rows out of a [`Table`](tabled::Table).
+  ///
+  /// This should be called before any panels are added to the table, as they're stored as rows.
+  pub fn from_tabled(title: &str, table: &Table) -> Self {
+    let mut records = table
+      .get_records()
+      .iter()
+      .map(|row| row.iter().map(|cell| cell.as_ref().to_string()).collect());
+    let headers = records.next().unwrap_or_default();
+
+    Self {
+      title: title.to_string(),
+      headers,
+      rows: records.collect(),
+    }
+  }
+}
+
+/// Joins each row's values with ` - `, putting each row on its own line.
+pub fn rows_to_lines(rows: &[Vec<String>]) -> String {
+  rows
+    .iter()
+    .map(|row| row.join(" - "))
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tabled::Tabled;
+
+  #[derive(Tabled)]
+  struct Entry {
+    name: &'static str,
+    #[tabled(rename = "bits_donated")]
+    bits: usize,
+  }
+
+  #[test]
+  fn tabled_tables_are_split_into_headers_and_rows() {
+    let table = Table::new([
+      Entry {
+        name: "linkthedot",
+        bits: 100,
+      },
+      Entry {
+        name: "fallenshadow",
+        bits: 50,
+      },
+    ]);
+
+    assert_eq!(
+      ReportTable::from_tabled("Bit Donations", &table),
+      ReportTable::new(
+        "== Bit Donations ==",
+        &["name", "bits_donated"],
+        vec![
+          vec!["linkthedot".into(), "100".into()],
+          vec!["fallenshadow".into(), "50".into()],
+        ]
+      )
+    );
+  }
+
+  #[test]
+  fn values_are_put_on_their_own_rows() {
+    assert_eq!(
+      ReportTable::from_values(
+        "= Chat statistics =",
+        vec![
+          ("total_chats", "10".into()),
+          ("first_time_chatters", "2".into())
+        ]
+      ),
+      ReportTable::new(
+        "Chat statistics",
+        &["statistic", "value"],
+        vec![
+          vec!["total_chats".into(), "10".into()],
+          vec!["first_time_chatters".into(), "2".into()],
+        ]
+      )
+    );
+  }
+}
@@ -4,6 +4,7 @@ use crate::{
 };
 use chrono::Utc;
 use database_connection::get_database_connection;
+use num_traits::cast::ToPrimitive;
 
 pub async fn get_points_for_subathon(streamer_twitch_user_id: i32) -> Result<i32, AppError> {
   let Some(subathon_start_date) = Args::subathon_start_date().cloned() else {
@@ -21,5 +22,5 @@ pub async fn get_points_for_subathon(streamer_twitch_user_id: i32) -> Result<i32
   let total_points =
     SubathonStatistics::points_from_donations(&subathon_conditions, database_connection).await?;
 
-  Ok(total_points as i32)
+  Ok(total_points.trunc().to_i32().unwrap_or_default())
 }
@@ -1,9 +1,9 @@
-use crate::clap::Args;
 use crate::conditions::query_conditions::AppQueryConditions;
 use crate::conditions::query_conditions_builder::AppQueryConditionsBuilder;
 use crate::errors::AppError;
 use crate::report_builders::tables::{
-  chat_messages::get_messages_sent_ranking,
+  channel_notices::get_channel_notices_table, chat_messages::get_messages_sent_ranking,
+  chat_timeline::get_chat_timeline_table, deleted_messages::get_deleted_messages_table,
   donation_rankings::get_donation_rankings_for_streamer_and_date, raids::get_raids_table,
   timeouts::get_timeouts_table, top_emotes::get_top_n_emotes_table,
 };
@@ -11,6 +11,8 @@ use crate::report_builders::templates::subathon_statistics::SubathonStatistics;
 use crate::report_builders::templates::{
   chat_statistics::ChatStatistics, template_renderer::TemplateRenderer,
 };
+use crate::reports::report_options::ReportOptions;
+use crate::reports::report_section::ReportSection;
 use crate::reports::{Report, Reports};
 use chrono::Utc;
 use database_connection::get_database_connection;
@@ -23,15 +25,19 @@ const REPORT_SECTION_SEPARATION: &str = "\n\n";
 pub async fn generate_reports(
   query_conditions: AppQueryConditions,
   streamer_twitch_user_id: i32,
+  report_options: &ReportOptions,
 ) -> Result<Reports, AppError> {
   let mut reports = Reports::default();
-  let Some(subathon_start_date) = Args::subathon_start_date().cloned() else {
+  let Some(subathon_start_date) = report_options.subathon_start_date else {
     return Err(AppError::MissingSubathonStartTime);
   };
-  let subathon_end_date = Args::subathon_end_date().cloned().unwrap_or(Utc::now());
+  let subathon_end_date = report_options.subathon_end_date.unwrap_or(Utc::now());
 
-  let monthly_conditions =
-    AppQueryConditions::from_month(Args::get_month(), streamer_twitch_user_id)?;
+  let monthly_conditions = AppQueryConditions::from_month(
+    report_options.year,
+    report_options.month,
+    streamer_twitch_user_id,
+  )?;
   let subathon_conditions =
     AppQueryConditionsBuilder::copy_from_existing_query_conditions(&query_conditions)
       .set_time_range(subathon_start_date, subathon_end_date)?
@@ -48,6 +54,7 @@ pub async fn generate_reports(
     &monthly_conditions,
     &subathon_conditions,
     streamer_twitch_user_id,
+    report_options,
   )
   .await?;
 
@@ -92,13 +99,26 @@ async fn get_baseline_reports(
     ])
     .await?;
 
-  let rendered_chat_statistics = template_renderer.render("general_stats")?;
-  let rendered_donation_statistics = template_renderer.render("donation_stats")?;
-  let rendered_subathon_statistics = template_renderer.render("subathon_stats")?;
+  let rendered_chat_statistics = ReportSection::new(
+    template_renderer.render("general_stats")?,
+    vec![general_chat_statistics.chat_table()],
+  );
+  let rendered_donation_statistics = ReportSection::new(
+    template_renderer.render("donation_stats")?,
+    vec![general_chat_statistics.donation_table()],
+  );
+  let rendered_subathon_statistics = ReportSection::new(
+    template_renderer.render("subathon_stats")?,
+    vec![subathon_statistics.table()],
+  );
   let top_emotes_table =
     get_top_n_emotes_table(subathon_conditions, database_connection, Some(15)).await?;
   let raids = get_raids_table(subathon_conditions, database_connection).await?;
   let timeouts = get_timeouts_table(subathon_conditions, database_connection).await?;
+  let deleted_messages =
+    get_deleted_messages_table(subathon_conditions, database_connection).await?;
+  let channel_notices = get_channel_notices_table(subathon_conditions, database_connection).await?;
+  let chat_timeline = get_chat_timeline_table(query_conditions, database_connection).await?;
 
   tracing::info!("Building report strings.");
 
@@ -107,6 +127,8 @@ async fn get_baseline_reports(
     &[
       &raids,
       &timeouts,
+      &deleted_messages,
+      &channel_notices,
       &top_emotes_table,
       &rendered_chat_statistics,
       &rendered_subathon_statistics,
@@ -118,6 +140,8 @@ async fn get_baseline_reports(
     &[
       &raids,
       &timeouts,
+      &deleted_messages,
+      &channel_notices,
       &top_emotes_table,
       &rendered_chat_statistics,
       &rendered_subathon_statistics,
@@ -135,21 +159,25 @@ async fn get_baseline_reports(
 
   tracing::info!("Gathering reports.");
 
-  let reports = vec![
+  let mut reports = vec![
     general_stats_report,
-    Report::new("unfiltered_chat_rankings", unfiltered_chat_report),
-    Report::new("filtered_chat_rankings", emote_filtered_chat_report),
-    Report::new(
+    Report::from_section("unfiltered_chat_rankings", unfiltered_chat_report),
+    Report::from_section("filtered_chat_rankings", emote_filtered_chat_report),
+    Report::from_section(
       "unfiltered_subathon_chat_report",
       unfiltered_subathon_chat_report,
     ),
-    Report::new(
+    Report::from_section(
       "subathon_emote_filtered_chat_report",
       subathon_emote_filtered_chat_report,
     ),
     general_stats_report_with_donations,
   ];
 
+  if !chat_timeline.is_empty() {
+    reports.push(Report::from_section("chat_timeline", chat_timeline));
+  }
+
   Ok(reports)
 }
 
@@ -158,6 +186,7 @@ async fn get_conditional_reports(
   monthly_conditions: &AppQueryConditions,
   subathon_conditions: &AppQueryConditions,
   streamer_twitch_user_id: i32,
+  report_options: &ReportOptions,
 ) -> Result<Vec<Report>, AppError> {
   tracing::info!("Generating conditional reports.");
 
@@ -173,7 +202,7 @@ async fn get_conditional_reports(
   .await;
 
   match donator_monthly_rankings_result {
-    Ok(donator_subathon_rankings) => conditional_reports.push(Report::new(
+    Ok(donator_subathon_rankings) => conditional_reports.push(Report::from_section(
       "donator_subathon_rankings",
       donator_subathon_rankings,
     )),
@@ -183,17 +212,17 @@ async fn get_conditional_reports(
     ),
   }
 
-  if Args::run_monthly_chat_ranking() {
+  if report_options.run_monthly_chat_ranking {
     tracing::info!("Generating monthly chat message rankings.");
 
     let (monthly_unfiltered_chat_report, monthly_emote_filtered_chat_report) =
       get_messages_sent_ranking(monthly_conditions, Some(MONTHLY_RANKING_ROW_LIMIT)).await?;
 
-    conditional_reports.push(Report::new(
+    conditional_reports.push(Report::from_section(
       "monthly_unfiltered_chat_rankings",
       monthly_unfiltered_chat_report,
     ));
-    conditional_reports.push(Report::new(
+    conditional_reports.push(Report::from_section(
       "monthly_emote_filtered_chat_rankings",
       monthly_emote_filtered_chat_report,
     ));
@@ -0,0 +1,135 @@
+use crate::conditions::query_conditions::AppQueryConditions;
+use crate::conditions::query_conditions_builder::AppQueryConditionsBuilder;
+use crate::errors::AppError;
+use crate::report_builders::tables::cheermotes::get_top_cheermotes_table;
+use crate::report_builders::tables::top_emotes::get_top_n_emotes_table;
+use crate::report_builders::templates::chat_statistics::ChatStatistics;
+use crate::report_builders::templates::template_renderer::TemplateRenderer;
+use crate::reports::report_section::ReportSection;
+use crate::reports::Report;
+use chrono::Datelike;
+use database_connection::get_database_connection;
+use entities::stream;
+use sea_orm::*;
+
+const REPORT_SECTION_SEPARATION: &str = "\n\n";
+
+/// Generates the all-time and per-year chat and donation reports for the given streamer.
+pub async fn generate_reports(streamer_twitch_user_id: i32) -> Result<Vec<Report>, AppError> {
+  let database_connection = get_database_connection().await;
+  let mut template_renderer = TemplateRenderer::new();
+
+  template_renderer
+    .add_many_templates_from_files(&[
+      (
+        "general_stats",
+        "report_generator/template_files/general_chat_stats",
+      ),
+      (
+        "donation_stats",
+        "report_generator/template_files/donation_stats",
+      ),
+    ])
+    .await?;
+
+  tracing::info!("Generating all-time totals.");
+  let all_time_conditions = AppQueryConditionsBuilder::new()
+    .set_streamer_twitch_user_id(streamer_twitch_user_id)
+    .build()?;
+  let all_time_totals = get_totals_sections(
+    &all_time_conditions,
+    &mut template_renderer,
+    database_connection,
+  )
+  .await?;
+
+  let mut yearly_totals = vec![];
+
+  for year in get_streamed_years(streamer_twitch_user_id, database_connection).await? {
+    tracing::info!("Generating totals for {year}.");
+    let year_conditions = AppQueryConditionsBuilder::new()
+      .set_streamer_twitch_user_id(streamer_twitch_user_id)
+      .set_year(year)?
+      .build()?;
+    let year_totals = get_totals_sections(
+      &year_conditions,
+      &mut template_renderer,
+      database_connection,
+    )
+    .await?;
+
+    yearly_totals.push(ReportSection::from_text(format!("=== {year} ===")));
+    yearly_totals.extend(year_totals);
+  }
+
+  Ok(vec![
+    Report::build_report_from_list(
+      "all_time_totals",
+      &all_time_totals.iter().collect::<Vec<&ReportSection>>(),
+      REPORT_SECTION_SEPARATION,
+    ),
+    Report::build_report_from_list(
+      "yearly_totals",
+      &yearly_totals.iter().collect::<Vec<&ReportSection>>(),
+      REPORT_SECTION_SEPARATION,
+    ),
+  ])
+}
+
+/// Gets the chat and donation statistics sections for the given conditions.
+async fn get_totals_sections(
+  query_conditions: &AppQueryConditions,
+  template_renderer: &mut TemplateRenderer,
+  database_connection: &DatabaseConnection,
+) -> Result<Vec<ReportSection>, AppError> {
+  let chat_statistics = ChatStatistics::new(query_conditions).await?;
+
+  template_renderer.add_context(ChatStatistics::NAME, &chat_statistics);
+
+  let rendered_chat_statistics = ReportSection::new(
+    template_renderer.render("general_stats")?,
+    vec![chat_statistics.chat_table()],
+  );
+  let rendered_donation_statistics = ReportSection::new(
+    template_renderer.render("donation_stats")?,
+    vec![chat_statistics.donation_table()],
+  );
+  let top_emotes_table =
+    get_top_n_emotes_table(query_conditions, database_connection, Some(15)).await?;
+  let top_cheermotes = get_top_cheermotes_table(query_conditions, database_connection).await?;
+
+  Ok(vec![
+    top_emotes_table,
+    rendered_chat_statistics,
+    rendered_donation_statistics,
+    top_cheermotes,
+  ])
+}
+
+/// Returns the years between the streamer's first and latest streams.
+async fn get_streamed_years(
+  streamer_twitch_user_id: i32,
+  database_connection: &DatabaseConnection,
+) -> Result<Vec<i32>, AppError> {
+  let streams = stream::Entity::find()
+    .filter(stream::Column::TwitchUserId.eq(streamer_twitch_user_id))
+    .filter(stream::Column::StartTimestamp.is_not_null());
+
+  let first_stream = streams
+    .clone()
+    .order_by_asc(stream::Column::StartTimestamp)
+    .one(database_connection)
+    .await?;
+  let latest_stream = streams
+    .order_by_desc(stream::Column::StartTimestamp)
+    .one(database_connection)
+    .await?;
+
+  let first_year = first_stream.and_then(|stream| stream.start_timestamp.map(|time| time.year()));
+  let latest_year = latest_stream.and_then(|stream| stream.start_timestamp.map(|time| time.year()));
+
+  match (first_year, latest_year) {
+    (Some(first_year), Some(latest_year)) => Ok((first_year..=latest_year).collect()),
+    _ => Ok(vec![]),
+  }
+}
@@ -0,0 +1,218 @@
+use crate::conditions::query_conditions::{get_month_range, get_year_range};
+use crate::errors::AppError;
+use crate::reports::report_options::ReportOptions;
+use crate::reports::report_section::{rows_to_lines, ReportSection, ReportTable};
+use crate::reports::{Report, Reports};
+use chrono::{Datelike, TimeDelta, Utc};
+use database_connection::get_database_connection;
+use entities::twitch_user;
+use entity_extensions::twitch_user::*;
+use entity_extensions::user_recap::UserRecap;
+use sea_orm::EntityTrait;
+
+const REPORT_SECTION_SEPARATION: &str = "\n\n";
+const RANK_HISTORY_HEADER: &str = "= Rank History =";
+const FAVOURITE_EMOTES_HEADER: &str = "= Favourite Emotes =";
+const DONATIONS_HEADER: &str = "= Donations Given =";
+const NAME_CHANGES_HEADER: &str = "= Name Changes =";
+
+/// Generates a recap of everything the user did in the streamer's channel.
+///
+/// Covers the month chosen in the report options, or the whole year if there's no month.
+/// The year defaults to the current one.
+pub async fn generate_reports(
+  streamer_twitch_user_id: i32,
+  recap_user_login: &str,
+  report_options: &ReportOptions,
+) -> Result<Reports, AppError> {
+  let database_connection = get_database_connection().await;
+  let Some(user) = twitch_user::Model::get_by_identifier(
+    ChannelIdentifier::Login(recap_user_login),
+    database_connection,
+  )
+  .await?
+  else {
+    return Err(AppError::UserNotFound(recap_user_login.to_string()));
+  };
+  let Some(channel) = twitch_user::Entity::find_by_id(streamer_twitch_user_id)
+    .one(database_connection)
+    .await?
+  else {
+    return Err(AppError::UserNotFound(streamer_twitch_user_id.to_string()));
+  };
+  let (period_start, period_end) = match report_options.month {
+    Some(month) => get_month_range(report_options.year, Some(month))?,
+    None => get_year_range(report_options.year.unwrap_or(Utc::now().year()))?,
+  };
+
+  tracing::info!(
+    "Building the recap for {} in {}'s channel.",
+    user.login_name,
+    channel.login_name
+  );
+
+  let user_recap =
+    UserRecap::new(user, channel, period_start, period_end, database_connection).await?;
+  let sections = build_user_recap_sections(&user_recap);
+  let mut reports = Reports::default();
+
+  reports.add_reports(vec![Report::build_report_from_list(
+    "user_recap",
+    &sections.iter().collect::<Vec<&ReportSection>>(),
+    REPORT_SECTION_SEPARATION,
+  )]);
+
+  Ok(reports)
+}
+
+fn build_user_recap_sections(user_recap: &UserRecap) -> Vec<ReportSection> {
+  let overview = format!(
+    "= {}'s recap for {} =\n{} to {}\n\nMessages sent: {}\nStreams attended: {} of {}\nLongest streak of streams attended: {}\nGift subs given: {}\nTimeouts received: {}",
+    user_recap.user.display_name,
+    user_recap.channel.display_name,
+    user_recap.period_start.format("%d-%m-%y"),
+    (user_recap.period_end - TimeDelta::seconds(1)).format("%d-%m-%y"),
+    user_recap.message_count,
+    user_recap.streams_attended,
+    user_recap.total_streams,
+    user_recap.longest_attendance_streak,
+    user_recap.gift_subs_given,
+    user_recap.timeouts_received,
+  );
+  let rank_history_rows = user_recap
+    .rank_history
+    .iter()
+    .map(|monthly_rank| {
+      vec![
+        format!("{}-{:02}", monthly_rank.year, monthly_rank.month),
+        monthly_rank
+          .rank
+          .map(|rank| format!("#{rank} of {}", monthly_rank.chatter_count))
+          .unwrap_or("unranked".into()),
+        format!("{} messages", monthly_rank.message_count),
+      ]
+    })
+    .collect();
+  let favourite_emote_rows = user_recap
+    .favourite_emotes
+    .iter()
+    .map(|emote_uses| vec![emote_uses.name.clone(), format!("{} uses", emote_uses.uses)])
+    .collect();
+  let donation_rows = user_recap
+    .donations
+    .iter()
+    .map(|donation_total| {
+      vec![
+        format!("{:?}", donation_total.event_type),
+        format!("{} donations", donation_total.count),
+        match &donation_total.currency {
+          Some(currency) => format!("{} {currency}", donation_total.amount.normalize()),
+          None => donation_total.amount.normalize().to_string(),
+        },
+      ]
+    })
+    .collect();
+  let name_change_rows = user_recap
+    .name_changes
+    .iter()
+    .map(|name_change| {
+      vec![
+        name_change.created_at.format("%d-%m-%y").to_string(),
+        name_change.previous_login_name.clone().unwrap_or_default(),
+        name_change.new_login_name.clone().unwrap_or_default(),
+      ]
+    })
+    .collect();
+
+  vec![
+    ReportSection::from_text(overview),
+    table_section(
+      RANK_HISTORY_HEADER,
+      &["month", "rank", "messages"],
+      rank_history_rows,
+    ),
+    table_section(
+      FAVOURITE_EMOTES_HEADER,
+      &["emote", "uses"],
+      favourite_emote_rows,
+    ),
+    table_section(
+      DONATIONS_HEADER,
+      &["type", "donations", "amount"],
+      donation_rows,
+    ),
+    table_section(
+      NAME_CHANGES_HEADER,
+      &["date", "previous_login", "new_login"],
+      name_change_rows,
+    ),
+  ]
+}
+
+/// Returns an empty section if there are no rows, so it's left out of the report.
+fn table_section(header: &str, columns: &[&str], rows: Vec<Vec<String>>) -> ReportSection {
+  if rows.is_empty() {
+    return ReportSection::default();
+  }
+
+  ReportSection::new(
+    format!("{header}\n{}", rows_to_lines(&rows)),
+    vec![ReportTable::new(header, columns, rows)],
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::testing_helper_methods::timestamp_from_string;
+  use entity_extensions::user_recap::{EmoteUses, MonthlyRank};
+
+  #[test]
+  fn recap_sections_skip_empty_tables() {
+    let user = |id: i32, name: &str| twitch_user::Model {
+      id,
+      twitch_id: id,
+      login_name: name.to_lowercase(),
+      display_name: name.into(),
+    };
+    let user_recap = UserRecap {
+      user: user(2, "LinkTheDot"),
+      channel: user(1, "FallenShadow"),
+      period_start: timestamp_from_string("1735689600000"),
+      period_end: timestamp_from_string("1738368000000"),
+      message_count: 120,
+      rank_history: vec![MonthlyRank {
+        year: 2025,
+        month: 1,
+        rank: Some(3),
+        message_count: 120,
+        chatter_count: 40,
+      }],
+      favourite_emotes: vec![EmoteUses {
+        name: "Kappa".into(),
+        uses: 12,
+      }],
+      streams_attended: 4,
+      total_streams: 5,
+      longest_attendance_streak: 3,
+      donations: vec![],
+      gift_subs_given: 0,
+      timeouts_received: 1,
+      name_changes: vec![],
+    };
+
+    let sections = build_user_recap_sections(&user_recap);
+    let report = Report::build_report_from_list(
+      "user_recap",
+      &sections.iter().collect::<Vec<&ReportSection>>(),
+      REPORT_SECTION_SEPARATION,
+    );
+
+    assert_eq!(
+      report.body,
+      "= LinkTheDot's recap for FallenShadow =\n01-01-25 to 31-01-25\n\nMessages sent: 120\nStreams attended: 4 of 5\nLongest streak of streams attended: 3\nGift subs given: 0\nTimeouts received: 1\n\n= Rank History =\n2025-01 - #3 of 40 - 120 messages\n\n= Favourite Emotes =\nKappa - 12 uses"
+    );
+    assert_eq!(report.sections.len(), 3);
+    assert_eq!(report.sections[1].tables()[0].title, "Rank History");
+  }
+}
@@ -14,6 +14,7 @@ pub fn generate_message(message_id: i32, user_id: i32, contents: &str) -> stream
     stream_id: None,
     is_subscriber: 1_i8,
     origin_id: Some("0".into()),
+    deleted_at: None,
   }
 }
 
@@ -23,3 +24,81 @@ pub fn timestamp_from_string(value: &str) -> DateTime<Utc> {
 
   chrono::Utc.timestamp_millis_opt(timestamp).unwrap()
 }
+
+/// A request received by a server from [`spawn_stand_in_server`].
+#[derive(Debug)]
+pub struct StandInRequest {
+  pub method: String,
+  pub path: String,
+  /// Keyed by the lowercase header name.
+  pub headers: std::collections::HashMap<String, String>,
+  pub body: Vec<u8>,
+}
+
+/// Starts a server on a local port that stands in for an external service, such as object storage or a webhook.
+///
+/// The server answers a single request with the given status and body.
+/// Returns the address of the server, and a handle that resolves to the request it received.
+pub async fn spawn_stand_in_server(
+  status: u16,
+  response_body: &'static str,
+) -> (
+  std::net::SocketAddr,
+  tokio::task::JoinHandle<StandInRequest>,
+) {
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let address = listener.local_addr().unwrap();
+
+  let received_request = tokio::spawn(async move {
+    let (mut stream, _) = listener.accept().await.unwrap();
+    let mut received = vec![];
+    let mut buffer = [0; 4096];
+
+    let headers_end = loop {
+      let read = stream.read(&mut buffer).await.unwrap();
+      received.extend_from_slice(&buffer[..read]);
+
+      if let Some(position) = received.windows(4).position(|bytes| bytes == b"\r\n\r\n") {
+        break position;
+      }
+    };
+
+    let head = String::from_utf8(received[..headers_end].to_vec()).unwrap();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap().split(' ');
+    let method = request_line.next().unwrap().to_string();
+    let path = request_line.next().unwrap().to_string();
+    let headers: std::collections::HashMap<String, String> = lines
+      .filter_map(|line| line.split_once(':'))
+      .map(|(name, value)| (name.to_lowercase(), value.trim().to_string()))
+      .collect();
+
+    let content_length = headers
+      .get("content-length")
+      .map(|length| length.parse::<usize>().unwrap())
+      .unwrap_or(0);
+    let mut body = received[headers_end + 4..].to_vec();
+
+    while body.len() < content_length {
+      let read = stream.read(&mut buffer).await.unwrap();
+      body.extend_from_slice(&buffer[..read]);
+    }
+
+    let response = format!(
+      "HTTP/1.1 {status} Stand-In\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response_body}",
+      response_body.len()
+    );
+    stream.write_all(response.as_bytes()).await.unwrap();
+
+    StandInRequest {
+      method,
+      path,
+      headers,
+      body,
+    }
+  });
+
+  (address, received_request)
+}
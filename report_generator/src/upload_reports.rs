@@ -1,68 +1,172 @@
 use crate::clap::Args;
-use crate::reports::{Report, Reports};
-use crate::{errors::AppError, pastebin::generate_pastebin};
+use crate::errors::AppError;
+use crate::report_formats::{RenderedReport, ReportFormat};
+use crate::report_sinks::{get_report_sinks, ReportSink, ReportUpload};
+use crate::reports::Reports;
 use entities::stream;
-use std::path::PathBuf;
-use tokio::{fs, io::AsyncWriteExt};
 
-const FILE_REPORTS_DIR: &str = "file_reports";
+/// A rendered report that was sent to a sink.
+#[derive(Debug)]
+pub struct SentReport {
+  /// The name of the report, including the suffix of the format it was rendered in.
+  pub report_name: String,
+  pub sink: &'static str,
+  /// Where the sink put the report, or why it couldn't be sent there.
+  pub result: Result<String, AppError>,
+}
 
-/// Uploads the reports given.
+impl SentReport {
+  pub fn was_sent(&self) -> bool {
+    self.result.is_ok()
+  }
+}
+
+/// Sends the reports given to each report sink.
+///
+/// Reports go to the sinks in the config, or pastebin if there are none. If the `-f` flag is passed in,
+/// the reports are written to files in `file_reports` instead.
 ///
-/// Takes (report_name, report_string) and uploads them to pastebin or if the `-f` flag is passed in.
-/// Writes the reports to files instead.
+/// Reports are rendered in the format passed in with `--format`, which is plain text by default.
 pub async fn upload_reports(stream: stream::Model, reports: Reports) -> Result<(), AppError> {
+  let sent_reports = send_reports(
+    &stream,
+    &reports,
+    Args::report_format(),
+    &get_report_sinks(),
+  )
+  .await?;
+
+  for SentReport {
+    report_name,
+    sink,
+    result,
+  } in sent_reports
+  {
+    match result {
+      Ok(location) => println!("{} ({}):\n  {}", report_name, sink, location),
+      Err(error) => println!("{} ({}) failed:\n  {}", report_name, sink, error),
+    }
+  }
+
+  Ok(())
+}
+
+/// Renders the reports in the given format and sends every rendered file to each of the sinks.
+///
+/// Each sink's result is returned for every report, whether it was sent or not.
+pub async fn send_reports(
+  stream: &stream::Model,
+  reports: &Reports,
+  report_format: ReportFormat,
+  report_sinks: &[Box<dyn ReportSink>],
+) -> Result<Vec<SentReport>, AppError> {
   let stream_start_time = stream
     .start_timestamp
     .unwrap()
     .format("%d-%m-%y")
     .to_string();
+  let mut sent_reports = vec![];
 
-  for Report {
-    name: report_name,
-    body: report,
-  } in reports.get_reports()
-  {
-    let report_date_and_name = format!("[{stream_start_time}]|{report_name}");
-
-    if Args::generate_file_reports() {
-      let mut file_reports_dir = PathBuf::from(FILE_REPORTS_DIR);
-      file_reports_dir.push(stream.id.to_string());
-
-      fs::create_dir_all(&file_reports_dir).await?;
-
-      let mut file_reports_path = file_reports_dir;
-      file_reports_path.push(&report_date_and_name);
-
-      let mut report_file = fs::OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .create(true)
-        .open(&file_reports_path)
-        .await?;
-
-      if let Err(error) = report_file.write(report.as_bytes()).await {
-        tracing::error!(
-          "Failed to write report {} into a file. Reason: {:?}",
-          report_date_and_name,
-          error
-        );
-      }
+  for report in reports.get_reports() {
+    for RenderedReport {
+      name_suffix,
+      contents,
+    } in report_format.render(report)?
+    {
+      let report_name = format!("{}{name_suffix}", report.name);
+      let upload = ReportUpload {
+        stream_id: stream.id,
+        date: &stream_start_time,
+        report_name: &report_name,
+        contents: &contents,
+      };
+
+      for report_sink in report_sinks {
+        let result = report_sink.send(&upload).await;
 
-      println!("Report {:?} generated.", report_name);
-    } else {
-      match generate_pastebin(&report_date_and_name, report).await {
-        Ok(pastebin_url) => println!("{}:\n  {}", report_name, pastebin_url),
-        Err(error) => {
+        if let Err(error) = &result {
           tracing::error!(
-            "Failed to generate pastebin for {}. Reason: {:?}",
-            report_date_and_name,
+            "Failed to send report {} to {}. Reason: {:?}",
+            report_name,
+            report_sink.name(),
             error
           );
         }
+
+        sent_reports.push(SentReport {
+          report_name: report_name.clone(),
+          sink: report_sink.name(),
+          result,
+        });
       }
     }
   }
 
-  Ok(())
+  Ok(sent_reports)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::report_sinks::ReportUpload;
+  use crate::reports::Report;
+
+  struct TestSink {
+    name: &'static str,
+    fails: bool,
+  }
+
+  #[async_trait::async_trait]
+  impl ReportSink for TestSink {
+    fn name(&self) -> &'static str {
+      self.name
+    }
+
+    async fn send(&self, upload: &ReportUpload<'_>) -> Result<String, AppError> {
+      if self.fails {
+        return Err(AppError::InvalidReportSinkUrl(upload.report_name.into()));
+      }
+
+      Ok(format!("{}/{}", self.name, upload.report_name))
+    }
+  }
+
+  #[tokio::test]
+  async fn each_sink_reports_its_own_result() {
+    let stream = stream::Model {
+      id: 1,
+      twitch_stream_id: 1,
+      start_timestamp: Some("2025-05-08T12:00:00Z".parse().unwrap()),
+      end_timestamp: None,
+      twitch_user_id: 1,
+    };
+    let mut reports = Reports::default();
+    reports.add_reports(vec![Report::new(
+      "general_stats",
+      "Messages sent: 10".into(),
+    )]);
+    let report_sinks: Vec<Box<dyn ReportSink>> = vec![
+      Box::new(TestSink {
+        name: "file",
+        fails: false,
+      }),
+      Box::new(TestSink {
+        name: "webhook",
+        fails: true,
+      }),
+    ];
+
+    let sent_reports = send_reports(&stream, &reports, ReportFormat::Text, &report_sinks)
+      .await
+      .unwrap();
+
+    assert_eq!(sent_reports.len(), 2);
+    assert_eq!(sent_reports[0].sink, "file");
+    assert_eq!(
+      sent_reports[0].result.as_deref().ok(),
+      Some("file/general_stats")
+    );
+    assert_eq!(sent_reports[1].sink, "webhook");
+    assert!(!sent_reports[1].was_sent());
+  }
 }
@@ -0,0 +1,287 @@
+use app_config::AppConfig;
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use database_connection::{
+  get_owned_database_connection_for, get_unmigrated_database_connection_for,
+};
+use irc::proto::{Command, Message as IrcMessage};
+use sea_orm::DatabaseConnection;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use twitch_chat_tracker::channel::third_party_emote_list_storage::EmoteListStorage;
+use twitch_chat_tracker::errors::AppError;
+use twitch_chat_tracker::irc_chat::message_archive::{ArchivedMessage, MessageArchive};
+use twitch_chat_tracker::irc_chat::message_parser::MessageParser;
+use twitch_chat_tracker::irc_chat::mirrored_twitch_objects::{
+  message::TwitchIrcMessage, twitch_message_type::TwitchMessageType,
+};
+use twitch_chat_tracker::irc_chat::twitch_irc::TwitchIrc;
+use twitch_chat_tracker::processes::replay_message_spool::message_already_stored;
+
+/// Feeds a raw IRC archive back through the message parser.
+#[derive(Parser, Debug)]
+#[command(name = "replay")]
+struct ReplayArgs {
+  /// An archive file, or a directory of archive files.
+  #[arg(short = 'a', long, required = true)]
+  archive: PathBuf,
+
+  /// The database to store the messages in. Defaults to the database in the config.
+  #[arg(short = 'd', long)]
+  database: Option<String>,
+
+  /// Report what would be stored without storing anything.
+  #[arg(long)]
+  dry_run: bool,
+
+  /// Skip messages received before this time. Formatted as RFC 3339.
+  #[arg(long)]
+  since: Option<DateTime<Utc>>,
+
+  /// Skip messages received after this time. Formatted as RFC 3339.
+  #[arg(long)]
+  until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+struct ReplaySummary {
+  /// Messages that were stored, or would be stored in a dry run, by type.
+  stored: HashMap<TwitchMessageType, usize>,
+  already_stored: usize,
+  ignored: usize,
+  unparseable: usize,
+  failed: usize,
+}
+
+#[tokio::main]
+async fn main() {
+  twitch_chat_tracker::logging::setup_logging_config().unwrap();
+
+  let args = ReplayArgs::parse();
+  let archive_files = match archive_files(&args.archive) {
+    Ok(archive_files) => archive_files,
+    Err(error) => {
+      tracing::error!(
+        "Failed to read the archive at {:?}. Reason: {}",
+        args.archive,
+        error
+      );
+
+      std::process::exit(1);
+    }
+  };
+
+  let database_name = args.database.as_deref().unwrap_or(AppConfig::database());
+  // A dry run connects without creating or migrating the database, so nothing is written.
+  let database_connection = if args.dry_run {
+    get_unmigrated_database_connection_for(database_name).await
+  } else {
+    get_owned_database_connection_for(database_name).await
+  };
+  let channel_names = archived_channel_names(read_archive(&archive_files, &args));
+
+  tracing::info!(
+    "Replaying {} archive files from {} channels.",
+    archive_files.len(),
+    channel_names.len()
+  );
+
+  // Building the emote lists stores emotes, so it's skipped when nothing should be written.
+  let third_party_emote_lists = if args.dry_run {
+    None
+  } else {
+    Some(
+      EmoteListStorage::new_without_memberships(&channel_names, &database_connection)
+        .await
+        .unwrap(),
+    )
+  };
+
+  let summary = replay_messages(
+    read_archive(&archive_files, &args),
+    third_party_emote_lists.as_ref(),
+    &database_connection,
+  )
+  .await;
+
+  print_summary(&summary, args.dry_run);
+}
+
+fn archive_files(archive_path: &Path) -> Result<Vec<PathBuf>, AppError> {
+  if archive_path.is_dir() {
+    MessageArchive::archive_files(archive_path)
+  } else {
+    Ok(vec![archive_path.to_path_buf()])
+  }
+}
+
+/// Reads the archive one message at a time, skipping messages outside of `--since` and `--until`.
+///
+/// If a file can't be read, the error is logged and the rest of that file is skipped.
+fn read_archive<'a>(
+  archive_files: &'a [PathBuf],
+  args: &'a ReplayArgs,
+) -> impl Iterator<Item = ArchivedMessage> + 'a {
+  archive_files
+    .iter()
+    .flat_map(|archive_file| {
+      tracing::info!("Reading archive file {:?}.", archive_file);
+
+      let archived_messages = MessageArchive::read_file(archive_file)
+        .inspect_err(|error| {
+          tracing::error!(
+            "Failed to read the archive file {:?}. Reason: {}",
+            archive_file,
+            error
+          )
+        })
+        .ok();
+
+      archived_messages
+        .into_iter()
+        .flatten()
+        .map_while(move |archived_message| {
+          archived_message
+            .inspect_err(|error| {
+              tracing::error!(
+                "Stopped reading the archive file {:?} partway through. Reason: {}",
+                archive_file,
+                error
+              )
+            })
+            .ok()
+        })
+    })
+    .filter(|archived_message| {
+      args
+        .since
+        .is_none_or(|since| archived_message.received_at >= since)
+        && args
+          .until
+          .is_none_or(|until| archived_message.received_at <= until)
+    })
+}
+
+/// The names of every channel a message in the archive was sent to.
+fn archived_channel_names(archived_messages: impl Iterator<Item = ArchivedMessage>) -> Vec<String> {
+  let channel_names: BTreeSet<String> = archived_messages
+    .filter_map(|archived_message| match archived_message.message.command {
+      Command::PRIVMSG(target, _) => Some(target),
+      Command::Raw(_, arguments) => arguments.into_iter().next(),
+      _ => None,
+    })
+    .filter_map(|channel| channel.strip_prefix('#').map(str::to_owned))
+    .collect();
+
+  channel_names.into_iter().collect()
+}
+
+/// Stores every message that isn't already stored.
+///
+/// If no emote lists are given, it's a dry run, and messages are only counted.
+async fn replay_messages(
+  archived_messages: impl Iterator<Item = ArchivedMessage>,
+  third_party_emote_lists: Option<&EmoteListStorage>,
+  database_connection: &DatabaseConnection,
+) -> ReplaySummary {
+  let mut summary = ReplaySummary::default();
+
+  for ArchivedMessage { message, .. } in archived_messages {
+    let message = &message;
+
+    if TwitchIrc::is_ignored_command(message) || matches!(message.command, Command::PING(_, _)) {
+      summary.ignored += 1;
+
+      continue;
+    }
+
+    let message_type = match TwitchIrcMessage::new(message) {
+      Ok(Some(twitch_message)) => twitch_message.message_type(),
+      Ok(None) => {
+        summary.ignored += 1;
+
+        continue;
+      }
+      Err(error) => {
+        tracing::warn!(
+          "Failed to parse an archived message. Reason: {}. Message: {:?}",
+          error,
+          message
+        );
+        summary.unparseable += 1;
+
+        continue;
+      }
+    };
+
+    match message_already_stored(message, database_connection).await {
+      Ok(true) => {
+        summary.already_stored += 1;
+
+        continue;
+      }
+      Ok(false) => (),
+      Err(error) => {
+        tracing::error!(
+          "Failed to check if a message was already stored. Reason: {}",
+          error
+        );
+        summary.failed += 1;
+
+        continue;
+      }
+    }
+
+    let Some(third_party_emote_lists) = third_party_emote_lists else {
+      *summary.stored.entry(message_type).or_default() += 1;
+
+      continue;
+    };
+
+    match parse_message(message, third_party_emote_lists, database_connection).await {
+      Ok(()) => *summary.stored.entry(message_type).or_default() += 1,
+      Err(error) if error.is_unique_constraint_violation() => summary.already_stored += 1,
+      Err(error) => {
+        tracing::error!(
+          "Failed to replay an archived message. Reason: {}. Message: {:?}",
+          error,
+          message
+        );
+        summary.failed += 1;
+      }
+    }
+  }
+
+  summary
+}
+
+async fn parse_message(
+  message: &IrcMessage,
+  third_party_emote_lists: &EmoteListStorage,
+  database_connection: &DatabaseConnection,
+) -> Result<(), AppError> {
+  let Some(message_parser) = MessageParser::new(message, third_party_emote_lists)? else {
+    return Ok(());
+  };
+
+  message_parser.parse(database_connection).await
+}
+
+fn print_summary(summary: &ReplaySummary, dry_run: bool) {
+  let stored_label = if dry_run { "Would store" } else { "Stored" };
+  let mut stored: Vec<(&TwitchMessageType, &usize)> = summary.stored.iter().collect();
+  stored.sort_by_key(|(message_type, _)| message_type.to_string());
+
+  if dry_run {
+    println!("Dry run. Nothing was written to the database.");
+  }
+
+  for (message_type, count) in stored {
+    println!("{stored_label} {message_type}: {count}");
+  }
+
+  println!("Already stored: {}", summary.already_stored);
+  println!("Ignored: {}", summary.ignored);
+  println!("Unparseable: {}", summary.unparseable);
+  println!("Failed: {}", summary.failed);
+}
@@ -12,6 +12,9 @@ const _7TV_API_URL: &str = "https://7tv.io/v3/";
 const _BTTV_API_URL: &str = "https://api.betterttv.net/3/cached/";
 const _FRANKER_FACE_Z_API_URL: &str = "https://api.betterttv.net/3/cached/frankerfacez/";
 
+/// The ID of a 7TV emote set, and the (emote_name, emote_id) pairs in it.
+type SevenTvEmoteSet = (Option<String>, Vec<(String, String)>);
+
 // -= Global Emote Lists =-
 // https://7tv.io/v3/emote-sets/global
 // https://api.betterttv.net/3/cached/emotes/global
@@ -29,6 +32,11 @@ const _FRANKER_FACE_Z_API_URL: &str = "https://api.betterttv.net/3/cached/franke
 #[derive(Debug)]
 pub struct EmoteList {
   channel_name: String,
+  /// The ID of the 7TV emote set the emotes were taken from.
+  /// Used to listen for changes to the set through 7TV's EventAPI.
+  seventv_emote_set_id: Option<String>,
+  /// The services whose lists failed to be retrieved.
+  failed_services: Vec<ExternalService>,
   /// Key: emote_name | Value: EmoteModel
   emote_list: HashMap<String, emote::Model>,
 }
@@ -41,25 +49,71 @@ impl EmoteList {
     ("waaa", "01FTCXPJ200001E12995B12626"),
     ("glorpass", "01JAQC65ZG07ABT7PJ082ZTF9M"),
   ];
+  /// The order services are prioritized in when two emotes share a name.
+  ///
+  /// 7TV is preferred as it's the most commonly used, and channels tend to remove
+  /// conflicting emotes from the other services over time.
+  pub const SERVICE_PRECEDENCE: &[ExternalService] = &[
+    ExternalService::SevenTv,
+    ExternalService::Bttv,
+    ExternalService::FrankerFaceZ,
+  ];
 
   pub fn get_empty(channel_name: String) -> Self {
     Self {
       channel_name,
+      seventv_emote_set_id: None,
+      failed_services: Self::SERVICE_PRECEDENCE.to_vec(),
       emote_list: HashMap::default(),
     }
   }
 
+  /// Retrieves the 7TV, BTTV, and FrankerFaceZ emotes for the given channel.
+  ///
+  /// When multiple services share an emote name, the service that appears first in
+  /// [`SERVICE_PRECEDENCE`](Self::SERVICE_PRECEDENCE) is kept.
+  ///
+  /// A failure to retrieve the 7TV list is returned as an error. Failures for BTTV and FrankerFaceZ are logged,
+  /// and the list is built without them.
   pub async fn get_list(
     channel: &twitch_user::Model,
     database_connection: &DatabaseConnection,
   ) -> Result<Self, AppError> {
     tracing::info!("Getting emote list for channel {:?}", channel);
-    let _7tv = Self::get_7tv_list(channel, database_connection).await?;
+    let (seventv_emote_set_id, _7tv) = Self::get_7tv_list(channel, database_connection).await?;
+    let mut failed_services = vec![];
+    let bttv = Self::get_bttv_list(channel, database_connection)
+      .await
+      .unwrap_or_else(|error| {
+        tracing::error!(
+          "Failed to retrieve the BTTV emote list for channel {}. Reason: {:?}",
+          channel.login_name,
+          error
+        );
+
+        failed_services.push(ExternalService::Bttv);
+
+        HashMap::default()
+      });
+    let frankerfacez = Self::get_frankerfacez_list(channel, database_connection)
+      .await
+      .unwrap_or_else(|error| {
+        tracing::error!(
+          "Failed to retrieve the FrankerFaceZ emote list for channel {}. Reason: {:?}",
+          channel.login_name,
+          error
+        );
+
+        failed_services.push(ExternalService::FrankerFaceZ);
+
+        HashMap::default()
+      });
 
     Ok(Self {
       channel_name: channel.login_name.to_owned(),
-      emote_list: _7tv,
-      // TODO: implememnt bttv and frankerfacez querying
+      seventv_emote_set_id,
+      failed_services,
+      emote_list: Self::merge_service_lists(_7tv, bttv, frankerfacez),
     })
   }
 
@@ -91,113 +145,243 @@ impl EmoteList {
     for channel_name in AppConfig::TEST_CHANNELS {
       emote_lists.push(EmoteList {
         channel_name: channel_name.to_string(),
+        seventv_emote_set_id: None,
+        failed_services: vec![],
         emote_list: test_emotes.clone(),
       })
     }
 
     emote_lists.push(EmoteList {
       channel_name: Self::GLOBAL_NAME.to_string(),
+      seventv_emote_set_id: None,
+      failed_services: vec![],
       emote_list: test_emotes,
     });
 
     Some(emote_lists)
   }
 
+  /// Returns the ID of the channel's 7TV emote set along with the emotes in it.
   async fn get_7tv_list(
     channel: &twitch_user::Model,
     database_connection: &DatabaseConnection,
-  ) -> Result<HashMap<String, emote::Model>, AppError> {
+  ) -> Result<(Option<String>, HashMap<String, emote::Model>), AppError> {
     let mut user_query_url = Url::parse(_7TV_API_URL)?;
     let channel_path = format!("users/twitch/{}", channel.twitch_id);
     user_query_url = user_query_url.join(&channel_path)?;
 
-    Self::_7tv_emote_list(user_query_url, database_connection).await
+    let response_body = Self::query_7tv(user_query_url).await?;
+    let (emote_set_id, emotes) = Self::parse_7tv_user_emotes(&response_body)?;
+    let emotes = Self::store_emotes(emotes, ExternalService::SevenTv, database_connection).await?;
+
+    Ok((emote_set_id, emotes))
   }
 
-  // The global response body is formatted different from the regular users, so it lives in a separate method.
+  async fn get_bttv_list(
+    channel: &twitch_user::Model,
+    database_connection: &DatabaseConnection,
+  ) -> Result<HashMap<String, emote::Model>, AppError> {
+    let mut user_query_url = Url::parse(_BTTV_API_URL)?;
+    let channel_path = format!("users/twitch/{}", channel.twitch_id);
+    user_query_url = user_query_url.join(&channel_path)?;
+
+    let Some(response_body) = Self::query_bttv(user_query_url).await? else {
+      return Ok(HashMap::default());
+    };
+    let emotes = Self::parse_bttv_user_emotes(&response_body)?;
+
+    Self::store_emotes(emotes, ExternalService::Bttv, database_connection).await
+  }
+
+  async fn get_frankerfacez_list(
+    channel: &twitch_user::Model,
+    database_connection: &DatabaseConnection,
+  ) -> Result<HashMap<String, emote::Model>, AppError> {
+    let mut user_query_url = Url::parse(_FRANKER_FACE_Z_API_URL)?;
+    let channel_path = format!("users/twitch/{}", channel.twitch_id);
+    user_query_url = user_query_url.join(&channel_path)?;
+
+    let Some(response_body) = Self::query_bttv(user_query_url).await? else {
+      return Ok(HashMap::default());
+    };
+    let emotes = Self::parse_frankerfacez_emotes(&response_body)?;
+
+    Self::store_emotes(emotes, ExternalService::FrankerFaceZ, database_connection).await
+  }
+
+  /// Retrieves the global emotes for 7TV, BTTV, and FrankerFaceZ.
+  ///
+  /// Name collisions are resolved the same way as [`get_list`](Self::get_list).
   pub async fn get_global_emote_list(
     database_connection: &DatabaseConnection,
   ) -> Result<Self, AppError> {
+    let (seventv_emote_set_id, _7tv) = Self::get_global_7tv_list(database_connection).await?;
+    let mut failed_services = vec![];
+    let bttv = Self::get_global_bttv_list(database_connection)
+      .await
+      .unwrap_or_else(|error| {
+        tracing::error!(
+          "Failed to retrieve the global BTTV emote list. Reason: {:?}",
+          error
+        );
+
+        failed_services.push(ExternalService::Bttv);
+
+        HashMap::default()
+      });
+    let frankerfacez = Self::get_global_frankerfacez_list(database_connection)
+      .await
+      .unwrap_or_else(|error| {
+        tracing::error!(
+          "Failed to retrieve the global FrankerFaceZ emote list. Reason: {:?}",
+          error
+        );
+
+        failed_services.push(ExternalService::FrankerFaceZ);
+
+        HashMap::default()
+      });
+
+    Ok(Self {
+      channel_name: Self::GLOBAL_NAME.to_string(),
+      seventv_emote_set_id,
+      failed_services,
+      emote_list: Self::merge_service_lists(_7tv, bttv, frankerfacez),
+    })
+  }
+
+  async fn get_global_7tv_list(
+    database_connection: &DatabaseConnection,
+  ) -> Result<(Option<String>, HashMap<String, emote::Model>), AppError> {
     let mut _7tv_query_url = Url::parse(_7TV_API_URL)?;
     _7tv_query_url = _7tv_query_url.join("emote-sets/global")?;
-    // let _7tv = Self::_7tv_emote_list(client, _7tv_query_url).await?;
-    let reqwest_client = reqwest::Client::new();
 
-    let response = reqwest_client.get(_7tv_query_url).send().await?;
+    let response_body = Self::query_7tv(_7tv_query_url).await?;
+    let (emote_set_id, emotes) = Self::parse_7tv_global_emotes(&response_body)?;
+    let emotes = Self::store_emotes(emotes, ExternalService::SevenTv, database_connection).await?;
+
+    Ok((emote_set_id, emotes))
+  }
+
+  async fn get_global_bttv_list(
+    database_connection: &DatabaseConnection,
+  ) -> Result<HashMap<String, emote::Model>, AppError> {
+    let mut bttv_query_url = Url::parse(_BTTV_API_URL)?;
+    bttv_query_url = bttv_query_url.join("emotes/global")?;
+
+    let Some(response_body) = Self::query_bttv(bttv_query_url).await? else {
+      return Ok(HashMap::default());
+    };
+    let emotes = Self::parse_bttv_global_emotes(&response_body)?;
+
+    Self::store_emotes(emotes, ExternalService::Bttv, database_connection).await
+  }
+
+  async fn get_global_frankerfacez_list(
+    database_connection: &DatabaseConnection,
+  ) -> Result<HashMap<String, emote::Model>, AppError> {
+    let mut frankerfacez_query_url = Url::parse(_FRANKER_FACE_Z_API_URL)?;
+    frankerfacez_query_url = frankerfacez_query_url.join("emotes/global")?;
+
+    let Some(response_body) = Self::query_bttv(frankerfacez_query_url).await? else {
+      return Ok(HashMap::default());
+    };
+    let emotes = Self::parse_frankerfacez_emotes(&response_body)?;
+
+    Self::store_emotes(emotes, ExternalService::FrankerFaceZ, database_connection).await
+  }
+
+  async fn query_7tv(query_url: Url) -> Result<String, AppError> {
+    let reqwest_client = reqwest::Client::new();
+    let response = reqwest_client.get(query_url).send().await?;
     let response_body = response.text().await?;
 
     if response_body.contains("error code: ") {
       return Err(AppError::FailedToQuery7TVForEmoteList(response_body));
     }
 
-    let Value::Object(data) = serde_json::from_str(&response_body)? else {
-      tracing::error!("Unkown response: {:?}", response_body);
+    Ok(response_body)
+  }
 
-      return Err(AppError::UnknownResponseBody(
-        "global data from 7tv response body.",
-      ));
-    };
+  /// Queries the BTTV cache, which serves both the BTTV and FrankerFaceZ lists.
+  ///
+  /// None is returned if BTTV doesn't know of the user.
+  async fn query_bttv(query_url: Url) -> Result<Option<String>, AppError> {
+    let reqwest_client = reqwest::Client::new();
+    let response = reqwest_client.get(query_url).send().await?;
+    let response_status = response.status();
 
-    if let Some(Value::Number(error_code)) = data.get("error_code") {
-      if error_code.as_u64() == Some(12000) {
-        return Ok(Self {
-          channel_name: Self::GLOBAL_NAME.to_string(),
-          emote_list: HashMap::default(),
-        });
-      }
+    if response_status == reqwest::StatusCode::NOT_FOUND {
+      return Ok(None);
     }
 
-    let Some(Value::Array(emote_set)) = data.get("emotes") else {
-      tracing::error!("Unkown response: {:?}", response_body);
+    let response_body = response.text().await?;
 
-      return Err(AppError::UnknownResponseBody(
-        "global emote set from 7tv response body.",
-      ));
-    };
+    if !response_status.is_success() {
+      return Err(AppError::FailedToQueryBttvForEmoteList(response_body));
+    }
 
-    let mut emote_list: HashMap<String, emote::Model> = HashMap::new();
+    Ok(Some(response_body))
+  }
 
-    for emote_object in emote_set {
-      let Value::Object(emote_object_map) = emote_object else {
-        continue;
-      };
-      let Some(Value::String(emote_name)) = emote_object_map.get("name") else {
-        continue;
-      };
-      let Some(Value::String(emote_id)) = emote_object_map.get("id") else {
-        continue;
-      };
+  /// Gets or sets each (emote_name, emote_id) pair in the database under the given service.
+  async fn store_emotes(
+    emotes: Vec<(String, String)>,
+    external_service: ExternalService,
+    database_connection: &DatabaseConnection,
+  ) -> Result<HashMap<String, emote::Model>, AppError> {
+    let mut emote_list: HashMap<String, emote::Model> = HashMap::new();
 
+    for (emote_name, emote_id) in emotes {
       let emote = emote::Model::get_or_set_third_party_emote_by_external_id(
-        emote_id,
-        emote_name,
-        ExternalService::SevenTv,
+        &emote_id,
+        &emote_name,
+        external_service.clone(),
         database_connection,
       )
       .await?;
 
-      emote_list.insert(emote_name.to_owned(), emote);
+      emote_list.insert(emote_name, emote);
     }
 
-    Ok(Self {
-      channel_name: Self::GLOBAL_NAME.to_string(),
-      emote_list,
-    })
+    Ok(emote_list)
   }
 
-  async fn _7tv_emote_list(
-    query_url: Url,
-    database_connection: &DatabaseConnection,
-  ) -> Result<HashMap<String, emote::Model>, AppError> {
-    let reqwest_client = reqwest::Client::new();
-    let response = reqwest_client.get(query_url).send().await?;
-    let response_body = response.text().await?;
+  /// Combines the lists from each service, keeping the emote from the highest precedence
+  /// service in [`SERVICE_PRECEDENCE`](Self::SERVICE_PRECEDENCE) when names collide.
+  fn merge_service_lists(
+    _7tv: HashMap<String, emote::Model>,
+    bttv: HashMap<String, emote::Model>,
+    frankerfacez: HashMap<String, emote::Model>,
+  ) -> HashMap<String, emote::Model> {
+    let mut service_lists = [
+      (ExternalService::SevenTv, _7tv),
+      (ExternalService::Bttv, bttv),
+      (ExternalService::FrankerFaceZ, frankerfacez),
+    ];
+    let mut emote_list: HashMap<String, emote::Model> = HashMap::new();
 
-    if response_body.contains("error code: ") {
-      return Err(AppError::FailedToQuery7TVForEmoteList(response_body));
+    for service in Self::SERVICE_PRECEDENCE {
+      let Some((_, service_list)) = service_lists
+        .iter_mut()
+        .find(|(list_service, _)| list_service == service)
+      else {
+        continue;
+      };
+
+      for (emote_name, emote) in std::mem::take(service_list) {
+        emote_list.entry(emote_name).or_insert(emote);
+      }
     }
 
-    let Value::Object(data) = serde_json::from_str(&response_body)? else {
+    emote_list
+  }
+
+  /// Returns the emote set ID and the (emote_name, emote_id) pairs from a 7TV user response body.
+  ///
+  /// An empty list is returned if the user has no 7TV account.
+  fn parse_7tv_user_emotes(response_body: &str) -> Result<SevenTvEmoteSet, AppError> {
+    let Value::Object(data) = serde_json::from_str(response_body)? else {
       tracing::error!("Unkown response: {:?}", response_body);
 
       return Err(AppError::UnknownResponseBody(
@@ -207,7 +391,7 @@ impl EmoteList {
 
     if let Some(Value::Number(error_code)) = data.get("error_code") {
       if error_code.as_u64() == Some(12000) {
-        return Ok(HashMap::default());
+        return Ok((None, vec![]));
       }
     }
 
@@ -218,6 +402,10 @@ impl EmoteList {
         "emote set from 7tv response body.",
       ));
     };
+    let emote_set_id = emote_set
+      .get("id")
+      .and_then(Value::as_str)
+      .map(ToOwned::to_owned);
     let Some(Value::Array(emote_set)) = emote_set.get("emotes") else {
       tracing::error!("Unkown response: {:?}", response_body);
 
@@ -226,31 +414,122 @@ impl EmoteList {
       ));
     };
 
-    let mut emotes: HashMap<String, emote::Model> = HashMap::new();
+    Ok((emote_set_id, Self::parse_emote_array(emote_set, "name")))
+  }
+
+  // The global response body is formatted different from the regular users, so it lives in a separate method.
+  fn parse_7tv_global_emotes(response_body: &str) -> Result<SevenTvEmoteSet, AppError> {
+    let Value::Object(data) = serde_json::from_str(response_body)? else {
+      tracing::error!("Unkown response: {:?}", response_body);
+
+      return Err(AppError::UnknownResponseBody(
+        "global data from 7tv response body.",
+      ));
+    };
+
+    if let Some(Value::Number(error_code)) = data.get("error_code") {
+      if error_code.as_u64() == Some(12000) {
+        return Ok((None, vec![]));
+      }
+    }
+
+    let Some(Value::Array(emote_set)) = data.get("emotes") else {
+      tracing::error!("Unkown response: {:?}", response_body);
+
+      return Err(AppError::UnknownResponseBody(
+        "global emote set from 7tv response body.",
+      ));
+    };
+    let emote_set_id = data
+      .get("id")
+      .and_then(Value::as_str)
+      .map(ToOwned::to_owned);
+
+    Ok((emote_set_id, Self::parse_emote_array(emote_set, "name")))
+  }
+
+  /// Returns the (emote_name, emote_id) pairs from a BTTV user response body.
+  ///
+  /// Contains both the channel's own emotes and the ones it has shared from other users.
+  fn parse_bttv_user_emotes(response_body: &str) -> Result<Vec<(String, String)>, AppError> {
+    let Value::Object(data) = serde_json::from_str(response_body)? else {
+      tracing::error!("Unkown response: {:?}", response_body);
+
+      return Err(AppError::UnknownResponseBody(
+        "data from bttv response body.",
+      ));
+    };
+
+    let Some(Value::Array(channel_emotes)) = data.get("channelEmotes") else {
+      tracing::error!("Unkown response: {:?}", response_body);
+
+      return Err(AppError::UnknownResponseBody(
+        "channel emotes from bttv response body.",
+      ));
+    };
+    let Some(Value::Array(shared_emotes)) = data.get("sharedEmotes") else {
+      tracing::error!("Unkown response: {:?}", response_body);
+
+      return Err(AppError::UnknownResponseBody(
+        "shared emotes from bttv response body.",
+      ));
+    };
+
+    let mut emotes = Self::parse_emote_array(channel_emotes, "code");
+    emotes.extend(Self::parse_emote_array(shared_emotes, "code"));
+
+    Ok(emotes)
+  }
+
+  fn parse_bttv_global_emotes(response_body: &str) -> Result<Vec<(String, String)>, AppError> {
+    let Value::Array(emote_set) = serde_json::from_str(response_body)? else {
+      tracing::error!("Unkown response: {:?}", response_body);
+
+      return Err(AppError::UnknownResponseBody(
+        "global emote set from bttv response body.",
+      ));
+    };
+
+    Ok(Self::parse_emote_array(&emote_set, "code"))
+  }
+
+  /// FrankerFaceZ lists are retrieved through the BTTV cache, which uses the same format for both global and user lists.
+  fn parse_frankerfacez_emotes(response_body: &str) -> Result<Vec<(String, String)>, AppError> {
+    let Value::Array(emote_set) = serde_json::from_str(response_body)? else {
+      tracing::error!("Unkown response: {:?}", response_body);
+
+      return Err(AppError::UnknownResponseBody(
+        "emote set from frankerfacez response body.",
+      ));
+    };
+
+    Ok(Self::parse_emote_array(&emote_set, "code"))
+  }
+
+  /// Takes the name under `name_key` and the `id` from each emote object.
+  ///
+  /// FrankerFaceZ IDs are numbers, so they're converted to strings here.
+  /// Any malformed emote object is skipped.
+  fn parse_emote_array(emote_set: &[Value], name_key: &str) -> Vec<(String, String)> {
+    let mut emotes = vec![];
 
     for emote_object in emote_set {
       let Value::Object(emote_object_map) = emote_object else {
         continue;
       };
-      let Some(Value::String(emote_name)) = emote_object_map.get("name") else {
+      let Some(Value::String(emote_name)) = emote_object_map.get(name_key) else {
         continue;
       };
-      let Some(Value::String(emote_id)) = emote_object_map.get("id") else {
-        continue;
+      let emote_id = match emote_object_map.get("id") {
+        Some(Value::String(emote_id)) => emote_id.to_owned(),
+        Some(Value::Number(emote_id)) => emote_id.to_string(),
+        _ => continue,
       };
 
-      let emote = emote::Model::get_or_set_third_party_emote_by_external_id(
-        emote_id,
-        emote_name,
-        ExternalService::SevenTv,
-        database_connection,
-      )
-      .await?;
-
-      emotes.insert(emote_name.to_owned(), emote);
+      emotes.push((emote_name.to_owned(), emote_id));
     }
 
-    Ok(emotes)
+    emotes
   }
 
   /// Returns the combined list of 7tv, bttv, and frankerfacez emotes.
@@ -268,7 +547,253 @@ impl EmoteList {
     &self.channel_name
   }
 
+  pub fn seventv_emote_set_id(&self) -> Option<&str> {
+    self.seventv_emote_set_id.as_deref()
+  }
+
+  /// Returns false if the list is missing any service's emotes due to a failed query.
+  pub fn is_complete(&self) -> bool {
+    self.failed_services.is_empty()
+  }
+
+  /// Fills in the emotes of the services that failed to be retrieved with the ones from the previous list,
+  /// so an outage doesn't stop those emotes from being parsed.
+  ///
+  /// Name collisions are resolved with [`SERVICE_PRECEDENCE`](Self::SERVICE_PRECEDENCE).
+  pub fn keep_failed_services_from(&mut self, previous_list: &EmoteList) {
+    let precedence = |service: &ExternalService| {
+      Self::SERVICE_PRECEDENCE
+        .iter()
+        .position(|precedence_service| precedence_service == service)
+        .unwrap_or(Self::SERVICE_PRECEDENCE.len())
+    };
+
+    for (emote_name, previous_emote) in &previous_list.emote_list {
+      if !self
+        .failed_services
+        .contains(&previous_emote.external_service)
+      {
+        continue;
+      }
+
+      let keeps_current_emote = self
+        .emote_list
+        .get(emote_name)
+        .is_some_and(|current_emote| {
+          precedence(&current_emote.external_service)
+            <= precedence(&previous_emote.external_service)
+        });
+
+      if !keeps_current_emote {
+        self
+          .emote_list
+          .insert(emote_name.clone(), previous_emote.clone());
+      }
+    }
+  }
+
   pub fn get(&self, emote_name: &str) -> Option<&emote::Model> {
     self.emote_list.get(emote_name)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const _7TV_USER_FIXTURE: &str = include_str!("../../test_files/emote_lists/7tv_user.json");
+  const _7TV_UNKNOWN_USER_FIXTURE: &str =
+    include_str!("../../test_files/emote_lists/7tv_unknown_user.json");
+  const _7TV_GLOBAL_FIXTURE: &str = include_str!("../../test_files/emote_lists/7tv_global.json");
+  const BTTV_USER_FIXTURE: &str = include_str!("../../test_files/emote_lists/bttv_user.json");
+  const BTTV_GLOBAL_FIXTURE: &str = include_str!("../../test_files/emote_lists/bttv_global.json");
+  const FRANKERFACEZ_USER_FIXTURE: &str =
+    include_str!("../../test_files/emote_lists/frankerfacez_user.json");
+
+  fn pair(emote_name: &str, emote_id: &str) -> (String, String) {
+    (emote_name.to_string(), emote_id.to_string())
+  }
+
+  fn emote_model(id: i32, emote_name: &str, external_service: ExternalService) -> emote::Model {
+    emote::Model {
+      id,
+      external_id: id.to_string(),
+      name: emote_name.to_string(),
+      external_service,
+    }
+  }
+
+  #[test]
+  fn parse_7tv_user_emotes_works() {
+    let expected_emotes = vec![
+      pair("glorp", "01H16FA16G0005EZED5J0EY7KN"),
+      pair("waaa", "01FTCXPJ200001E12995B12626"),
+      pair("catJAM", "01F6MQ33FG000FFJ97ZB8MWV52"),
+    ];
+
+    let (emote_set_id, emotes) = EmoteList::parse_7tv_user_emotes(_7TV_USER_FIXTURE).unwrap();
+
+    assert_eq!(emote_set_id.as_deref(), Some("01HKQJ0B0G000BRZ1VQMSX9Q1E"));
+    assert_eq!(emotes, expected_emotes);
+  }
+
+  #[test]
+  fn parse_7tv_unknown_user_is_empty() {
+    let (emote_set_id, emotes) =
+      EmoteList::parse_7tv_user_emotes(_7TV_UNKNOWN_USER_FIXTURE).unwrap();
+
+    assert!(emote_set_id.is_none());
+    assert!(emotes.is_empty());
+  }
+
+  #[test]
+  fn parse_7tv_global_emotes_works() {
+    let expected_emotes = vec![
+      pair("EZ", "01F6NACCD80006SZ7ZW5FMWKWK"),
+      pair("Clap", "01EZPJ8YMR000C438200A44F9N"),
+    ];
+
+    let (emote_set_id, emotes) = EmoteList::parse_7tv_global_emotes(_7TV_GLOBAL_FIXTURE).unwrap();
+
+    assert_eq!(emote_set_id.as_deref(), Some("01HKQT8EWR000ESSWF3625XCS4"));
+    assert_eq!(emotes, expected_emotes);
+  }
+
+  #[test]
+  fn parse_bttv_user_emotes_includes_shared_emotes() {
+    let expected_emotes = vec![
+      pair("catJAM", "5f1b0186cf6d2144653d2970"),
+      pair("shadowWave", "60419081306b602acc5972c9"),
+      pair("KEKW", "5e0fa9d40550d42106b8a489"),
+    ];
+
+    let emotes = EmoteList::parse_bttv_user_emotes(BTTV_USER_FIXTURE).unwrap();
+
+    assert_eq!(emotes, expected_emotes);
+  }
+
+  #[test]
+  fn parse_bttv_global_emotes_works() {
+    let expected_emotes = vec![
+      pair("OhMyGoodness", "54fa925e01e468494b85b54d"),
+      pair("Clap", "54fa8f1401e468494b85b537"),
+    ];
+
+    let emotes = EmoteList::parse_bttv_global_emotes(BTTV_GLOBAL_FIXTURE).unwrap();
+
+    assert_eq!(emotes, expected_emotes);
+  }
+
+  #[test]
+  fn parse_frankerfacez_emotes_converts_numeric_ids() {
+    let expected_emotes = vec![pair("shadowWave", "720507"), pair("OMEGALUL", "128054")];
+
+    let emotes = EmoteList::parse_frankerfacez_emotes(FRANKERFACEZ_USER_FIXTURE).unwrap();
+
+    assert_eq!(emotes, expected_emotes);
+  }
+
+  #[test]
+  fn parsers_reject_unexpected_response_bodies() {
+    assert!(EmoteList::parse_7tv_user_emotes("[]").is_err());
+    assert!(EmoteList::parse_bttv_user_emotes(BTTV_GLOBAL_FIXTURE).is_err());
+    assert!(EmoteList::parse_bttv_global_emotes(BTTV_USER_FIXTURE).is_err());
+    assert!(EmoteList::parse_frankerfacez_emotes(r#"{"message":"Not Found"}"#).is_err());
+  }
+
+  #[test]
+  fn failed_services_keep_their_previous_emotes() {
+    let previous_list = EmoteList {
+      channel_name: "fallenshadow".to_string(),
+      seventv_emote_set_id: None,
+      failed_services: vec![],
+      emote_list: HashMap::from([
+        (
+          "catJAM".to_string(),
+          emote_model(1, "catJAM", ExternalService::Bttv),
+        ),
+        (
+          "KEKW".to_string(),
+          emote_model(2, "KEKW", ExternalService::Bttv),
+        ),
+        (
+          "OMEGALUL".to_string(),
+          emote_model(3, "OMEGALUL", ExternalService::FrankerFaceZ),
+        ),
+      ]),
+    };
+    let mut emote_list = EmoteList {
+      channel_name: "fallenshadow".to_string(),
+      seventv_emote_set_id: None,
+      failed_services: vec![ExternalService::Bttv],
+      emote_list: HashMap::from([
+        (
+          "catJAM".to_string(),
+          emote_model(4, "catJAM", ExternalService::SevenTv),
+        ),
+        (
+          "KEKW".to_string(),
+          emote_model(5, "KEKW", ExternalService::FrankerFaceZ),
+        ),
+      ]),
+    };
+
+    emote_list.keep_failed_services_from(&previous_list);
+
+    assert_eq!(emote_list.emote_list().len(), 2);
+    assert_eq!(
+      emote_list.get("catJAM").unwrap().external_service,
+      ExternalService::SevenTv
+    );
+    assert_eq!(
+      emote_list.get("KEKW").unwrap().external_service,
+      ExternalService::Bttv
+    );
+    assert!(emote_list.get("OMEGALUL").is_none());
+    assert!(!emote_list.is_complete());
+  }
+
+  #[test]
+  fn merge_service_lists_follows_service_precedence() {
+    let _7tv = HashMap::from([(
+      "catJAM".to_string(),
+      emote_model(1, "catJAM", ExternalService::SevenTv),
+    )]);
+    let bttv = HashMap::from([
+      (
+        "catJAM".to_string(),
+        emote_model(2, "catJAM", ExternalService::Bttv),
+      ),
+      (
+        "shadowWave".to_string(),
+        emote_model(3, "shadowWave", ExternalService::Bttv),
+      ),
+    ]);
+    let frankerfacez = HashMap::from([
+      (
+        "shadowWave".to_string(),
+        emote_model(4, "shadowWave", ExternalService::FrankerFaceZ),
+      ),
+      (
+        "OMEGALUL".to_string(),
+        emote_model(5, "OMEGALUL", ExternalService::FrankerFaceZ),
+      ),
+    ]);
+
+    let emote_list = EmoteList::merge_service_lists(_7tv, bttv, frankerfacez);
+
+    assert_eq!(emote_list.len(), 3);
+    assert_eq!(
+      emote_list.get("catJAM").unwrap().external_service,
+      ExternalService::SevenTv
+    );
+    assert_eq!(
+      emote_list.get("shadowWave").unwrap().external_service,
+      ExternalService::Bttv
+    );
+    assert_eq!(
+      emote_list.get("OMEGALUL").unwrap().external_service,
+      ExternalService::FrankerFaceZ
+    );
+  }
+}
@@ -1,13 +1,16 @@
 use crate::channel::third_party_emote_list::EmoteList;
 use crate::errors::AppError;
-use entities::{emote, twitch_user};
+use chrono::Utc;
+use entities::{channel_emote_membership, emote, twitch_user};
 use entity_extensions::prelude::*;
 use sea_orm::DatabaseConnection;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
 
 #[derive(Debug)]
 pub struct EmoteListStorage {
-  third_party_emote_lists: HashMap<String, EmoteList>,
+  /// Lists are swapped out whole when they're refreshed, so a reader never sees a partially updated list.
+  third_party_emote_lists: RwLock<HashMap<String, Arc<EmoteList>>>,
 }
 
 impl EmoteListStorage {
@@ -18,6 +21,24 @@ impl EmoteListStorage {
   pub async fn new(
     channel_names: &[String],
     database_connection: &DatabaseConnection,
+  ) -> Result<Self, AppError> {
+    Self::build(channel_names, true, database_connection).await
+  }
+
+  /// Generates the lists the same way as [`new`](Self::new), without recording the channel's emote memberships.
+  ///
+  /// Used when replaying old messages, where the current lists say nothing about when the emotes were added.
+  pub async fn new_without_memberships(
+    channel_names: &[String],
+    database_connection: &DatabaseConnection,
+  ) -> Result<Self, AppError> {
+    Self::build(channel_names, false, database_connection).await
+  }
+
+  async fn build(
+    channel_names: &[String],
+    record_memberships: bool,
+    database_connection: &DatabaseConnection,
   ) -> Result<Self, AppError> {
     if cfg!(test) {
       panic!("Called new on EmoteListStorage in a test environment. Use EmoteListStorage::test_list instead.");
@@ -29,7 +50,7 @@ impl EmoteListStorage {
       Ok(global_emote_list) => {
         third_party_emote_lists.insert(
           global_emote_list.channel_name().to_string(),
-          global_emote_list,
+          Arc::new(global_emote_list),
         );
       }
       Err(error) => {
@@ -55,21 +76,25 @@ impl EmoteListStorage {
 
           third_party_emote_lists.insert(
             channel_login_name.clone(),
-            EmoteList::get_empty(channel_login_name.to_owned()),
+            Arc::new(EmoteList::get_empty(channel_login_name.to_owned())),
           );
 
           continue;
         }
       };
 
+      if record_memberships {
+        Self::record_emote_memberships(&channel, &channel_emote_list, database_connection).await;
+      }
+
       third_party_emote_lists.insert(
         channel_emote_list.channel_name().to_owned(),
-        channel_emote_list,
+        Arc::new(channel_emote_list),
       );
     }
 
     Ok(Self {
-      third_party_emote_lists,
+      third_party_emote_lists: RwLock::new(third_party_emote_lists),
     })
   }
 
@@ -86,31 +111,168 @@ impl EmoteListStorage {
     let third_party_emote_lists = test_emote_storage.into_iter().fold(
       HashMap::new(),
       |mut third_party_emote_lists, emote_list| {
-        third_party_emote_lists.insert(emote_list.channel_name().to_string(), emote_list);
+        third_party_emote_lists.insert(emote_list.channel_name().to_string(), Arc::new(emote_list));
 
         third_party_emote_lists
       },
     );
 
     Some(Self {
-      third_party_emote_lists,
+      third_party_emote_lists: RwLock::new(third_party_emote_lists),
     })
   }
 
+  /// Queries the emote list for the given channel name, replacing the stored one if successful.
+  ///
+  /// The [`GLOBAL`](EmoteList::GLOBAL_NAME) name will refresh the global emote list.
+  /// If any service failed to respond, its emotes are kept from the stored list.
+  pub async fn refresh_list(
+    &self,
+    channel_name: &str,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), AppError> {
+    let mut emote_list = if channel_name == EmoteList::GLOBAL_NAME {
+      EmoteList::get_global_emote_list(database_connection).await?
+    } else {
+      let channel =
+        twitch_user::Model::get_or_set_by_name(channel_name, database_connection).await?;
+      let emote_list = EmoteList::get_list(&channel, database_connection).await?;
+
+      Self::record_emote_memberships(&channel, &emote_list, database_connection).await;
+
+      emote_list
+    };
+
+    if !emote_list.is_complete() {
+      if let Some(previous_list) = self.get_list(channel_name) {
+        emote_list.keep_failed_services_from(&previous_list);
+      }
+    }
+
+    tracing::info!(
+      "Refreshed the emote list for {}. {} emotes.",
+      channel_name,
+      emote_list.emote_list().len()
+    );
+
+    self.replace_list(emote_list);
+
+    Ok(())
+  }
+
+  /// Updates the channel's emote memberships to match the emotes in the given list.
+  ///
+  /// Incomplete lists are skipped, as a service failing to respond would otherwise mark all of its emotes as removed.
+  async fn record_emote_memberships(
+    channel: &twitch_user::Model,
+    emote_list: &EmoteList,
+    database_connection: &DatabaseConnection,
+  ) {
+    if !emote_list.is_complete() {
+      tracing::warn!(
+        "Skipping emote membership update for {}. The emote list is incomplete.",
+        channel.login_name
+      );
+
+      return;
+    }
+
+    let current_emote_ids: HashSet<i32> = emote_list
+      .emote_list()
+      .values()
+      .map(|emote| emote.id)
+      .collect();
+
+    if let Err(error) = channel_emote_membership::Model::update_channel_memberships(
+      channel.id,
+      &current_emote_ids,
+      Utc::now(),
+      database_connection,
+    )
+    .await
+    {
+      tracing::error!(
+        "Failed to update emote memberships for {}. Reason: {:?}",
+        channel.login_name,
+        error
+      );
+    }
+  }
+
+  /// Refreshes every stored list, keeping the old list for any that failed.
+  pub async fn refresh_all_lists(&self, database_connection: &DatabaseConnection) {
+    for channel_name in self.channel_names() {
+      if let Err(error) = self.refresh_list(&channel_name, database_connection).await {
+        tracing::error!(
+          "Failed to refresh the emote list for {}. Reason: {:?}",
+          channel_name,
+          error
+        );
+      }
+    }
+  }
+
+  /// Replaces the stored list under the list's channel name.
+  pub fn replace_list(&self, emote_list: EmoteList) {
+    let mut third_party_emote_lists = self.third_party_emote_lists.write().unwrap();
+
+    third_party_emote_lists.insert(emote_list.channel_name().to_owned(), Arc::new(emote_list));
+  }
+
+  fn get_list(&self, channel_name: &str) -> Option<Arc<EmoteList>> {
+    self
+      .third_party_emote_lists
+      .read()
+      .unwrap()
+      .get(channel_name)
+      .cloned()
+  }
+
+  /// Returns the names of every list stored, including the [`global`](EmoteList::GLOBAL_NAME) list.
+  pub fn channel_names(&self) -> Vec<String> {
+    self
+      .third_party_emote_lists
+      .read()
+      .unwrap()
+      .keys()
+      .cloned()
+      .collect()
+  }
+
+  /// Key: 7TV emote set ID | Value: channel name
+  pub fn seventv_emote_set_ids(&self) -> HashMap<String, String> {
+    self
+      .third_party_emote_lists
+      .read()
+      .unwrap()
+      .values()
+      .filter_map(|emote_list| {
+        let emote_set_id = emote_list.seventv_emote_set_id()?;
+
+        Some((
+          emote_set_id.to_owned(),
+          emote_list.channel_name().to_owned(),
+        ))
+      })
+      .collect()
+  }
+
   pub fn get_channel_emote(
     &self,
     channel: &twitch_user::Model,
     emote_name: &str,
-  ) -> Option<&emote::Model> {
-    if let Some(channel_emote_list) = self.third_party_emote_lists.get(&channel.login_name) {
+  ) -> Option<emote::Model> {
+    let third_party_emote_lists = self.third_party_emote_lists.read().unwrap();
+
+    if let Some(channel_emote_list) = third_party_emote_lists.get(&channel.login_name) {
       if let Some(emote) = channel_emote_list.get(emote_name) {
-        return Some(emote);
+        return Some(emote.clone());
       }
     }
 
-    if let Some(global_emote_list) = self.third_party_emote_lists.get(EmoteList::GLOBAL_NAME) {
+    if let Some(global_emote_list) = third_party_emote_lists.get(EmoteList::GLOBAL_NAME) {
       if let Some(emote) = global_emote_list.get(emote_name) {
-        return Some(emote);
+        return Some(emote.clone());
       }
     }
 
@@ -120,6 +282,40 @@ impl EmoteListStorage {
   pub fn contains_channel(&self, channel: &twitch_user::Model) -> bool {
     self
       .third_party_emote_lists
+      .read()
+      .unwrap()
       .contains_key(&channel.login_name)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn channel_model(login_name: &str) -> twitch_user::Model {
+    twitch_user::Model {
+      id: 1,
+      twitch_id: 1,
+      login_name: login_name.to_string(),
+      display_name: login_name.to_string(),
+    }
+  }
+
+  #[test]
+  fn replace_list_only_affects_the_given_channel() {
+    let emote_list_storage = EmoteListStorage::test_list().unwrap();
+    let replaced_channel = channel_model("fallenshadow");
+    let untouched_channel = channel_model("shadowchama");
+
+    emote_list_storage.replace_list(EmoteList::get_empty("fallenshadow".to_string()));
+    emote_list_storage.replace_list(EmoteList::get_empty(EmoteList::GLOBAL_NAME.to_string()));
+
+    assert!(emote_list_storage
+      .get_channel_emote(&replaced_channel, "glorp")
+      .is_none());
+    assert!(emote_list_storage
+      .get_channel_emote(&untouched_channel, "glorp")
+      .is_some());
+    assert!(emote_list_storage.contains_channel(&replaced_channel));
+  }
+}
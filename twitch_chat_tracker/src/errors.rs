@@ -103,6 +103,9 @@ pub enum AppError {
   #[error("Attempted to query 7TV for a user's emote list, but got an error code back. {:?}", .0)]
   FailedToQuery7TVForEmoteList(String),
 
+  #[error("Attempted to query BTTV for an emote list, but got an error back. {:?}", .0)]
+  FailedToQueryBttvForEmoteList(String),
+
   #[error("Failed to deserialize a value. Reason: {:?}", .0)]
   DeserializeError(#[from] serde::de::value::Error),
 
@@ -123,11 +126,28 @@ pub enum AppError {
   },
 
   #[error(
-    "Expected `streamelements` when parsing a donation, got `{}` instead",
+    "Expected one of the channel's donation bots when parsing a donation, got `{}` instead",
     got_user
   )]
   IncorrectUserWhenParsingStreamlabsDonation { got_user: String },
 
+  #[error(
+    "Invalid donation bot config for channel `{}`. Reason: {}",
+    channel,
+    reason
+  )]
+  InvalidDonationBotConfig { channel: String, reason: String },
+
+  #[error(
+    "Invalid post stream report config for channel `{}`. Reason: {}",
+    channel,
+    reason
+  )]
+  InvalidPostStreamReportConfig { channel: String, reason: String },
+
+  #[error("Failed to generate reports. Reason: {0}")]
+  ReportGeneratorError(#[from] report_generator::errors::AppError),
+
   #[error(
     "Incorrect message format received at {}. Got command: {:?}",
     location,
@@ -160,6 +180,9 @@ pub enum AppError {
   #[error("The websocket connection has timedout.")]
   WebsocketTimeout,
 
+  #[error("7TV's EventAPI has closed the connection.")]
+  SevenTvEventApiClosed,
+
   #[error("Received an unknown value when parsing the event type for a websocket stream update message. Got: {:?}", value)]
   UnknownEventTypeValueInStreamUpdateMessage { value: String },
 
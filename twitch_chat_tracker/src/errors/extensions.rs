@@ -1,4 +1,5 @@
 use super::{AppError, DbErrExtension};
+use entity_extensions::errors::EntityExtensionError;
 
 impl AppError {
   pub fn is_unique_constraint_violation(&self) -> bool {
@@ -7,4 +8,15 @@ impl AppError {
       _ => false,
     }
   }
+
+  /// Returns true if the error was caused by the database being unreachable.
+  pub fn is_database_unavailable(&self) -> bool {
+    match self {
+      Self::SeaOrmDbError(db_error) => db_error.is_connection_error(),
+      Self::EntityExtensionError(EntityExtensionError::SeaOrmDbError(db_error)) => {
+        db_error.is_connection_error()
+      }
+      _ => false,
+    }
+  }
 }
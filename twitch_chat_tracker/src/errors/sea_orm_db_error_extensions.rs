@@ -2,6 +2,7 @@ use sea_orm::DbErr;
 
 pub trait DbErrExtension {
   fn is_unique_constraint_violation(&self) -> bool;
+  fn is_connection_error(&self) -> bool;
 }
 
 impl DbErrExtension for DbErr {
@@ -16,4 +17,20 @@ impl DbErrExtension for DbErr {
 
     false
   }
+
+  /// Returns true if the error came from not being able to reach the database,
+  /// rather than from the query itself.
+  fn is_connection_error(&self) -> bool {
+    match self {
+      DbErr::Conn(_) | DbErr::ConnectionAcquire(_) => true,
+      DbErr::Exec(sea_orm::RuntimeErr::SqlxError(sqlx_err))
+      | DbErr::Query(sea_orm::RuntimeErr::SqlxError(sqlx_err)) => matches!(
+        sqlx_err,
+        sea_orm::sqlx::Error::Io(_)
+          | sea_orm::sqlx::Error::PoolTimedOut
+          | sea_orm::sqlx::Error::PoolClosed
+      ),
+      _ => false,
+    }
+  }
 }
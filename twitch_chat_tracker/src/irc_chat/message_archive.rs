@@ -0,0 +1,303 @@
+use crate::errors::AppError;
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::{read::MultiGzDecoder, write::GzEncoder, Compression};
+use irc::proto::Message as IrcMessage;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::{fs::OpenOptions, io::AsyncWriteExt, sync::mpsc};
+
+const ARCHIVE_FILE_PREFIX: &str = "irc-";
+const ARCHIVE_FILE_EXTENSION: &str = ".log.gz";
+
+/// The amount of lines held in memory before they're compressed and written to the archive.
+const ARCHIVE_FLUSH_LINE_COUNT: usize = 500;
+/// How long lines can be held in memory before they're written, regardless of how many there are.
+const ARCHIVE_FLUSH_INTERVAL: Duration = Duration::new(30, 0);
+/// How many lines can be waiting for the writer before [`record`](MessageArchive::record) waits for room.
+const ARCHIVE_QUEUE_CAPACITY: usize = 10_000;
+
+/// A raw record of every line received from the IRC client, kept so messages can be reprocessed later.
+///
+/// Lines are stored as `<receive timestamp>\t<raw IRC line>` in gzip files, one per day (UTC).
+/// Lines are written in batches, each as its own gzip member, so a crash can only lose the batch that hadn't been written yet.
+pub struct MessageArchive {
+  line_sender: mpsc::Sender<ArchivedMessage>,
+}
+
+/// A single line from the archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedMessage {
+  pub received_at: DateTime<Utc>,
+  pub message: IrcMessage,
+}
+
+impl MessageArchive {
+  /// Spawns the writer for archive files under the given directory.
+  pub fn new(directory: PathBuf) -> Self {
+    let (line_sender, line_receiver) = mpsc::channel(ARCHIVE_QUEUE_CAPACITY);
+
+    tracing::info!("Archiving raw IRC messages to {:?}.", directory);
+
+    tokio::spawn(run_archive_writer(directory, line_receiver));
+
+    Self { line_sender }
+  }
+
+  /// Queues the message to be written to the archive for the day it was received.
+  pub async fn record(&self, message: &IrcMessage, received_at: DateTime<Utc>) {
+    let archived_message = ArchivedMessage {
+      received_at,
+      message: message.to_owned(),
+    };
+
+    if self.line_sender.send(archived_message).await.is_err() {
+      tracing::error!("The message archive writer has stopped. Message was not archived.");
+    }
+  }
+
+  /// The file messages received on the given date are written to.
+  pub fn file_path(directory: &Path, date: NaiveDate) -> PathBuf {
+    directory.join(format!(
+      "{ARCHIVE_FILE_PREFIX}{}{ARCHIVE_FILE_EXTENSION}",
+      date.format("%Y-%m-%d")
+    ))
+  }
+
+  /// Returns every archive file in the directory, oldest first.
+  pub fn archive_files(directory: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let mut archive_files: Vec<PathBuf> = std::fs::read_dir(directory)?
+      .filter_map(Result::ok)
+      .map(|entry| entry.path())
+      .filter(|path| {
+        path
+          .file_name()
+          .and_then(|file_name| file_name.to_str())
+          .is_some_and(|file_name| {
+            file_name.starts_with(ARCHIVE_FILE_PREFIX)
+              && file_name.ends_with(ARCHIVE_FILE_EXTENSION)
+          })
+      })
+      .collect();
+
+    // The date format sorts chronologically.
+    archive_files.sort();
+
+    Ok(archive_files)
+  }
+
+  /// Reads the messages out of an archive file one line at a time.
+  ///
+  /// Lines that can't be parsed are logged and skipped.
+  pub fn read_file(
+    path: &Path,
+  ) -> Result<impl Iterator<Item = Result<ArchivedMessage, AppError>>, AppError> {
+    let archive_file = std::fs::File::open(path)?;
+    let reader = BufReader::new(MultiGzDecoder::new(archive_file));
+
+    let archived_messages = reader.lines().filter_map(|line| {
+      let line = match line {
+        Ok(line) => line,
+        Err(error) => return Some(Err(error.into())),
+      };
+
+      if line.trim().is_empty() {
+        return None;
+      }
+
+      let archived_message = ArchivedMessage::from_line(&line);
+
+      if archived_message.is_none() {
+        tracing::warn!("Skipping an unreadable archive line: {:?}", line);
+      }
+
+      archived_message.map(Ok)
+    });
+
+    Ok(archived_messages)
+  }
+}
+
+impl ArchivedMessage {
+  fn to_line(&self) -> String {
+    let raw_message = self.message.to_string();
+
+    format!(
+      "{}\t{}\n",
+      self.received_at.to_rfc3339(),
+      raw_message.trim_end_matches(['\r', '\n'])
+    )
+  }
+
+  fn from_line(line: &str) -> Option<Self> {
+    let (received_at, raw_message) = line.split_once('\t')?;
+    let received_at = DateTime::parse_from_rfc3339(received_at)
+      .ok()?
+      .with_timezone(&Utc);
+    let message = format!("{raw_message}\r\n").parse().ok()?;
+
+    Some(Self {
+      received_at,
+      message,
+    })
+  }
+}
+
+async fn run_archive_writer(
+  directory: PathBuf,
+  mut line_receiver: mpsc::Receiver<ArchivedMessage>,
+) {
+  let mut pending_lines: Vec<ArchivedMessage> = Vec::with_capacity(ARCHIVE_FLUSH_LINE_COUNT);
+  let mut flush_timer = tokio::time::interval(ARCHIVE_FLUSH_INTERVAL);
+
+  loop {
+    tokio::select! {
+      archived_message = line_receiver.recv() => {
+        let Some(archived_message) = archived_message else {
+          break;
+        };
+        let received_on = archived_message.received_at.date_naive();
+        let crosses_day = pending_lines
+          .first()
+          .is_some_and(|first| first.received_at.date_naive() != received_on);
+
+        if crosses_day {
+          flush_pending_lines(&directory, &mut pending_lines).await;
+        }
+
+        pending_lines.push(archived_message);
+
+        if pending_lines.len() >= ARCHIVE_FLUSH_LINE_COUNT {
+          flush_pending_lines(&directory, &mut pending_lines).await;
+        }
+      }
+
+      _ = flush_timer.tick() => {
+        flush_pending_lines(&directory, &mut pending_lines).await;
+      }
+    }
+  }
+
+  flush_pending_lines(&directory, &mut pending_lines).await;
+
+  tracing::error!("The message archive writer has lost its queue. Exiting.");
+}
+
+/// Writes the pending lines to the archive file for their day.
+///
+/// Every pending line must be from the same day.
+async fn flush_pending_lines(directory: &Path, pending_lines: &mut Vec<ArchivedMessage>) {
+  let Some(first_line) = pending_lines.first() else {
+    return;
+  };
+  let archive_path = MessageArchive::file_path(directory, first_line.received_at.date_naive());
+
+  if let Err(error) = write_archive_member(&archive_path, pending_lines).await {
+    tracing::error!(
+      "Failed to write {} lines to the archive at {:?}. Reason: {}",
+      pending_lines.len(),
+      archive_path,
+      error
+    );
+  }
+
+  pending_lines.clear();
+}
+
+/// Compresses the lines into a single gzip member and appends it to the file.
+async fn write_archive_member(
+  archive_path: &Path,
+  lines: &[ArchivedMessage],
+) -> Result<(), AppError> {
+  let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+
+  for line in lines {
+    encoder.write_all(line.to_line().as_bytes())?;
+  }
+
+  let compressed_lines = encoder.finish()?;
+
+  if let Some(parent_directory) = archive_path.parent() {
+    if !parent_directory.as_os_str().is_empty() {
+      tokio::fs::create_dir_all(parent_directory).await?;
+    }
+  }
+
+  let mut archive_file = OpenOptions::new()
+    .create(true)
+    .append(true)
+    .open(archive_path)
+    .await?;
+
+  archive_file.write_all(&compressed_lines).await?;
+  archive_file.flush().await?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::TimeZone;
+
+  fn archive_test_directory(name: &str) -> PathBuf {
+    std::env::temp_dir()
+      .join(format!(
+        "twitch_chat_tracker_archive_test_{}",
+        std::process::id()
+      ))
+      .join(name)
+  }
+
+  fn archived_message(contents: &str, received_at: DateTime<Utc>) -> ArchivedMessage {
+    ArchivedMessage {
+      received_at,
+      message: format!(
+        "@room-id=578762718;user-id=128831052 :fallenshadow!fallenshadow@fallenshadow.tmi.twitch.tv PRIVMSG #fallenshadow :{contents}\r\n"
+      )
+      .parse()
+      .unwrap(),
+    }
+  }
+
+  #[tokio::test]
+  async fn batches_written_separately_are_read_back_in_order() {
+    let directory = archive_test_directory("batches");
+    let _ = tokio::fs::remove_dir_all(&directory).await;
+    let received_at = Utc.with_ymd_and_hms(2025, 7, 13, 19, 45, 33).unwrap();
+    let archive_path = MessageArchive::file_path(&directory, received_at.date_naive());
+
+    let first_batch = vec![
+      archived_message("glorp", received_at),
+      archived_message("waaa", received_at),
+    ];
+    let second_batch = vec![archived_message("glorpass", received_at)];
+
+    write_archive_member(&archive_path, &first_batch)
+      .await
+      .unwrap();
+    write_archive_member(&archive_path, &second_batch)
+      .await
+      .unwrap();
+
+    let archived_messages: Vec<ArchivedMessage> = MessageArchive::read_file(&archive_path)
+      .unwrap()
+      .collect::<Result<_, _>>()
+      .unwrap();
+    let archive_files = MessageArchive::archive_files(&directory).unwrap();
+
+    let _ = tokio::fs::remove_dir_all(&directory).await;
+
+    assert_eq!(archived_messages, [first_batch, second_batch].concat());
+    assert_eq!(archive_files, vec![archive_path]);
+  }
+
+  #[test]
+  fn archive_files_are_named_by_day() {
+    let date = NaiveDate::from_ymd_opt(2025, 7, 13).unwrap();
+
+    let archive_path = MessageArchive::file_path(Path::new("archive"), date);
+
+    assert_eq!(archive_path, PathBuf::from("archive/irc-2025-07-13.log.gz"));
+  }
+}
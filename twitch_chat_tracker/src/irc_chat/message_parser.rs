@@ -2,15 +2,22 @@ use super::mirrored_twitch_objects::message::TwitchIrcMessage;
 use super::mirrored_twitch_objects::twitch_message_type::TwitchMessageType;
 use crate::channel::third_party_emote_list_storage::EmoteListStorage;
 use crate::errors::AppError;
+use entities::bits_cheermote;
 use irc::proto::Message as IrcMessage;
 use sea_orm::*;
 
 mod bits_message_parsing;
+mod bits_power_up_parsing;
+mod channel_notice_parsing;
+mod channel_point_redemption_parsing;
+pub mod donation_bots;
 mod gift_sub_message_parsing;
+mod hype_chat_parsing;
+mod hype_train_parsing;
 mod live_status_message_parsing;
+mod message_deletion_parsing;
 mod raid_message_parsing;
 mod stream_message_parsing;
-pub mod streamlabs_donation;
 mod streamlabs_donation_message_parsing;
 mod subscription_message_parsing;
 mod timeout_message_parsing;
@@ -37,13 +44,44 @@ impl<'a> MessageParser<'a> {
 
   pub async fn parse(self, database_connection: &DatabaseConnection) -> Result<(), AppError> {
     if self.message.message_type_has_user_message_attached() {
-      self.parse_user_message(database_connection).await?;
+      match self.parse_user_message(database_connection).await {
+        Ok(()) => (),
+        // A replayed event can have its chat message stored without the event itself.
+        Err(error)
+          if error.is_unique_constraint_violation()
+            && self.message.message_type() != TwitchMessageType::UserMessage => {}
+        Err(error) => return Err(error),
+      }
     }
 
     match self.message.message_type() {
       TwitchMessageType::Bits => {
+        let donation_event = self.parse_bits(database_connection).await?;
+        let transaction = database_connection.begin().await?;
+        let donation_event = donation_event.insert(&transaction).await?;
+        let cheermotes = self.parse_bits_cheermotes(donation_event.id);
+
+        if !cheermotes.is_empty() {
+          bits_cheermote::Entity::insert_many(cheermotes)
+            .exec(&transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+      }
+      TwitchMessageType::BitsPowerUp => {
+        self.parse_bits_power_up(database_connection).await?;
+      }
+      TwitchMessageType::HypeChat => {
         self
-          .parse_bits(database_connection)
+          .parse_hype_chat(database_connection)
+          .await?
+          .insert(database_connection)
+          .await?;
+      }
+      TwitchMessageType::ChannelPointRedemption => {
+        self
+          .parse_channel_point_redemption(database_connection)
           .await?
           .insert(database_connection)
           .await?;
@@ -65,6 +103,12 @@ impl<'a> MessageParser<'a> {
           .insert(database_connection)
           .await?;
       }
+      TwitchMessageType::MessageDeletion => {
+        self.parse_message_deletion(database_connection).await?;
+      }
+      TwitchMessageType::ChatClear => {
+        self.parse_chat_clear(database_connection).await?;
+      }
       TwitchMessageType::StreamlabsDonation => {
         self
           .parse_streamlabs_donation(database_connection)
@@ -79,6 +123,13 @@ impl<'a> MessageParser<'a> {
           .insert(database_connection)
           .await?;
       }
+      TwitchMessageType::ChannelNotice => {
+        self
+          .parse_channel_notice(database_connection)
+          .await?
+          .insert(database_connection)
+          .await?;
+      }
       _ => (),
     };
 
@@ -96,6 +147,7 @@ mod tests {
   use irc::proto::message::Tag as IrcTag;
   use irc::proto::Message as IrcMessage;
   use irc::proto::{Command, Prefix};
+  use sea_orm::prelude::Decimal;
 
   #[tokio::test]
   async fn bits_donation_parses_message_too() {
@@ -109,6 +161,19 @@ mod tests {
       .parse(&bit_donation_mock_database)
       .await
       .unwrap();
+
+    let transaction_log = bit_donation_mock_database.into_transaction_log();
+    let donation_statements: Vec<&str> = transaction_log
+      .last()
+      .unwrap()
+      .statements()
+      .iter()
+      .map(|statement| statement.sql.as_str())
+      .collect();
+
+    assert_eq!(donation_statements.first(), Some(&"BEGIN"));
+    assert_eq!(donation_statements.last(), Some(&"COMMIT"));
+    assert_eq!(donation_statements.len(), 5);
   }
 
   fn bits_donation_parses_message_too_template() -> (IrcMessage, DatabaseConnection) {
@@ -161,6 +226,7 @@ mod tests {
         stream_id: None,
         is_subscriber: 1_i8,
         origin_id: None,
+        deleted_at: None,
       }]])
       .append_exec_results([MockExecResult {
         last_insert_id: 1,
@@ -184,7 +250,7 @@ mod tests {
       .append_query_results([vec![donation_event::Model {
         id: 1,
         event_type: EventType::Bits,
-        amount: 100000.0_f32,
+        amount: Decimal::from(100000),
         timestamp: timestamp_from_string("1740956922774"),
         donator_twitch_user_id: Some(3),
         donation_receiver_twitch_user_id: 1,
@@ -193,11 +259,20 @@ mod tests {
         unknown_user_id: None,
         origin_id: None,
         source_id: None,
+        hype_train_id: None,
+        currency: None,
+        original_amount: None,
       }]])
-      .append_exec_results([MockExecResult {
-        last_insert_id: 1,
-        rows_affected: 1,
-      }])
+      .append_exec_results([
+        MockExecResult {
+          last_insert_id: 1,
+          rows_affected: 1,
+        },
+        MockExecResult {
+          last_insert_id: 1,
+          rows_affected: 1,
+        },
+      ])
       .into_connection();
 
     (message, mock_database)
@@ -4,6 +4,8 @@ use crate::irc_chat::mirrored_twitch_objects::twitch_message_type::TwitchMessage
 use entities::sea_orm_active_enums::EventType;
 use entities::*;
 use entity_extensions::prelude::*;
+use irc::client::prelude::*;
+use sea_orm::prelude::Decimal;
 use sea_orm::*;
 
 impl MessageParser<'_> {
@@ -42,7 +44,7 @@ impl MessageParser<'_> {
         location: "bit donation parsing",
       });
     };
-    let Ok(bit_quantity) = bit_quantity.trim().parse::<f32>() else {
+    let Ok(bit_quantity) = bit_quantity.trim().parse::<Decimal>() else {
       return Err(AppError::FailedToParseValue {
         value_name: "bit_quantity",
         location: "bit donation parsing",
@@ -57,12 +59,71 @@ impl MessageParser<'_> {
       donator_twitch_user_id: Set(Some(donator.id)),
       donation_receiver_twitch_user_id: Set(streamer_model.id),
       stream_id: Set(maybe_stream.map(|stream| stream.id)),
+      origin_id: Set(self.message.origin_id().map(str::to_owned)),
       source_id: Set(self.message.message_source_id().map(str::to_owned)),
       ..Default::default()
     };
 
     Ok(donation_event)
   }
+
+  /// Parses each cheermote used in the message, such as `Cheer100` or `Corgo50`.
+  ///
+  /// Nothing is returned if the cheermotes don't add up to the bits donated,
+  /// as that means words were mistaken for cheermotes or some were missed.
+  pub fn parse_bits_cheermotes(&self, donation_event_id: i32) -> Vec<bits_cheermote::ActiveModel> {
+    let Command::PRIVMSG(_, message_contents) = self.message.command() else {
+      return vec![];
+    };
+    let Some(bit_quantity) = self
+      .message
+      .bits()
+      .and_then(|bit_quantity| bit_quantity.trim().parse::<i64>().ok())
+    else {
+      return vec![];
+    };
+    let cheermotes = parse_cheermotes(message_contents);
+    let cheermote_total: i64 = cheermotes.iter().map(|(_, amount)| *amount as i64).sum();
+
+    if cheermote_total != bit_quantity {
+      tracing::warn!(
+        "Cheermotes in message {:?} add up to {cheermote_total} instead of {bit_quantity} bits.",
+        message_contents
+      );
+
+      return vec![];
+    }
+
+    cheermotes
+      .into_iter()
+      .map(|(prefix, amount)| bits_cheermote::ActiveModel {
+        donation_event_id: Set(donation_event_id),
+        prefix: Set(prefix),
+        amount: Set(amount),
+        ..Default::default()
+      })
+      .collect()
+  }
+}
+
+/// Returns the (prefix, amount) of every word that looks like a cheermote.
+///
+/// Prefixes are lowercased, as Twitch matches them regardless of case.
+fn parse_cheermotes(message_contents: &str) -> Vec<(String, i32)> {
+  message_contents
+    .split_whitespace()
+    .filter_map(|word| {
+      let prefix = word.trim_end_matches(|character: char| character.is_ascii_digit());
+      let amount = word[prefix.len()..].parse::<i32>().ok()?;
+
+      let is_cheermote = amount > 0
+        && !prefix.is_empty()
+        && prefix.chars().all(|character| character.is_alphanumeric())
+        && !prefix.chars().all(|character| character.is_ascii_digit());
+
+      is_cheermote.then(|| (prefix.to_lowercase(), amount))
+    })
+    .collect()
 }
 
 #[cfg(test)]
@@ -90,20 +151,52 @@ mod tests {
     let expected_active_model = donation_event::ActiveModel {
       id: ActiveValue::NotSet,
       event_type: Set(EventType::Bits),
-      amount: Set(100000.0_f32),
+      amount: Set(Decimal::from(100000)),
       timestamp: Set(timestamp_from_string("1740956922774")),
       donator_twitch_user_id: Set(Some(3)),
       donation_receiver_twitch_user_id: Set(1),
       stream_id: Set(None),
       subscription_tier: ActiveValue::NotSet,
       unknown_user_id: ActiveValue::NotSet,
-      origin_id: ActiveValue::NotSet,
+      origin_id: Set(None),
       source_id: Set(None),
+      hype_train_id: ActiveValue::NotSet,
+      currency: ActiveValue::NotSet,
+      original_amount: ActiveValue::NotSet,
     };
 
     assert_eq!(result, expected_active_model);
   }
 
+  #[test]
+  fn cheermotes_are_parsed_from_the_message() {
+    let (bits_message, _) = get_bits_template();
+    let third_party_emote_storage = EmoteListStorage::test_list().unwrap();
+    let message_parser = MessageParser::new(&bits_message, &third_party_emote_storage)
+      .unwrap()
+      .unwrap();
+
+    let cheermotes = message_parser.parse_bits_cheermotes(5);
+
+    assert_eq!(
+      cheermotes,
+      vec![bits_cheermote::ActiveModel {
+        id: ActiveValue::NotSet,
+        donation_event_id: Set(5),
+        prefix: Set("cheer".into()),
+        amount: Set(100000),
+      }]
+    );
+    assert_eq!(
+      parse_cheermotes("Cheer100 hi Corgo50 4Head10 at 10 2x"),
+      vec![
+        ("cheer".into(), 100),
+        ("corgo".into(), 50),
+        ("4head".into(), 10)
+      ]
+    );
+  }
+
   fn get_bits_template() -> (IrcMessage, DatabaseConnection) {
     let tags = vec![
       IrcTag("room-id".into(), Some("578762718".into())),
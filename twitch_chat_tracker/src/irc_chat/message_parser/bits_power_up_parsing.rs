@@ -0,0 +1,362 @@
+use super::MessageParser;
+use crate::errors::AppError;
+use crate::irc_chat::mirrored_twitch_objects::tag_values::TwitchIrcTagValues;
+use crate::irc_chat::mirrored_twitch_objects::twitch_message_type::TwitchMessageType;
+use app_config::AppConfig;
+use entities::sea_orm_active_enums::{BitsPowerUpType, EventType, ExternalService};
+use entities::*;
+use entity_extensions::prelude::*;
+use irc::client::prelude::*;
+use sea_orm::prelude::Decimal;
+use sea_orm::*;
+
+impl MessageParser<'_> {
+  /// Stores the bits spent on a power-up as a bits donation, along with which power-up was used.
+  ///
+  /// Both are inserted in one transaction so a donation is never left without its power-up.
+  pub async fn parse_bits_power_up(
+    &self,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), AppError> {
+    let donation_event = self
+      .parse_bits_power_up_donation(database_connection)
+      .await?;
+    let mut bits_power_up = self
+      .parse_bits_power_up_details(database_connection)
+      .await?;
+    let transaction = database_connection.begin().await?;
+
+    let donation_event = donation_event.insert(&transaction).await?;
+    bits_power_up.donation_event_id = Set(donation_event.id);
+    bits_power_up.insert(&transaction).await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+  }
+
+  /// Power-up messages don't come with the bits spent, so the configured cost of the power-up is used.
+  pub async fn parse_bits_power_up_donation(
+    &self,
+    database_connection: &DatabaseConnection,
+  ) -> Result<donation_event::ActiveModel, AppError> {
+    let power_up_type = self.bits_power_up_type()?;
+
+    let Some(streamer_twitch_id) = self.message.room_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "room id",
+        location: "bits power-up parsing",
+      });
+    };
+    let streamer_model =
+      twitch_user::Model::get_or_set_by_twitch_id(streamer_twitch_id, database_connection).await?;
+    let maybe_stream =
+      stream::Model::get_active_stream_for_user(&streamer_model, database_connection).await?;
+    let Some(donator_id) = self.message.user_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "user id",
+        location: "bits power-up parsing",
+      });
+    };
+    let donator =
+      twitch_user::Model::get_or_set_by_twitch_id(donator_id, database_connection).await?;
+    let bit_cost = match power_up_type {
+      BitsPowerUpType::GigantifiedEmote => AppConfig::gigantified_emote_bit_cost(),
+      BitsPowerUpType::MessageEffect => AppConfig::message_effect_bit_cost(),
+    };
+
+    let donation_event = donation_event::ActiveModel {
+      event_type: Set(EventType::Bits),
+      amount: Set(Decimal::from(bit_cost)),
+      timestamp: Set(*self.message.timestamp()),
+      donator_twitch_user_id: Set(Some(donator.id)),
+      donation_receiver_twitch_user_id: Set(streamer_model.id),
+      stream_id: Set(maybe_stream.map(|stream| stream.id)),
+      origin_id: Set(self.message.origin_id().map(str::to_owned)),
+      source_id: Set(self.message.message_source_id().map(str::to_owned)),
+      ..Default::default()
+    };
+
+    Ok(donation_event)
+  }
+
+  /// The donation event ID is left for the caller to set once the donation has been inserted.
+  async fn parse_bits_power_up_details(
+    &self,
+    database_connection: &DatabaseConnection,
+  ) -> Result<bits_power_up::ActiveModel, AppError> {
+    let power_up_type = self.bits_power_up_type()?;
+    let emote = match power_up_type {
+      BitsPowerUpType::GigantifiedEmote => {
+        let Command::PRIVMSG(_, message_contents) = self.message.command() else {
+          return Err(AppError::IncorrectCommandWhenParsingMessage {
+            location: "bits power-up parsing",
+            command_string: format!("{:?}", self.message.command()),
+          });
+        };
+        let emotes = self.message.emotes().unwrap_or("");
+
+        match gigantified_emote(message_contents, emotes) {
+          Some(emote_active_model) => Some(
+            emote::Model::get_or_set_active_model(emote_active_model, database_connection).await?,
+          ),
+          None => {
+            tracing::error!(
+              "Failed to find the gigantified emote in message {:?}. Emotes: {:?}",
+              message_contents,
+              emotes
+            );
+
+            None
+          }
+        }
+      }
+      BitsPowerUpType::MessageEffect => None,
+    };
+
+    Ok(bits_power_up::ActiveModel {
+      power_up_type: Set(power_up_type),
+      emote_id: Set(emote.map(|emote| emote.id)),
+      animation_id: Set(self.message.animation_id().map(str::to_owned)),
+      ..Default::default()
+    })
+  }
+
+  fn bits_power_up_type(&self) -> Result<BitsPowerUpType, AppError> {
+    if self.message.message_type() != TwitchMessageType::BitsPowerUp {
+      return Err(AppError::IncorrectMessageType {
+        expected_type: TwitchMessageType::BitsPowerUp,
+        got_type: self.message.message_type(),
+      });
+    }
+
+    match self.message.message_id() {
+      Some(TwitchIrcTagValues::GIGANTIFIED_EMOTE_MSG_ID) => Ok(BitsPowerUpType::GigantifiedEmote),
+      Some(TwitchIrcTagValues::MESSAGE_EFFECT_MSG_ID) => Ok(BitsPowerUpType::MessageEffect),
+      _ => Err(AppError::MissingExpectedValue {
+        expected_value_name: "power-up message id",
+        location: "bits power-up parsing",
+      }),
+    }
+  }
+}
+
+/// Twitch gigantifies the last emote in the message.
+///
+/// The emote list is formatted as: `emote_id:0-1,2-3/` as per Twitch's emote storage.
+fn gigantified_emote(message_contents: &str, emotes: &str) -> Option<emote::ActiveModel> {
+  let (emote_id, emote_name_start, emote_name_end) = emotes
+    .split('/')
+    .filter_map(|emote_usage| emote_usage.split_once(':'))
+    .flat_map(|(emote_id, emote_positions)| {
+      emote_positions
+        .split(',')
+        .filter_map(move |emote_position| {
+          let (start, end) = emote_position.split_once('-')?;
+
+          Some((
+            emote_id,
+            start.parse::<usize>().ok()?,
+            end.parse::<usize>().ok()?,
+          ))
+        })
+    })
+    .max_by_key(|(_, start, _)| *start)?;
+
+  if emote_name_end < emote_name_start || message_contents.chars().count() <= emote_name_end {
+    return None;
+  }
+
+  let emote_name: String = message_contents
+    .chars()
+    .skip(emote_name_start)
+    .take(emote_name_end - emote_name_start + 1)
+    .collect();
+
+  Some(emote::ActiveModel {
+    external_id: Set(emote_id.to_owned()),
+    name: Set(emote_name),
+    external_service: Set(ExternalService::Twitch),
+    ..Default::default()
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::channel::third_party_emote_list_storage::EmoteListStorage;
+  use crate::testing_helper_methods::timestamp_from_string;
+  use irc::proto::message::Tag as IrcTag;
+  use irc::proto::Message as IrcMessage;
+  use irc::proto::{Command, Prefix};
+
+  #[tokio::test]
+  async fn parse_bits_power_up_donation_expected_value() {
+    let (power_up_message, power_up_mock_database) = get_gigantified_emote_template();
+    let third_party_emote_storage = EmoteListStorage::test_list().unwrap();
+    let message_parser = MessageParser::new(&power_up_message, &third_party_emote_storage)
+      .unwrap()
+      .unwrap();
+
+    let result = message_parser
+      .parse_bits_power_up_donation(&power_up_mock_database)
+      .await
+      .unwrap();
+
+    let expected_active_model = donation_event::ActiveModel {
+      id: ActiveValue::NotSet,
+      event_type: Set(EventType::Bits),
+      amount: Set(Decimal::from(AppConfig::gigantified_emote_bit_cost())),
+      timestamp: Set(timestamp_from_string("1740956922774")),
+      donator_twitch_user_id: Set(Some(3)),
+      donation_receiver_twitch_user_id: Set(1),
+      stream_id: Set(None),
+      subscription_tier: ActiveValue::NotSet,
+      unknown_user_id: ActiveValue::NotSet,
+      origin_id: Set(Some("0c4a3a5e-5b1e-4f6b-9d2a-3e8f7c6b5a49".into())),
+      source_id: Set(None),
+      hype_train_id: ActiveValue::NotSet,
+      currency: ActiveValue::NotSet,
+      original_amount: ActiveValue::NotSet,
+    };
+
+    assert_eq!(result, expected_active_model);
+  }
+
+  #[tokio::test]
+  async fn power_ups_are_inserted_with_their_donation_in_one_transaction() {
+    let (power_up_message, _) = get_gigantified_emote_template();
+    let third_party_emote_storage = EmoteListStorage::test_list().unwrap();
+    let message_parser = MessageParser::new(&power_up_message, &third_party_emote_storage)
+      .unwrap()
+      .unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([
+        vec![twitch_user::Model {
+          id: 1,
+          twitch_id: 578762718,
+          login_name: "fallenshadow".into(),
+          display_name: "fallenshadow".into(),
+        }],
+        vec![],
+        vec![twitch_user::Model {
+          id: 3,
+          twitch_id: 128831052,
+          login_name: "linkthedot".into(),
+          display_name: "LinkTheDot".into(),
+        }],
+      ])
+      .append_query_results([vec![emote::Model {
+        id: 2,
+        external_id: "25".into(),
+        name: "Kappa".into(),
+        external_service: ExternalService::Twitch,
+      }]])
+      .append_exec_results([MockExecResult {
+        last_insert_id: 1,
+        rows_affected: 1,
+      }])
+      .append_query_results([vec![donation_event::Model {
+        id: 1,
+        event_type: EventType::Bits,
+        amount: Decimal::from(AppConfig::gigantified_emote_bit_cost()),
+        timestamp: timestamp_from_string("1740956922774"),
+        donator_twitch_user_id: Some(3),
+        donation_receiver_twitch_user_id: 1,
+        stream_id: None,
+        subscription_tier: None,
+        unknown_user_id: None,
+        origin_id: Some("0c4a3a5e-5b1e-4f6b-9d2a-3e8f7c6b5a49".into()),
+        source_id: None,
+        hype_train_id: None,
+        currency: None,
+        original_amount: None,
+      }]])
+      .append_exec_results([MockExecResult {
+        last_insert_id: 1,
+        rows_affected: 1,
+      }])
+      .append_query_results([vec![bits_power_up::Model {
+        id: 1,
+        donation_event_id: 1,
+        power_up_type: BitsPowerUpType::GigantifiedEmote,
+        emote_id: Some(2),
+        animation_id: None,
+      }]])
+      .into_connection();
+
+    message_parser
+      .parse_bits_power_up(&mock_database)
+      .await
+      .unwrap();
+
+    let transaction_log = mock_database.into_transaction_log();
+
+    let insert_statements: Vec<&str> = transaction_log[4]
+      .statements()
+      .iter()
+      .map(|statement| statement.sql.as_str())
+      .collect();
+
+    assert_eq!(transaction_log.len(), 5);
+    assert_eq!(insert_statements.first(), Some(&"BEGIN"));
+    assert_eq!(insert_statements.last(), Some(&"COMMIT"));
+    assert_eq!(insert_statements.len(), 6);
+  }
+
+  #[test]
+  fn gigantified_emote_is_the_last_emote() {
+    let emote = gigantified_emote("Kappa hello LUL Kappa", "25:0-4,16-20/425618:12-14").unwrap();
+
+    assert_eq!(emote.external_id, Set("25".to_string()));
+    assert_eq!(emote.name, Set("Kappa".to_string()));
+    assert!(gigantified_emote("hello", "").is_none());
+    assert!(gigantified_emote("LUL", "425618:0-9").is_none());
+  }
+
+  fn get_gigantified_emote_template() -> (IrcMessage, DatabaseConnection) {
+    let tags = vec![
+      IrcTag("room-id".into(), Some("578762718".into())),
+      IrcTag("user-id".into(), Some("128831052".into())),
+      IrcTag(
+        "id".into(),
+        Some("0c4a3a5e-5b1e-4f6b-9d2a-3e8f7c6b5a49".into()),
+      ),
+      IrcTag("msg-id".into(), Some("gigantified-emote-message".into())),
+      IrcTag("emotes".into(), Some("25:6-10".into())),
+      IrcTag("tmi-sent-ts".into(), Some("1740956922774".into())),
+      IrcTag("display-name".into(), Some("LinkTheDot".into())),
+      IrcTag("login".into(), Some("linkthedot".into())),
+    ];
+
+    let message = IrcMessage {
+      tags: Some(tags),
+      prefix: Some(Prefix::Nickname(
+        "linkthedot".into(),
+        "linkthedot".into(),
+        "linkthedot.tmi.twitch.tv".into(),
+      )),
+      command: Command::PRIVMSG("#fallenshadow".into(), "hello Kappa".into()),
+    };
+
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([
+        vec![twitch_user::Model {
+          id: 1,
+          twitch_id: 578762718,
+          login_name: "fallenshadow".into(),
+          display_name: "fallenshadow".into(),
+        }],
+        vec![],
+        vec![twitch_user::Model {
+          id: 3,
+          twitch_id: 128831052,
+          login_name: "linkthedot".into(),
+          display_name: "LinkTheDot".into(),
+        }],
+      ])
+      .into_connection();
+
+    (message, mock_database)
+  }
+}
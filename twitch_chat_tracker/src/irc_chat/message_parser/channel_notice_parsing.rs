@@ -0,0 +1,121 @@
+use super::MessageParser;
+use crate::errors::AppError;
+use crate::irc_chat::mirrored_twitch_objects::twitch_message_type::TwitchMessageType;
+use entities::*;
+use entity_extensions::prelude::*;
+use irc::client::prelude::*;
+use sea_orm::*;
+
+impl MessageParser<'_> {
+  /// Stores announcements, viewer milestones and bits badge tiers.
+  ///
+  /// Every `msg-param-` tag is kept in the parameters, as each notice type sends a different set.
+  pub async fn parse_channel_notice(
+    &self,
+    database_connection: &DatabaseConnection,
+  ) -> Result<channel_notice::ActiveModel, AppError> {
+    if self.message.message_type() != TwitchMessageType::ChannelNotice {
+      return Err(AppError::IncorrectMessageType {
+        expected_type: TwitchMessageType::ChannelNotice,
+        got_type: self.message.message_type(),
+      });
+    }
+
+    let Some(notice_type) = self.message.message_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "message id",
+        location: "channel notice parsing",
+      });
+    };
+    let Some(streamer_twitch_id) = self.message.room_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "room id",
+        location: "channel notice parsing",
+      });
+    };
+    let Some(user_twitch_id) = self.message.user_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "user id",
+        location: "channel notice parsing",
+      });
+    };
+    let streamer =
+      twitch_user::Model::get_or_set_by_twitch_id(streamer_twitch_id, database_connection).await?;
+    let maybe_stream =
+      stream::Model::get_active_stream_for_user(&streamer, database_connection).await?;
+    let user =
+      twitch_user::Model::get_or_set_by_twitch_id(user_twitch_id, database_connection).await?;
+    let parameters = serde_json::to_value(self.message.message_parameters())?;
+    // USERNOTICE #channel :contents
+    let contents = match self.message.command() {
+      Command::Raw(_, arguments) => arguments.get(1).cloned(),
+      _ => None,
+    };
+
+    Ok(channel_notice::ActiveModel {
+      timestamp: Set(*self.message.timestamp()),
+      channel_id: Set(streamer.id),
+      twitch_user_id: Set(user.id),
+      stream_id: Set(maybe_stream.map(|stream| stream.id)),
+      notice_type: Set(notice_type.to_owned()),
+      parameters: Set(Some(parameters)),
+      contents: Set(contents),
+      origin_id: Set(self.message.origin_id().map(str::to_owned)),
+      ..Default::default()
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::channel::third_party_emote_list_storage::EmoteListStorage;
+  use crate::testing_helper_methods::timestamp_from_string;
+  use irc::proto::Message as IrcMessage;
+
+  #[tokio::test]
+  async fn announcements_keep_their_parameters_and_contents() {
+    let message: IrcMessage =
+      "@id=159ba37c-c6aa-4fdd-bc62-c5fadbab0770;msg-id=announcement;msg-param-color=PRIMARY;room-id=578762718;user-id=128831052;login=linkthedot;display-name=LinkTheDot;tmi-sent-ts=1740956922774 :tmi.twitch.tv USERNOTICE #fallenshadow :glorp\r\n"
+        .parse()
+        .unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([vec![twitch_user::Model {
+        id: 1,
+        twitch_id: 578762718,
+        login_name: "fallenshadow".into(),
+        display_name: "fallenshadow".into(),
+      }]])
+      .append_query_results([Vec::<stream::Model>::new()])
+      .append_query_results([vec![twitch_user::Model {
+        id: 3,
+        twitch_id: 128831052,
+        login_name: "linkthedot".into(),
+        display_name: "LinkTheDot".into(),
+      }]])
+      .into_connection();
+    let third_party_emote_storage = EmoteListStorage::test_list().unwrap();
+    let message_parser = MessageParser::new(&message, &third_party_emote_storage)
+      .unwrap()
+      .unwrap();
+
+    let result = message_parser
+      .parse_channel_notice(&mock_database)
+      .await
+      .unwrap();
+
+    let expected_active_model = channel_notice::ActiveModel {
+      id: ActiveValue::NotSet,
+      timestamp: Set(timestamp_from_string("1740956922774")),
+      channel_id: Set(1),
+      twitch_user_id: Set(3),
+      stream_id: Set(None),
+      notice_type: Set("announcement".into()),
+      parameters: Set(Some(serde_json::json!({ "color": "PRIMARY" }))),
+      contents: Set(Some("glorp".into())),
+      origin_id: Set(Some("159ba37c-c6aa-4fdd-bc62-c5fadbab0770".into())),
+    };
+
+    assert_eq!(result, expected_active_model);
+  }
+}
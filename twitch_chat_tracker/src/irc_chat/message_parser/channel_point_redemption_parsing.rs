@@ -0,0 +1,219 @@
+use super::MessageParser;
+use crate::errors::AppError;
+use crate::irc_chat::mirrored_twitch_objects::twitch_message_type::TwitchMessageType;
+use crate::websocket_connection::twitch_objects::channel_point_redemption::TwitchChannelPointRedemptionMessage;
+use entities::*;
+use entity_extensions::prelude::*;
+use irc::client::prelude::*;
+use sea_orm::*;
+
+/// The title of Twitch's built in reward for highlighted messages.
+const HIGHLIGHTED_MESSAGE_REWARD_TITLE: &str = "Highlight My Message";
+
+impl MessageParser<'_> {
+  /// Reward titles and costs aren't sent through IRC.
+  /// For custom rewards, they're taken from the last redemption of the same reward that had them filled in by EventSub.
+  pub async fn parse_channel_point_redemption(
+    &self,
+    database_connection: &DatabaseConnection,
+  ) -> Result<channel_point_redemption::ActiveModel, AppError> {
+    if self.message.message_type() != TwitchMessageType::ChannelPointRedemption {
+      return Err(AppError::IncorrectMessageType {
+        expected_type: TwitchMessageType::ChannelPointRedemption,
+        got_type: self.message.message_type(),
+      });
+    }
+
+    let Some(streamer_twitch_id) = self.message.room_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "room id",
+        location: "channel point redemption parsing",
+      });
+    };
+    let streamer =
+      twitch_user::Model::get_or_set_by_twitch_id(streamer_twitch_id, database_connection).await?;
+    let maybe_stream =
+      stream::Model::get_active_stream_for_user(&streamer, database_connection).await?;
+    let Some(redeemer_twitch_id) = self.message.user_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "user id",
+        location: "channel point redemption parsing",
+      });
+    };
+    let redeemer =
+      twitch_user::Model::get_or_set_by_twitch_id(redeemer_twitch_id, database_connection).await?;
+    let contents = match self.message.command() {
+      Command::PRIVMSG(_, contents) => Some(contents.to_owned()),
+      _ => None,
+    };
+    let is_highlighted_message = self.message.is_highlighted_message();
+    let reward_id = self.message.custom_reward_id();
+    let (reward_title, reward_cost) = match reward_id {
+      _ if is_highlighted_message => (Some(HIGHLIGHTED_MESSAGE_REWARD_TITLE.to_string()), None),
+      Some(reward_id) => channel_point_redemption::Model::get_latest_titled_redemption_for_reward(
+        streamer.id,
+        reward_id,
+        database_connection,
+      )
+      .await?
+      .map(|redemption| (redemption.reward_title, redemption.reward_cost))
+      .unwrap_or_default(),
+      None => (None, None),
+    };
+
+    Ok(channel_point_redemption::ActiveModel {
+      timestamp: Set(*self.message.timestamp()),
+      channel_id: Set(streamer.id),
+      twitch_user_id: Set(redeemer.id),
+      stream_id: Set(maybe_stream.map(|stream| stream.id)),
+      reward_id: Set(reward_id.map(str::to_owned)),
+      reward_title: Set(reward_title),
+      reward_cost: Set(reward_cost),
+      is_highlighted_message: Set(is_highlighted_message as i8),
+      contents: Set(contents),
+      ..Default::default()
+    })
+  }
+
+  /// Takes a [`JsonValue`](serde_json::Value) constructed from Twitch's Websocket connection for
+  /// `channel.channel_points_custom_reward_redemption.add` events, and fills in the reward's title
+  /// and cost on any stored redemptions missing them.
+  pub async fn parse_websocket_channel_point_redemption_message(
+    message: JsonValue,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), AppError> {
+    let Ok(redemption_message) =
+      serde_json::from_value::<TwitchChannelPointRedemptionMessage>(message.clone())
+    else {
+      return Err(AppError::FailedToParseValue {
+        value_name: "channel point redemption",
+        location: "parse websocket channel point redemption message",
+        value: format!("{:?}", message),
+      });
+    };
+    let streamer = twitch_user::Model::get_or_set_by_twitch_id(
+      redemption_message.get_streamer_twitch_id(),
+      database_connection,
+    )
+    .await?;
+
+    channel_point_redemption::Model::fill_reward_details(
+      streamer.id,
+      redemption_message.get_reward_id(),
+      redemption_message.get_reward_title(),
+      redemption_message.get_reward_cost(),
+      database_connection,
+    )
+    .await?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::channel::third_party_emote_list_storage::EmoteListStorage;
+  use crate::testing_helper_methods::timestamp_from_string;
+  use irc::proto::Message as IrcMessage;
+
+  fn user_query_results() -> Vec<Vec<twitch_user::Model>> {
+    vec![
+      vec![twitch_user::Model {
+        id: 1,
+        twitch_id: 578762718,
+        login_name: "fallenshadow".into(),
+        display_name: "fallenshadow".into(),
+      }],
+      vec![],
+      vec![twitch_user::Model {
+        id: 3,
+        twitch_id: 128831052,
+        login_name: "linkthedot".into(),
+        display_name: "LinkTheDot".into(),
+      }],
+    ]
+  }
+
+  #[tokio::test]
+  async fn custom_reward_redemption_is_parsed() {
+    let message: IrcMessage =
+      "@room-id=578762718;user-id=128831052;tmi-sent-ts=1740956922774;display-name=LinkTheDot;custom-reward-id=5d1f6b3e-1a4c-4b5e-9c3e-2f3a4b5c6d7e :linkthedot!linkthedot@linkthedot.tmi.twitch.tv PRIVMSG #fallenshadow :glorp\r\n"
+        .parse()
+        .unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results(user_query_results())
+      .append_query_results([vec![channel_point_redemption::Model {
+        id: 1,
+        timestamp: timestamp_from_string("1740956900000"),
+        channel_id: 1,
+        twitch_user_id: 2,
+        stream_id: None,
+        reward_id: Some("5d1f6b3e-1a4c-4b5e-9c3e-2f3a4b5c6d7e".into()),
+        reward_title: Some("Hydrate".into()),
+        reward_cost: Some(500),
+        is_highlighted_message: 0,
+        contents: Some("drink water".into()),
+      }]])
+      .into_connection();
+    let third_party_emote_storage = EmoteListStorage::test_list().unwrap();
+    let message_parser = MessageParser::new(&message, &third_party_emote_storage)
+      .unwrap()
+      .unwrap();
+
+    let result = message_parser
+      .parse_channel_point_redemption(&mock_database)
+      .await
+      .unwrap();
+
+    let expected_active_model = channel_point_redemption::ActiveModel {
+      id: ActiveValue::NotSet,
+      timestamp: Set(timestamp_from_string("1740956922774")),
+      channel_id: Set(1),
+      twitch_user_id: Set(3),
+      stream_id: Set(None),
+      reward_id: Set(Some("5d1f6b3e-1a4c-4b5e-9c3e-2f3a4b5c6d7e".into())),
+      reward_title: Set(Some("Hydrate".into())),
+      reward_cost: Set(Some(500)),
+      is_highlighted_message: Set(0_i8),
+      contents: Set(Some("glorp".into())),
+    };
+
+    assert_eq!(result, expected_active_model);
+  }
+
+  #[tokio::test]
+  async fn highlighted_message_is_parsed() {
+    let message: IrcMessage =
+      "@room-id=578762718;user-id=128831052;tmi-sent-ts=1740956922774;display-name=LinkTheDot;msg-id=highlighted-message :linkthedot!linkthedot@linkthedot.tmi.twitch.tv PRIVMSG #fallenshadow :waaa\r\n"
+        .parse()
+        .unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results(user_query_results())
+      .into_connection();
+    let third_party_emote_storage = EmoteListStorage::test_list().unwrap();
+    let message_parser = MessageParser::new(&message, &third_party_emote_storage)
+      .unwrap()
+      .unwrap();
+
+    let result = message_parser
+      .parse_channel_point_redemption(&mock_database)
+      .await
+      .unwrap();
+
+    let expected_active_model = channel_point_redemption::ActiveModel {
+      id: ActiveValue::NotSet,
+      timestamp: Set(timestamp_from_string("1740956922774")),
+      channel_id: Set(1),
+      twitch_user_id: Set(3),
+      stream_id: Set(None),
+      reward_id: Set(None),
+      reward_title: Set(Some(HIGHLIGHTED_MESSAGE_REWARD_TITLE.into())),
+      reward_cost: Set(None),
+      is_highlighted_message: Set(1_i8),
+      contents: Set(Some("waaa".into())),
+    };
+
+    assert_eq!(result, expected_active_model);
+  }
+}
@@ -0,0 +1,288 @@
+use crate::errors::AppError;
+use crate::irc_chat::mirrored_twitch_objects::message::TwitchIrcMessage;
+use app_config::donation_bot_config::{DonationBotConfig, DonationParserKind};
+use app_config::AppConfig;
+use currency_amount::iso_currency_code;
+use sea_orm::prelude::Decimal;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use streamlabs_donation::StreamlabsDonation;
+use template_donation::TemplateDonation;
+
+pub mod currency_amount;
+pub mod streamlabs_donation;
+pub mod template_donation;
+
+static DONATION_BOTS: OnceLock<DonationBots> = OnceLock::new();
+
+/// A donation read from a bot's alert in chat.
+#[derive(Debug, PartialEq)]
+pub struct DonationAlert<'a> {
+  /// The amount in the currency it was donated in.
+  pub amount: Decimal,
+  /// The ISO 4217 code of the currency the donation was made in.
+  pub currency: &'a str,
+  pub donation_message: &'a str,
+  pub donator_name: &'a str,
+}
+
+/// Reads the donation alerts a bot posts in chat.
+///
+/// New alert formats are added by implementing this and adding a [`DonationParserKind`] for it.
+pub trait DonationBotParser: std::fmt::Debug + Send + Sync {
+  /// Returns the donation if the message is an alert in this parser's format.
+  fn parse<'a>(&'a self, message_contents: &'a str) -> Option<DonationAlert<'a>>;
+}
+
+#[derive(Debug)]
+struct DonationBot {
+  bot_user_id: String,
+  parser: Box<dyn DonationBotParser>,
+}
+
+impl DonationBot {
+  fn streamelements() -> Self {
+    Self {
+      bot_user_id: TwitchIrcMessage::STREAMELEMENTS_TWITCH_ID.to_string(),
+      parser: Box::new(StreamlabsDonation),
+    }
+  }
+}
+
+/// The donation bots for each channel, built from [`AppConfig::donation_bots`].
+///
+/// Channels without any configured bots read StreamElements' alerts with the [`StreamlabsDonation`] parser.
+#[derive(Debug)]
+pub struct DonationBots {
+  /// Keyed by channel login name.
+  channel_bots: HashMap<String, Vec<DonationBot>>,
+  default_bot: DonationBot,
+}
+
+impl DonationBots {
+  /// Builds the donation bots from the config, returning an error if any of them are misconfigured.
+  ///
+  /// Should be called once on startup so a misconfigured bot stops the tracker before it connects.
+  pub fn init() -> Result<&'static Self, AppError> {
+    if let Some(donation_bots) = DONATION_BOTS.get() {
+      return Ok(donation_bots);
+    }
+
+    let donation_bots = Self::from_config(AppConfig::donation_bots())?;
+
+    Ok(DONATION_BOTS.get_or_init(|| donation_bots))
+  }
+
+  /// Returns the donation bots from the config.
+  ///
+  /// If they weren't built with [`init`](Self::init) and are misconfigured, every channel falls back to StreamElements.
+  pub fn get() -> &'static Self {
+    DONATION_BOTS.get_or_init(|| {
+      Self::from_config(AppConfig::donation_bots()).unwrap_or_else(|error| {
+        tracing::error!(
+          "Failed to build the donation bots, falling back to StreamElements. Reason: {error}"
+        );
+
+        Self {
+          channel_bots: HashMap::new(),
+          default_bot: DonationBot::streamelements(),
+        }
+      })
+    })
+  }
+
+  pub fn from_config(donation_bot_configs: &[DonationBotConfig]) -> Result<Self, AppError> {
+    let mut channel_bots: HashMap<String, Vec<DonationBot>> = HashMap::new();
+
+    for donation_bot_config in donation_bot_configs {
+      let donation_bot = DonationBot {
+        bot_user_id: donation_bot_config.bot_user_id.clone(),
+        parser: Self::build_parser(donation_bot_config)?,
+      };
+
+      channel_bots
+        .entry(donation_bot_config.channel.to_lowercase())
+        .or_default()
+        .push(donation_bot);
+    }
+
+    Ok(Self {
+      channel_bots,
+      default_bot: DonationBot::streamelements(),
+    })
+  }
+
+  fn build_parser(
+    donation_bot_config: &DonationBotConfig,
+  ) -> Result<Box<dyn DonationBotParser>, AppError> {
+    let invalid_config = |reason: String| AppError::InvalidDonationBotConfig {
+      channel: donation_bot_config.channel.clone(),
+      reason,
+    };
+
+    match donation_bot_config.parser {
+      DonationParserKind::Streamlabs => Ok(Box::new(StreamlabsDonation)),
+      DonationParserKind::Template => {
+        let currency = donation_bot_config
+          .currency
+          .as_deref()
+          .unwrap_or(AppConfig::reporting_currency());
+        let Some(currency) = iso_currency_code(currency) else {
+          return Err(invalid_config(format!(
+            "{currency:?} isn't an ISO 4217 currency code."
+          )));
+        };
+        let parser = match (&donation_bot_config.pattern, &donation_bot_config.template) {
+          (Some(pattern), _) => TemplateDonation::from_pattern(pattern, currency),
+          (None, Some(template)) => TemplateDonation::from_template(template, currency),
+          (None, None) => {
+            return Err(invalid_config(
+              "The template parser needs a template or pattern.".to_string(),
+            ))
+          }
+        };
+
+        parser
+          .map(|parser| Box::new(parser) as Box<dyn DonationBotParser>)
+          .map_err(|error| invalid_config(error.to_string()))
+      }
+    }
+  }
+
+  /// Returns true if the user posts donation alerts in the channel.
+  pub fn is_donation_bot(&self, channel_login: &str, user_id: &str) -> bool {
+    self
+      .bots_for_channel(channel_login)
+      .iter()
+      .any(|donation_bot| donation_bot.bot_user_id == user_id)
+  }
+
+  /// Returns the donation if the user is one of the channel's donation bots, and the message is one of its alerts.
+  pub fn parse<'a>(
+    &'a self,
+    channel_login: &str,
+    user_id: &str,
+    message_contents: &'a str,
+  ) -> Option<DonationAlert<'a>> {
+    self
+      .bots_for_channel(channel_login)
+      .iter()
+      .filter(|donation_bot| donation_bot.bot_user_id == user_id)
+      .find_map(|donation_bot| donation_bot.parser.parse(message_contents))
+  }
+
+  fn bots_for_channel(&self, channel_login: &str) -> &[DonationBot] {
+    let channel_login = channel_login.trim_start_matches('#').to_lowercase();
+
+    match self.channel_bots.get(&channel_login) {
+      Some(channel_bots) => channel_bots,
+      None => std::slice::from_ref(&self.default_bot),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const FOSSABOT_TWITCH_ID: &str = "237719657";
+
+  fn donation_bots() -> DonationBots {
+    DonationBots::from_config(&[DonationBotConfig {
+      channel: "shadowchama".into(),
+      bot_user_id: FOSSABOT_TWITCH_ID.into(),
+      parser: DonationParserKind::Template,
+      template: Some("{name} donated {amount}! {message}".into()),
+      pattern: None,
+      currency: Some("USD".into()),
+    }])
+    .unwrap()
+  }
+
+  #[test]
+  fn configured_channels_use_their_own_bots() {
+    let donation_bots = donation_bots();
+
+    let result = donation_bots
+      .parse(
+        "#shadowchama",
+        FOSSABOT_TWITCH_ID,
+        "linkthedot donated 5! glorp",
+      )
+      .unwrap();
+
+    assert_eq!(
+      result,
+      DonationAlert {
+        amount: Decimal::from(5),
+        currency: "USD",
+        donation_message: "glorp",
+        donator_name: "linkthedot",
+      }
+    );
+    assert!(
+      !donation_bots.is_donation_bot("#shadowchama", TwitchIrcMessage::STREAMELEMENTS_TWITCH_ID)
+    );
+  }
+
+  #[test]
+  fn unconfigured_channels_use_streamelements() {
+    let donation_bots = donation_bots();
+    let alert = "linkthedot just tipped £5.00! here's what they say: glorp";
+
+    assert!(donation_bots
+      .parse(
+        "#fallenshadow",
+        TwitchIrcMessage::STREAMELEMENTS_TWITCH_ID,
+        alert
+      )
+      .is_some());
+    assert!(donation_bots
+      .parse("#fallenshadow", FOSSABOT_TWITCH_ID, alert)
+      .is_none());
+  }
+
+  #[test]
+  fn template_parsers_need_a_template_or_pattern() {
+    let result = DonationBots::from_config(&[DonationBotConfig {
+      channel: "shadowchama".into(),
+      bot_user_id: FOSSABOT_TWITCH_ID.into(),
+      parser: DonationParserKind::Template,
+      template: None,
+      pattern: None,
+      currency: None,
+    }]);
+
+    assert!(matches!(
+      result,
+      Err(AppError::InvalidDonationBotConfig { .. })
+    ));
+  }
+
+  #[test]
+  fn invalid_patterns_and_currencies_are_config_errors() {
+    let donation_bot_config = |pattern: &str, currency: &str| DonationBotConfig {
+      channel: "shadowchama".into(),
+      bot_user_id: FOSSABOT_TWITCH_ID.into(),
+      parser: DonationParserKind::Template,
+      template: None,
+      pattern: Some(pattern.into()),
+      currency: Some(currency.into()),
+    };
+
+    let invalid_pattern = DonationBots::from_config(&[donation_bot_config("(?P<name>", "USD")]);
+    let invalid_currency = DonationBots::from_config(&[donation_bot_config(
+      r"(?P<name>\w+) (?P<amount>\d+)",
+      "dollars",
+    )]);
+
+    assert!(matches!(
+      invalid_pattern,
+      Err(AppError::InvalidDonationBotConfig { .. })
+    ));
+    assert!(matches!(
+      invalid_currency,
+      Err(AppError::InvalidDonationBotConfig { .. })
+    ));
+  }
+}
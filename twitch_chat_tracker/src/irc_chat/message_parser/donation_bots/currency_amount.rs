@@ -0,0 +1,106 @@
+use regex::Captures;
+use sea_orm::prelude::Decimal;
+
+/// Currency symbols and the ISO code they're stored as.
+///
+/// Prefixed dollar signs are listed before `$` so they're matched first.
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+  ("CA$", "CAD"),
+  ("C$", "CAD"),
+  ("AU$", "AUD"),
+  ("A$", "AUD"),
+  ("NZ$", "NZD"),
+  ("MX$", "MXN"),
+  ("US$", "USD"),
+  ("R$", "BRL"),
+  ("$", "USD"),
+  ("£", "GBP"),
+  ("€", "EUR"),
+  ("¥", "JPY"),
+  ("₹", "INR"),
+  ("₩", "KRW"),
+  ("₽", "RUB"),
+  ("zł", "PLN"),
+];
+/// ISO codes accepted when an amount is written as `10.00 USD` or `USD 10.00`.
+const CURRENCY_CODES: &[&str] = &[
+  "USD", "EUR", "GBP", "CAD", "AUD", "NZD", "JPY", "BRL", "MXN", "INR", "KRW", "RUB", "PLN", "SEK",
+  "NOK", "DKK", "CHF", "CZK", "HUF", "TRY", "ZAR", "SGD", "HKD", "PHP", "ILS",
+];
+/// The active ISO 4217 codes for circulating currencies.
+const ISO_4217_CODES: &[&str] = &[
+  "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT", "BGN",
+  "BHD", "BIF", "BMD", "BND", "BOB", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD", "CDF", "CHF",
+  "CLP", "CNY", "COP", "CRC", "CUP", "CVE", "CZK", "DJF", "DKK", "DOP", "DZD", "EGP", "ERN", "ETB",
+  "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP", "GMD", "GNF", "GTQ", "GYD", "HKD", "HNL", "HTG",
+  "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD", "JOD", "JPY", "KES", "KGS", "KHR", "KMF",
+  "KPW", "KRW", "KWD", "KYD", "KZT", "LAK", "LBP", "LKR", "LRD", "LSL", "LYD", "MAD", "MDL", "MGA",
+  "MKD", "MMK", "MNT", "MOP", "MRU", "MUR", "MVR", "MWK", "MXN", "MYR", "MZN", "NAD", "NGN", "NIO",
+  "NOK", "NPR", "NZD", "OMR", "PAB", "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD",
+  "RUB", "RWF", "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP", "SLE", "SOS", "SRD", "SSP", "STN",
+  "SVC", "SYP", "SZL", "THB", "TJS", "TMT", "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX",
+  "USD", "UYU", "UZS", "VED", "VES", "VND", "VUV", "WST", "XAF", "XCD", "XCG", "XOF", "XPF", "YER",
+  "ZAR", "ZMW", "ZWG",
+];
+/// Matches `1234`, `1234.56` and `1,234.56`.
+pub const AMOUNT_PATTERN: &str = r"\d{1,3}(?:,\d{3})+(?:\.\d+)?|\d+(?:\.\d+)?";
+
+/// A regex fragment matching an amount with a currency symbol or ISO code, such as `£5.00`, `CA$5`, `10.00 USD` or `EUR 10`.
+///
+/// Read the match with [`currency_and_amount`].
+pub fn currency_amount_pattern() -> String {
+  let symbols = CURRENCY_SYMBOLS
+    .iter()
+    .map(|(symbol, _)| regex::escape(symbol))
+    .collect::<Vec<String>>()
+    .join("|");
+  let codes = CURRENCY_CODES.join("|");
+
+  format!(
+    r"(?:(?P<symbol>{symbols})\s?(?P<symbol_amount>{AMOUNT_PATTERN})|(?P<leading_code>{codes})\s?(?P<leading_code_amount>{AMOUNT_PATTERN})|(?P<trailing_code_amount>{AMOUNT_PATTERN})\s?(?P<trailing_code>{codes}))"
+  )
+}
+
+/// Returns the ISO code and amount matched by [`currency_amount_pattern`].
+pub fn currency_and_amount(amount_captures: &Captures) -> Option<(&'static str, Decimal)> {
+  if let Some(symbol) = amount_captures.name("symbol") {
+    let currency = CURRENCY_SYMBOLS
+      .iter()
+      .find(|(currency_symbol, _)| *currency_symbol == symbol.as_str())
+      .map(|(_, currency)| *currency)?;
+
+    return Some((
+      currency,
+      parse_amount(amount_captures.name("symbol_amount")?.as_str())?,
+    ));
+  }
+
+  let (code, amount) = match amount_captures.name("leading_code") {
+    Some(code) => (code, amount_captures.name("leading_code_amount")?),
+    None => (
+      amount_captures.name("trailing_code")?,
+      amount_captures.name("trailing_code_amount")?,
+    ),
+  };
+  let currency = CURRENCY_CODES
+    .iter()
+    .find(|currency_code| **currency_code == code.as_str())
+    .copied()?;
+
+  Some((currency, parse_amount(amount.as_str())?))
+}
+
+/// Returns the uppercased ISO 4217 code if the currency is one.
+pub fn iso_currency_code(currency: &str) -> Option<&'static str> {
+  let currency = currency.trim().to_uppercase();
+
+  ISO_4217_CODES
+    .iter()
+    .find(|currency_code| **currency_code == currency)
+    .copied()
+}
+
+/// Parses an amount matched by [`AMOUNT_PATTERN`].
+pub fn parse_amount(amount: &str) -> Option<Decimal> {
+  amount.replace(',', "").parse().ok()
+}
@@ -1,19 +1,28 @@
+use super::currency_amount::{currency_amount_pattern, currency_and_amount};
+use super::{DonationAlert, DonationBotParser};
 use regex::Regex;
+use std::sync::LazyLock;
 
-#[derive(Debug, PartialEq)]
-pub struct StreamlabsDonation<'a> {
-  pub amount: f32,
-  pub donation_message: &'a str,
-  pub donator_name: &'a str,
+static AMOUNT_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(&format!("{}!", currency_amount_pattern())).unwrap());
+
+/// Reads alerts formatted as `<name> just tipped <amount>! <text> here's what they say: <message>`.
+#[derive(Debug, Default)]
+pub struct StreamlabsDonation;
+
+impl DonationBotParser for StreamlabsDonation {
+  fn parse<'a>(&'a self, message_contents: &'a str) -> Option<DonationAlert<'a>> {
+    Self::parse_streamlabs_donation_value_from_message_content(message_contents)
+  }
 }
 
 // Take every character from start to the last instance of the pattern "just tipped" to get the name.
-//   Take the last instance of £[0-9]*.[0-9]*! before the first `here's what they say:` to get the amount.
+//   Take the last amount ending in `!` before the first `here's what they say:` to get the amount and currency.
 //   Take everything after the first `here's what they say:` to get the message.
-impl<'a> StreamlabsDonation<'a> {
+impl StreamlabsDonation {
   pub fn parse_streamlabs_donation_value_from_message_content(
-    message_content: &'a str,
-  ) -> Option<Self> {
+    message_content: &str,
+  ) -> Option<DonationAlert<'_>> {
     let name_and_amount_max_position = message_content.find("here's what they say:")?;
     let donation_message =
       message_content[name_and_amount_max_position + "here's what they say:".len()..].trim();
@@ -23,14 +32,12 @@ impl<'a> StreamlabsDonation<'a> {
 
     let donator_name = name_and_amount_content[..just_tipped_position].trim();
 
-    let amount_regex = Regex::new(r"£(\d+(?:\.\d+)?)!").ok()?;
-    let amount_match = amount_regex.find_iter(name_and_amount_content).last()?;
-    let amount_captures =
-      amount_regex.captures(&name_and_amount_content[amount_match.start()..amount_match.end()])?;
-    let amount: f32 = amount_captures.get(1)?.as_str().parse().ok()?;
+    let amount_captures = AMOUNT_REGEX.captures_iter(name_and_amount_content).last()?;
+    let (currency, amount) = currency_and_amount(&amount_captures)?;
 
-    Some(StreamlabsDonation {
+    Some(DonationAlert {
       amount,
+      currency,
       donation_message,
       donator_name,
     })
@@ -40,6 +47,7 @@ impl<'a> StreamlabsDonation<'a> {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use sea_orm::prelude::Decimal;
 
   #[test]
   fn test_empty_message() {
@@ -50,7 +58,7 @@ mod tests {
       StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(input).unwrap();
 
     assert_eq!(result.donator_name, "anon y moose");
-    assert_eq!(result.amount, 120.0);
+    assert_eq!(result.amount, Decimal::new(120, 0));
     assert_eq!(result.donation_message, "");
   }
 
@@ -62,7 +70,7 @@ mod tests {
       StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(input).unwrap();
 
     assert_eq!(result.donator_name, "anon y moose");
-    assert_eq!(result.amount, 120.0);
+    assert_eq!(result.amount, Decimal::new(120, 0));
     assert_eq!(result.donation_message, "This is a message");
   }
 
@@ -74,7 +82,7 @@ mod tests {
       StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(input).unwrap();
 
     assert_eq!(result.donator_name, "john doe mentioned £50.00! but then");
-    assert_eq!(result.amount, 75.25);
+    assert_eq!(result.amount, Decimal::new(7525, 2));
     assert_eq!(result.donation_message, "Great content!");
   }
 
@@ -86,7 +94,7 @@ mod tests {
       StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(input).unwrap();
 
     assert_eq!(result.donator_name, "alice just tipped earlier, but bob");
-    assert_eq!(result.amount, 30.5);
+    assert_eq!(result.amount, Decimal::new(305, 1));
     assert_eq!(result.donation_message, "Hello world");
   }
 
@@ -99,7 +107,7 @@ mod tests {
       StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(input).unwrap();
 
     assert_eq!(result.donator_name, "alice");
-    assert_eq!(result.amount, 30.0);
+    assert_eq!(result.amount, Decimal::new(30, 0));
     assert_eq!(result.donation_message, "I also have £50.25! in my account");
   }
 
@@ -111,7 +119,7 @@ mod tests {
       StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(input).unwrap();
 
     assert_eq!(result.donator_name, "user £100! fake");
-    assert_eq!(result.amount, 25.5);
+    assert_eq!(result.amount, Decimal::new(255, 1));
     assert_eq!(result.donation_message, "Testing");
   }
 
@@ -123,10 +131,53 @@ mod tests {
       StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(input).unwrap();
 
     assert_eq!(result.donator_name, "user123");
-    assert_eq!(result.amount, 5.0);
+    assert_eq!(result.amount, Decimal::new(5, 0));
     assert_eq!(result.donation_message, "Short tip");
   }
 
+  #[test]
+  fn currency_symbols_are_recognized() {
+    let inputs = [
+      ("user123 just tipped £5.00! here's what they say: hi", "GBP"),
+      ("user123 just tipped $5.00! here's what they say: hi", "USD"),
+      ("user123 just tipped €5.00! here's what they say: hi", "EUR"),
+      (
+        "user123 just tipped CA$5.00! here's what they say: hi",
+        "CAD",
+      ),
+      (
+        "user123 just tipped A$5.00! here's what they say: hi",
+        "AUD",
+      ),
+    ];
+
+    for (input, expected_currency) in inputs {
+      let result =
+        StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(input).unwrap();
+
+      assert_eq!(result.currency, expected_currency, "{input}");
+      assert_eq!(result.amount, Decimal::new(5, 0), "{input}");
+    }
+  }
+
+  #[test]
+  fn currency_codes_are_recognized() {
+    let trailing_code = "user123 just tipped 10.00 USD! here's what they say: hi";
+    let leading_code = "user123 just tipped EUR 1,250.50! here's what they say: hi";
+
+    let trailing_code_result =
+      StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(trailing_code)
+        .unwrap();
+    let leading_code_result =
+      StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(leading_code)
+        .unwrap();
+
+    assert_eq!(trailing_code_result.currency, "USD");
+    assert_eq!(trailing_code_result.amount, Decimal::new(10, 0));
+    assert_eq!(leading_code_result.currency, "EUR");
+    assert_eq!(leading_code_result.amount, Decimal::new(12505, 1));
+  }
+
   #[test]
   fn test_invalid_input() {
     assert!(
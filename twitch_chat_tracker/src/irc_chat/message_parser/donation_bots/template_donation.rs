@@ -0,0 +1,215 @@
+use super::currency_amount::{
+  currency_amount_pattern, currency_and_amount, iso_currency_code, parse_amount, AMOUNT_PATTERN,
+};
+use super::{DonationAlert, DonationBotParser};
+use regex::Regex;
+
+const NAME_PLACEHOLDER: &str = "{name}";
+const AMOUNT_PLACEHOLDER: &str = "{amount}";
+const MESSAGE_PLACEHOLDER: &str = "{message}";
+
+/// Reads alerts with a configured regex, such as the custom alert texts set up in Fossabot or a Ko-fi relay.
+///
+/// The regex needs `name` and `amount` groups, and can have `currency` and `message` groups.
+/// Amounts without a currency are taken to be in the configured currency.
+/// Alerts with a `currency` that isn't an ISO 4217 code are ignored.
+#[derive(Debug)]
+pub struct TemplateDonation {
+  pattern: Regex,
+  currency: String,
+}
+
+impl TemplateDonation {
+  /// Builds the parser from alert text with `{name}`, `{amount}` and `{message}` placeholders.
+  ///
+  /// Everything else in the template has to match the alert exactly.
+  pub fn from_template(template: &str, currency: &str) -> Result<Self, regex::Error> {
+    let mut pattern = String::from("^");
+    let mut remaining_template = template.trim();
+
+    while let Some((position, placeholder)) = next_placeholder(remaining_template) {
+      pattern.push_str(&regex::escape(&remaining_template[..position]));
+      pattern.push_str(&placeholder_pattern(placeholder));
+
+      remaining_template = &remaining_template[position + placeholder.len()..];
+    }
+
+    pattern.push_str(&regex::escape(remaining_template));
+    pattern.push('$');
+
+    Self::from_pattern(&pattern, currency)
+  }
+
+  pub fn from_pattern(pattern: &str, currency: &str) -> Result<Self, regex::Error> {
+    Ok(Self {
+      pattern: Regex::new(pattern)?,
+      currency: currency.to_uppercase(),
+    })
+  }
+}
+
+impl DonationBotParser for TemplateDonation {
+  fn parse<'a>(&'a self, message_contents: &'a str) -> Option<DonationAlert<'a>> {
+    let captures = self.pattern.captures(message_contents)?;
+    let donator_name = captures.name("name")?.as_str().trim();
+    let (currency, amount) = match captures.name("amount") {
+      Some(amount) => {
+        let currency = match captures.name("currency") {
+          Some(currency) => {
+            let Some(currency_code) = iso_currency_code(currency.as_str()) else {
+              tracing::warn!(
+                "Ignoring a donation alert with an unknown currency {:?}: {message_contents:?}",
+                currency.as_str()
+              );
+              return None;
+            };
+
+            currency_code
+          }
+          None => &self.currency,
+        };
+
+        (currency, parse_amount(amount.as_str())?)
+      }
+      // `{amount}` matched an amount with a currency symbol or code.
+      None => currency_and_amount(&captures)?,
+    };
+    let donation_message = captures
+      .name("message")
+      .map(|message| message.as_str().trim())
+      .unwrap_or_default();
+
+    Some(DonationAlert {
+      amount,
+      currency,
+      donation_message,
+      donator_name,
+    })
+  }
+}
+
+/// Returns the position of the first placeholder in the template, and which one it is.
+fn next_placeholder(template: &str) -> Option<(usize, &'static str)> {
+  [NAME_PLACEHOLDER, AMOUNT_PLACEHOLDER, MESSAGE_PLACEHOLDER]
+    .into_iter()
+    .filter_map(|placeholder| Some((template.find(placeholder)?, placeholder)))
+    .min_by_key(|(position, _)| *position)
+}
+
+fn placeholder_pattern(placeholder: &str) -> String {
+  match placeholder {
+    NAME_PLACEHOLDER => r"(?P<name>.+?)".to_string(),
+    AMOUNT_PLACEHOLDER => format!(
+      r"(?:{}|(?P<amount>{AMOUNT_PATTERN}))",
+      currency_amount_pattern()
+    ),
+    _ => r"(?P<message>.*)".to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use sea_orm::prelude::Decimal;
+
+  /// An alert text, and the (name, amount, currency, message) it should parse to.
+  type CorpusEntry = (
+    &'static str,
+    (&'static str, &'static str, &'static str, &'static str),
+  );
+
+  const FOSSABOT_TEMPLATE: &str = "{name} donated {amount}! {message}";
+  const FOSSABOT_CORPUS: &[CorpusEntry] = &[
+    (
+      "linkthedot donated $5.00! glorp",
+      ("linkthedot", "5.00", "USD", "glorp"),
+    ),
+    (
+      "anon y moose donated €1,250.50! thank you for the chocolate funds",
+      (
+        "anon y moose",
+        "1250.50",
+        "EUR",
+        "thank you for the chocolate funds",
+      ),
+    ),
+    ("linkthedot donated 10! ", ("linkthedot", "10", "GBP", "")),
+  ];
+
+  const KOFI_TEMPLATE: &str = "{name} bought you a coffee ({amount}) on Ko-fi: {message}";
+  const KOFI_CORPUS: &[CorpusEntry] = &[
+    (
+      "linkthedot bought you a coffee (£3.00) on Ko-fi: waaa",
+      ("linkthedot", "3.00", "GBP", "waaa"),
+    ),
+    (
+      "fallenshadow bought you a coffee (15.00 CAD) on Ko-fi: ",
+      ("fallenshadow", "15.00", "CAD", ""),
+    ),
+  ];
+
+  fn assert_corpus(parser: &TemplateDonation, corpus: &[CorpusEntry]) {
+    for (alert, (name, amount, currency, message)) in corpus {
+      let result = parser.parse(alert).unwrap_or_else(|| panic!("{alert}"));
+
+      assert_eq!(result.donator_name, *name, "{alert}");
+      assert_eq!(result.amount, amount.parse::<Decimal>().unwrap(), "{alert}");
+      assert_eq!(result.currency, *currency, "{alert}");
+      assert_eq!(result.donation_message, *message, "{alert}");
+    }
+  }
+
+  #[test]
+  fn fossabot_corpus() {
+    let parser = TemplateDonation::from_template(FOSSABOT_TEMPLATE, "gbp").unwrap();
+
+    assert_corpus(&parser, FOSSABOT_CORPUS);
+  }
+
+  #[test]
+  fn kofi_corpus() {
+    let parser = TemplateDonation::from_template(KOFI_TEMPLATE, "GBP").unwrap();
+
+    assert_corpus(&parser, KOFI_CORPUS);
+  }
+
+  #[test]
+  fn patterns_are_used_as_is() {
+    let parser = TemplateDonation::from_pattern(
+      r"^Thank you (?P<name>\w+) for the (?P<amount>\d+) (?P<currency>[A-Z]{3})",
+      "GBP",
+    )
+    .unwrap();
+
+    let result = parser.parse("Thank you linkthedot for the 20 USD").unwrap();
+
+    assert_eq!(result.donator_name, "linkthedot");
+    assert_eq!(result.amount, Decimal::from(20));
+    assert_eq!(result.currency, "USD");
+    assert_eq!(result.donation_message, "");
+  }
+
+  #[test]
+  fn captured_currencies_are_uppercased_iso_codes() {
+    let parser = TemplateDonation::from_pattern(
+      r"^Thank you (?P<name>\w+) for the (?P<amount>\d+) (?P<currency>\w+)",
+      "GBP",
+    )
+    .unwrap();
+
+    let result = parser.parse("Thank you linkthedot for the 20 usd").unwrap();
+
+    assert_eq!(result.currency, "USD");
+    assert!(parser
+      .parse("Thank you linkthedot for the 20 dollarydoos")
+      .is_none());
+  }
+
+  #[test]
+  fn text_outside_of_placeholders_must_match() {
+    let parser = TemplateDonation::from_template(FOSSABOT_TEMPLATE, "GBP").unwrap();
+
+    assert!(parser.parse("linkthedot followed! glorp").is_none());
+    assert!(parser.parse("linkthedot donated nothing! glorp").is_none());
+  }
+}
@@ -4,6 +4,7 @@ use crate::irc_chat::mirrored_twitch_objects::twitch_message_type::TwitchMessage
 use entities::*;
 use entity_extensions::donation_event::DonationEventExtensions;
 use entity_extensions::prelude::*;
+use sea_orm::prelude::Decimal;
 use sea_orm::*;
 use sea_orm_active_enums::EventType;
 
@@ -87,7 +88,7 @@ impl MessageParser<'_> {
         location: "gift sub parsing",
       });
     };
-    let Ok(gift_amount) = gift_amount.trim().parse::<f32>() else {
+    let Ok(gift_amount) = gift_amount.trim().parse::<Decimal>() else {
       return Err(AppError::FailedToParseValue {
         value_name: "gift sub count",
         location: "gift sub parsing",
@@ -200,7 +201,7 @@ mod tests {
     let expected_active_model = donation_event::ActiveModel {
       id: ActiveValue::NotSet,
       event_type: Set(EventType::GiftSubs),
-      amount: Set(5.0_f32),
+      amount: Set(Decimal::from(5)),
       timestamp: Set(timestamp_from_string("1740956922774")),
       donator_twitch_user_id: Set(Some(3)),
       donation_receiver_twitch_user_id: Set(1),
@@ -209,6 +210,9 @@ mod tests {
       unknown_user_id: ActiveValue::NotSet,
       origin_id: Set(Some("1000".into())),
       source_id: NotSet,
+      hype_train_id: NotSet,
+      currency: NotSet,
+      original_amount: NotSet,
     };
 
     assert_eq!(result, Some(expected_active_model));
@@ -229,7 +233,7 @@ mod tests {
     let expected_active_model = donation_event::ActiveModel {
       id: ActiveValue::NotSet,
       event_type: Set(EventType::GiftSubs),
-      amount: Set(1.0_f32),
+      amount: Set(Decimal::from(1)),
       timestamp: Set(timestamp_from_string("1740956922774")),
       donator_twitch_user_id: Set(Some(3)),
       donation_receiver_twitch_user_id: Set(1),
@@ -238,6 +242,9 @@ mod tests {
       unknown_user_id: ActiveValue::NotSet,
       origin_id: Set(Some("1000".into())),
       source_id: NotSet,
+      hype_train_id: NotSet,
+      currency: NotSet,
+      original_amount: NotSet,
     };
 
     assert_eq!(result, Some(expected_active_model));
@@ -291,7 +298,7 @@ mod tests {
       .append_query_results([vec![donation_event::Model {
         id: 1,
         event_type: EventType::GiftSubs,
-        amount: 1.0_f32,
+        amount: Decimal::from(1),
         timestamp: timestamp_from_string("1740956922774"),
         donator_twitch_user_id: Some(3),
         donation_receiver_twitch_user_id: 1,
@@ -300,6 +307,9 @@ mod tests {
         unknown_user_id: None,
         origin_id: Some("1000".into()),
         source_id: None,
+        hype_train_id: None,
+        currency: None,
+        original_amount: None,
       }]]);
 
     for iteration in 0..sub_count.unwrap_or(0) {
@@ -426,7 +436,7 @@ mod tests {
     let expected_model = donation_event::Model {
       id: 1,
       event_type: EventType::GiftSubs,
-      amount: 1.0_f32,
+      amount: Decimal::from(1),
       timestamp: timestamp_from_string("1740956922774"),
       donator_twitch_user_id: Some(3),
       donation_receiver_twitch_user_id: 1,
@@ -435,11 +445,14 @@ mod tests {
       unknown_user_id: None,
       origin_id: Some("1000".into()),
       source_id: None,
+      hype_train_id: None,
+      currency: None,
+      original_amount: None,
     };
     let expected_active_model = donation_event::ActiveModel {
       id: ActiveValue::NotSet,
       event_type: Set(EventType::GiftSubs),
-      amount: Set(3.0_f32),
+      amount: Set(Decimal::from(3)),
       timestamp: Set(timestamp_from_string("1740956922774")),
       donator_twitch_user_id: Set(Some(3)),
       donation_receiver_twitch_user_id: Set(1),
@@ -448,6 +461,9 @@ mod tests {
       unknown_user_id: ActiveValue::NotSet,
       origin_id: Set(Some("1000".into())),
       source_id: NotSet,
+      hype_train_id: NotSet,
+      currency: NotSet,
+      original_amount: NotSet,
     };
     let (bulk_message, _) = get_gift_subs_template(None);
     let bulk_message_parser = MessageParser::new(&bulk_message, &third_party_emote_storage)
@@ -0,0 +1,196 @@
+use super::MessageParser;
+use crate::errors::AppError;
+use crate::irc_chat::mirrored_twitch_objects::twitch_message_type::TwitchMessageType;
+use entities::sea_orm_active_enums::EventType;
+use entities::*;
+use entity_extensions::prelude::*;
+use sea_orm::prelude::Decimal;
+use sea_orm::*;
+
+impl MessageParser<'_> {
+  /// Parses the amount paid for a Hype Chat.
+  ///
+  /// The donation is linked to the message through the origin ID.
+  pub async fn parse_hype_chat(
+    &self,
+    database_connection: &DatabaseConnection,
+  ) -> Result<donation_event::ActiveModel, AppError> {
+    if self.message.message_type() != TwitchMessageType::HypeChat {
+      return Err(AppError::IncorrectMessageType {
+        expected_type: TwitchMessageType::HypeChat,
+        got_type: self.message.message_type(),
+      });
+    }
+
+    let Some(streamer_twitch_id) = self.message.room_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "room id",
+        location: "hype chat parsing",
+      });
+    };
+    let streamer_model =
+      twitch_user::Model::get_or_set_by_twitch_id(streamer_twitch_id, database_connection).await?;
+    let maybe_stream =
+      stream::Model::get_active_stream_for_user(&streamer_model, database_connection).await?;
+    let Some(donator_id) = self.message.user_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "user id",
+        location: "hype chat parsing",
+      });
+    };
+    let donator =
+      twitch_user::Model::get_or_set_by_twitch_id(donator_id, database_connection).await?;
+    let amount = self.hype_chat_amount()?;
+    let Some(currency) = self.message.hype_chat_currency() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "hype chat currency",
+        location: "hype chat parsing",
+      });
+    };
+
+    let donation_event = donation_event::ActiveModel {
+      event_type: Set(EventType::HypeChat),
+      amount: Set(amount),
+      currency: Set(Some(currency.to_uppercase())),
+      original_amount: Set(Some(amount)),
+      timestamp: Set(*self.message.timestamp()),
+      donator_twitch_user_id: Set(Some(donator.id)),
+      donation_receiver_twitch_user_id: Set(streamer_model.id),
+      stream_id: Set(maybe_stream.map(|stream| stream.id)),
+      origin_id: Set(self.message.origin_id().map(str::to_owned)),
+      source_id: Set(self.message.message_source_id().map(str::to_owned)),
+      ..Default::default()
+    };
+
+    Ok(donation_event)
+  }
+
+  /// Twitch sends the amount in the currency's smallest unit, with the exponent being how many
+  /// decimal places to shift it by. `500` with an exponent of `2` is `5.00`.
+  fn hype_chat_amount(&self) -> Result<Decimal, AppError> {
+    let Some(amount) = self.message.hype_chat_amount() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "hype chat amount",
+        location: "hype chat parsing",
+      });
+    };
+    let Some(exponent) = self.message.hype_chat_exponent() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "hype chat exponent",
+        location: "hype chat parsing",
+      });
+    };
+    let Ok(amount) = amount.trim().parse::<i64>() else {
+      return Err(AppError::FailedToParseValue {
+        value_name: "hype_chat_amount",
+        location: "hype chat parsing",
+        value: amount.to_string(),
+      });
+    };
+    let Some(exponent) = exponent
+      .trim()
+      .parse::<u32>()
+      .ok()
+      .filter(|exponent| *exponent <= Decimal::MAX_SCALE)
+    else {
+      return Err(AppError::FailedToParseValue {
+        value_name: "hype_chat_exponent",
+        location: "hype chat parsing",
+        value: exponent.to_string(),
+      });
+    };
+
+    Ok(Decimal::new(amount, exponent))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::channel::third_party_emote_list_storage::EmoteListStorage;
+  use crate::testing_helper_methods::timestamp_from_string;
+  use irc::proto::message::Tag as IrcTag;
+  use irc::proto::Message as IrcMessage;
+  use irc::proto::{Command, Prefix};
+
+  #[tokio::test]
+  async fn parse_hype_chat_expected_value() {
+    let (hype_chat_message, hype_chat_mock_database) = get_hype_chat_template();
+    let third_party_emote_storage = EmoteListStorage::test_list().unwrap();
+    let message_parser = MessageParser::new(&hype_chat_message, &third_party_emote_storage)
+      .unwrap()
+      .unwrap();
+
+    let result = message_parser
+      .parse_hype_chat(&hype_chat_mock_database)
+      .await
+      .unwrap();
+
+    let expected_active_model = donation_event::ActiveModel {
+      id: ActiveValue::NotSet,
+      event_type: Set(EventType::HypeChat),
+      amount: Set(Decimal::new(1250, 2)),
+      timestamp: Set(timestamp_from_string("1740956922774")),
+      donator_twitch_user_id: Set(Some(3)),
+      donation_receiver_twitch_user_id: Set(1),
+      stream_id: Set(None),
+      subscription_tier: ActiveValue::NotSet,
+      unknown_user_id: ActiveValue::NotSet,
+      origin_id: Set(Some("5e2b2f4c-8b0f-4b8e-9a1c-7d6f3e2a1b0c".into())),
+      source_id: Set(None),
+      hype_train_id: ActiveValue::NotSet,
+      currency: Set(Some("EUR".into())),
+      original_amount: Set(Some(Decimal::new(1250, 2))),
+    };
+
+    assert_eq!(result, expected_active_model);
+  }
+
+  fn get_hype_chat_template() -> (IrcMessage, DatabaseConnection) {
+    let tags = vec![
+      IrcTag("room-id".into(), Some("578762718".into())),
+      IrcTag("user-id".into(), Some("128831052".into())),
+      IrcTag(
+        "id".into(),
+        Some("5e2b2f4c-8b0f-4b8e-9a1c-7d6f3e2a1b0c".into()),
+      ),
+      IrcTag("pinned-chat-paid-amount".into(), Some("1250".into())),
+      IrcTag("pinned-chat-paid-currency".into(), Some("EUR".into())),
+      IrcTag("pinned-chat-paid-exponent".into(), Some("2".into())),
+      IrcTag("pinned-chat-paid-level".into(), Some("TWO".into())),
+      IrcTag("tmi-sent-ts".into(), Some("1740956922774".into())),
+      IrcTag("display-name".into(), Some("LinkTheDot".into())),
+      IrcTag("login".into(), Some("linkthedot".into())),
+    ];
+
+    let message = IrcMessage {
+      tags: Some(tags),
+      prefix: Some(Prefix::Nickname(
+        "linkthedot".into(),
+        "linkthedot".into(),
+        "linkthedot.tmi.twitch.tv".into(),
+      )),
+      command: Command::PRIVMSG("#fallenshadow".into(), "Pinned for the stream!".into()),
+    };
+
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([
+        vec![twitch_user::Model {
+          id: 1,
+          twitch_id: 578762718,
+          login_name: "fallenshadow".into(),
+          display_name: "fallenshadow".into(),
+        }],
+        vec![],
+        vec![twitch_user::Model {
+          id: 3,
+          twitch_id: 128831052,
+          login_name: "linkthedot".into(),
+          display_name: "LinkTheDot".into(),
+        }],
+      ])
+      .into_connection();
+
+    (message, mock_database)
+  }
+}
@@ -0,0 +1,239 @@
+use super::MessageParser;
+use crate::errors::AppError;
+use crate::websocket_connection::twitch_objects::hype_train::{
+  HypeTrainContributionType, HypeTrainEventType, TwitchHypeTrainMessage,
+};
+use entities::sea_orm_active_enums::HypeTrainContributionType as StoredContributionType;
+use entities::*;
+use entity_extensions::prelude::*;
+use sea_orm::*;
+
+impl MessageParser<'_> {
+  /// Takes a [`JsonValue`](serde_json::Value) constructed from Twitch's Websocket connection for
+  /// `channel.hype_train.begin`, `channel.hype_train.progress`, and `channel.hype_train.end` events.
+  ///
+  /// Stores the hype train and its top contributors, then links any donations made during it.
+  pub async fn parse_websocket_hype_train_message(
+    message: JsonValue,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), AppError> {
+    let Ok(hype_train_message) = serde_json::from_value::<TwitchHypeTrainMessage>(message.clone())
+    else {
+      return Err(AppError::FailedToParseValue {
+        value_name: "hype train update",
+        location: "parse websocket hype train message",
+        value: format!("{:?}", message),
+      });
+    };
+
+    if hype_train_message.get_subscription_event_type() == HypeTrainEventType::Unknown {
+      return Err(AppError::FailedToParseValue {
+        value_name: "hype train event type",
+        location: "parse websocket hype train message",
+        value: format!("{:?}", message["metadata"]["subscription_type"]),
+      });
+    }
+
+    let hype_train = Self::hype_train_update(&hype_train_message, database_connection)
+      .await?
+      .save(database_connection)
+      .await?
+      .try_into_model()?;
+    let contributors =
+      Self::hype_train_contributors(&hype_train, &hype_train_message, database_connection).await?;
+
+    hype_train
+      .replace_top_contributors(contributors, database_connection)
+      .await?;
+    hype_train
+      .link_donation_events(hype_train_message.get_expires_at(), database_connection)
+      .await?;
+
+    Ok(())
+  }
+
+  /// Creates the hype train if it hasn't been seen yet, otherwise updates its level, total, and end time.
+  ///
+  /// Creating it on any event means a missed `begin` event doesn't lose the train.
+  async fn hype_train_update(
+    hype_train_message: &TwitchHypeTrainMessage,
+    database_connection: &DatabaseConnection,
+  ) -> Result<hype_train::ActiveModel, AppError> {
+    let maybe_hype_train = hype_train::Model::get_by_twitch_hype_train_id(
+      hype_train_message.get_hype_train_id(),
+      database_connection,
+    )
+    .await?;
+
+    let mut hype_train_active_model = match maybe_hype_train {
+      Some(hype_train) => hype_train.into_active_model(),
+      None => {
+        let streamer = twitch_user::Model::get_or_set_by_twitch_id(
+          hype_train_message.get_streamer_twitch_id(),
+          database_connection,
+        )
+        .await?;
+        let maybe_stream =
+          stream::Model::get_active_stream_for_user(&streamer, database_connection).await?;
+
+        hype_train::ActiveModel {
+          twitch_hype_train_id: Set(hype_train_message.get_hype_train_id().to_owned()),
+          channel_id: Set(streamer.id),
+          stream_id: Set(maybe_stream.map(|stream| stream.id)),
+          start_timestamp: Set(*hype_train_message.get_started_at()),
+          ..Default::default()
+        }
+      }
+    };
+
+    hype_train_active_model.level = Set(hype_train_message.get_level());
+    hype_train_active_model.total = Set(hype_train_message.get_total());
+
+    if let Some(ended_at) = hype_train_message.get_ended_at() {
+      hype_train_active_model.end_timestamp = Set(Some(*ended_at));
+    }
+
+    Ok(hype_train_active_model)
+  }
+
+  async fn hype_train_contributors(
+    hype_train: &hype_train::Model,
+    hype_train_message: &TwitchHypeTrainMessage,
+    database_connection: &DatabaseConnection,
+  ) -> Result<Vec<hype_train_contributor::ActiveModel>, AppError> {
+    let mut contributors = vec![];
+
+    for contribution in hype_train_message.get_top_contributions() {
+      let contributor =
+        twitch_user::Model::get_or_set_by_twitch_id(&contribution.user_id, database_connection)
+          .await?;
+      let contribution_type = match contribution.contribution_type {
+        HypeTrainContributionType::Bits => StoredContributionType::Bits,
+        HypeTrainContributionType::Subscription => StoredContributionType::Subscription,
+        HypeTrainContributionType::Other => StoredContributionType::Other,
+      };
+
+      contributors.push(hype_train_contributor::ActiveModel {
+        hype_train_id: Set(hype_train.id),
+        twitch_user_id: Set(contributor.id),
+        contribution_type: Set(contribution_type),
+        total: Set(contribution.total),
+        ..Default::default()
+      });
+    }
+
+    Ok(contributors)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::DateTime;
+
+  #[tokio::test]
+  async fn new_hype_train_is_created_from_any_event() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([Vec::<hype_train::Model>::new()])
+      .append_query_results([vec![twitch_user::Model {
+        id: 1,
+        twitch_id: 578762718,
+        login_name: "fallenshadow".into(),
+        display_name: "fallenshadow".into(),
+      }]])
+      .append_query_results([Vec::<stream::Model>::new()])
+      .into_connection();
+
+    let result = MessageParser::hype_train_update(&hype_train_message(), &mock_database)
+      .await
+      .unwrap();
+
+    let expected_active_model = hype_train::ActiveModel {
+      id: ActiveValue::NotSet,
+      twitch_hype_train_id: Set("1b0AsbInCHZW2SQFQkCzqN07Ib2".into()),
+      channel_id: Set(1),
+      stream_id: Set(None),
+      level: Set(3),
+      total: Set(1500),
+      start_timestamp: Set(started_at()),
+      end_timestamp: Set(Some(
+        DateTime::parse_from_rfc3339("2025-05-08T00:20:29.532137847Z")
+          .unwrap()
+          .to_utc(),
+      )),
+    };
+
+    assert_eq!(result, expected_active_model);
+  }
+
+  #[tokio::test]
+  async fn existing_hype_train_is_updated() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([vec![hype_train::Model {
+        id: 1,
+        twitch_hype_train_id: "1b0AsbInCHZW2SQFQkCzqN07Ib2".into(),
+        channel_id: 1,
+        stream_id: Some(1),
+        level: 2,
+        total: 700,
+        start_timestamp: started_at(),
+        end_timestamp: None,
+      }]])
+      .into_connection();
+
+    let result = MessageParser::hype_train_update(&hype_train_message(), &mock_database)
+      .await
+      .unwrap();
+
+    let expected_active_model = hype_train::ActiveModel {
+      id: ActiveValue::Unchanged(1),
+      twitch_hype_train_id: ActiveValue::Unchanged("1b0AsbInCHZW2SQFQkCzqN07Ib2".into()),
+      channel_id: ActiveValue::Unchanged(1),
+      stream_id: ActiveValue::Unchanged(Some(1)),
+      level: Set(3),
+      total: Set(1500),
+      start_timestamp: ActiveValue::Unchanged(started_at()),
+      end_timestamp: Set(Some(
+        DateTime::parse_from_rfc3339("2025-05-08T00:20:29.532137847Z")
+          .unwrap()
+          .to_utc(),
+      )),
+    };
+
+    assert_eq!(result, expected_active_model);
+  }
+
+  fn started_at() -> chrono::DateTime<chrono::Utc> {
+    DateTime::parse_from_rfc3339("2025-05-08T00:02:29.532137847Z")
+      .unwrap()
+      .to_utc()
+  }
+
+  fn hype_train_message() -> TwitchHypeTrainMessage {
+    let json_data = r#"{
+  "metadata": {
+    "message_id": "734fce04-be84-b905-89e5-54a23163c6ee",
+    "message_timestamp": "2025-05-08T00:20:29.579998945Z",
+    "message_type": "notification",
+    "subscription_type": "channel.hype_train.end",
+    "subscription_version": "2"
+  },
+  "payload": {
+    "event": {
+      "id": "1b0AsbInCHZW2SQFQkCzqN07Ib2",
+      "broadcaster_user_id": "578762718",
+      "broadcaster_user_login": "fallenshadow",
+      "broadcaster_user_name": "fallenshadow",
+      "level": 3,
+      "total": 1500,
+      "top_contributions": [],
+      "started_at": "2025-05-08T00:02:29.532137847Z",
+      "ended_at": "2025-05-08T00:20:29.532137847Z",
+      "cooldown_ends_at": "2025-05-08T01:20:29.532137847Z"
+    }
+  }
+}"#;
+
+    serde_json::from_str::<TwitchHypeTrainMessage>(json_data).unwrap()
+  }
+}
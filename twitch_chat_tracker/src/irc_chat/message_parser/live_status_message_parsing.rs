@@ -1,5 +1,6 @@
 use super::MessageParser;
 use crate::errors::AppError;
+use crate::processes::post_stream_reports::generate_post_stream_reports;
 use crate::websocket_connection::twitch_objects::stream_status::{
   StreamUpdateEventType, TwitchStreamUpdateMessage,
 };
@@ -36,10 +37,12 @@ impl MessageParser<'_> {
       }
 
       StreamUpdateEventType::Offline => {
-        Self::stream_update_offline(stream_update_message, database_connection)
+        let ended_stream = Self::stream_update_offline(stream_update_message, database_connection)
           .await?
           .update(database_connection)
           .await?;
+
+        tokio::spawn(generate_post_stream_reports(ended_stream));
       }
 
       StreamUpdateEventType::Unknown => {
@@ -0,0 +1,205 @@
+use super::MessageParser;
+use crate::errors::AppError;
+use crate::irc_chat::mirrored_twitch_objects::twitch_message_type::TwitchMessageType;
+use entities::*;
+use entity_extensions::prelude::*;
+use entity_extensions::stream_message::StreamMessageExtensions;
+use irc::client::prelude::*;
+use sea_orm::*;
+
+impl MessageParser<'_> {
+  /// Stores the deletion, then marks the deleted message if it was stored.
+  pub async fn parse_message_deletion(
+    &self,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), AppError> {
+    let message_deletion = self
+      .get_message_deletion(database_connection)
+      .await?
+      .insert(database_connection)
+      .await?;
+
+    if let Some(target_origin_id) = &message_deletion.target_origin_id {
+      stream_message::Model::mark_deleted_by_origin_id(
+        target_origin_id,
+        message_deletion.timestamp,
+        database_connection,
+      )
+      .await?;
+    }
+
+    Ok(())
+  }
+
+  /// Stores the chat clear, then marks every message sent so far in the active stream.
+  ///
+  /// If the channel isn't live, only the clear itself is stored.
+  pub async fn parse_chat_clear(
+    &self,
+    database_connection: &DatabaseConnection,
+  ) -> Result<(), AppError> {
+    let chat_clear = self
+      .get_message_deletion(database_connection)
+      .await?
+      .insert(database_connection)
+      .await?;
+
+    if let Some(stream_id) = chat_clear.stream_id {
+      stream_message::Model::mark_stream_messages_deleted(
+        stream_id,
+        chat_clear.timestamp,
+        database_connection,
+      )
+      .await?;
+    }
+
+    Ok(())
+  }
+
+  async fn get_message_deletion(
+    &self,
+    database_connection: &DatabaseConnection,
+  ) -> Result<message_deletion::ActiveModel, AppError> {
+    let message_type = self.message.message_type();
+
+    if !matches!(
+      message_type,
+      TwitchMessageType::MessageDeletion | TwitchMessageType::ChatClear
+    ) {
+      return Err(AppError::IncorrectMessageType {
+        expected_type: TwitchMessageType::MessageDeletion,
+        got_type: message_type,
+      });
+    }
+
+    let Some(streamer_twitch_id) = self.message.room_id() else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "room id",
+        location: "message deletion parsing",
+      });
+    };
+    let streamer =
+      twitch_user::Model::get_or_set_by_twitch_id(streamer_twitch_id, database_connection).await?;
+    let maybe_stream =
+      stream::Model::get_active_stream_for_user(&streamer, database_connection).await?;
+
+    let is_chat_clear = message_type == TwitchMessageType::ChatClear;
+    let (target_origin_id, login_name, contents) = if is_chat_clear {
+      (None, None, None)
+    } else {
+      let Some(target_origin_id) = self.message.deleted_message_id() else {
+        return Err(AppError::MissingExpectedValue {
+          expected_value_name: "target message id",
+          location: "message deletion parsing",
+        });
+      };
+      // CLEARMSG #channel :contents
+      let contents = match self.message.command() {
+        Command::Raw(_, arguments) => arguments.get(1).cloned(),
+        _ => None,
+      };
+
+      (
+        Some(target_origin_id.to_owned()),
+        self.message.login_name().map(str::to_owned),
+        contents,
+      )
+    };
+
+    Ok(message_deletion::ActiveModel {
+      timestamp: Set(*self.message.timestamp()),
+      channel_id: Set(streamer.id),
+      stream_id: Set(maybe_stream.map(|stream| stream.id)),
+      target_origin_id: Set(target_origin_id),
+      login_name: Set(login_name),
+      contents: Set(contents),
+      is_chat_clear: Set(is_chat_clear as i8),
+      ..Default::default()
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::channel::third_party_emote_list_storage::EmoteListStorage;
+  use crate::testing_helper_methods::timestamp_from_string;
+  use irc::proto::Message as IrcMessage;
+
+  fn streamer_query_results() -> Vec<Vec<twitch_user::Model>> {
+    vec![vec![twitch_user::Model {
+      id: 1,
+      twitch_id: 578762718,
+      login_name: "fallenshadow".into(),
+      display_name: "fallenshadow".into(),
+    }]]
+  }
+
+  #[tokio::test]
+  async fn clearmsg_is_parsed_as_a_message_deletion() {
+    let message: IrcMessage =
+      "@login=linkthedot;room-id=578762718;target-msg-id=159ba37c-c6aa-4fdd-bc62-c5fadbab0770;tmi-sent-ts=1740956922774 :tmi.twitch.tv CLEARMSG #fallenshadow :waaa\r\n"
+        .parse()
+        .unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results(streamer_query_results())
+      .append_query_results([Vec::<stream::Model>::new()])
+      .into_connection();
+    let third_party_emote_storage = EmoteListStorage::test_list().unwrap();
+    let message_parser = MessageParser::new(&message, &third_party_emote_storage)
+      .unwrap()
+      .unwrap();
+
+    let result = message_parser
+      .get_message_deletion(&mock_database)
+      .await
+      .unwrap();
+
+    let expected_active_model = message_deletion::ActiveModel {
+      id: ActiveValue::NotSet,
+      timestamp: Set(timestamp_from_string("1740956922774")),
+      channel_id: Set(1),
+      stream_id: Set(None),
+      target_origin_id: Set(Some("159ba37c-c6aa-4fdd-bc62-c5fadbab0770".into())),
+      login_name: Set(Some("linkthedot".into())),
+      contents: Set(Some("waaa".into())),
+      is_chat_clear: Set(0_i8),
+    };
+
+    assert_eq!(result, expected_active_model);
+  }
+
+  #[tokio::test]
+  async fn clearchat_without_a_target_is_parsed_as_a_chat_clear() {
+    let message: IrcMessage =
+      "@room-id=578762718;tmi-sent-ts=1740956922774 :tmi.twitch.tv CLEARCHAT #fallenshadow\r\n"
+        .parse()
+        .unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results(streamer_query_results())
+      .append_query_results([Vec::<stream::Model>::new()])
+      .into_connection();
+    let third_party_emote_storage = EmoteListStorage::test_list().unwrap();
+    let message_parser = MessageParser::new(&message, &third_party_emote_storage)
+      .unwrap()
+      .unwrap();
+
+    let result = message_parser
+      .get_message_deletion(&mock_database)
+      .await
+      .unwrap();
+
+    let expected_active_model = message_deletion::ActiveModel {
+      id: ActiveValue::NotSet,
+      timestamp: Set(timestamp_from_string("1740956922774")),
+      channel_id: Set(1),
+      stream_id: Set(None),
+      target_origin_id: Set(None),
+      login_name: Set(None),
+      contents: Set(None),
+      is_chat_clear: Set(1_i8),
+    };
+
+    assert_eq!(result, expected_active_model);
+  }
+}
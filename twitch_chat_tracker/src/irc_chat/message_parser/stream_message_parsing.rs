@@ -79,7 +79,7 @@ impl<'a> MessageParser<'a> {
       channel_id: Set(streamer_twitch_user_model.id),
       stream_id: Set(maybe_stream.map(|stream| stream.id)),
       is_subscriber: Set(self.message.is_subscriber() as i8),
-      origin_id: Set(self.message.message_source_id().map(str::to_owned)),
+      origin_id: Set(self.message.origin_id().map(str::to_owned)),
       ..Default::default()
     };
 
@@ -208,6 +208,7 @@ mod tests {
         stream_id: None,
         is_subscriber: 1_i8,
         origin_id: Some("159ba37c-c6aa-4fdd-bc62-c5fadbab0770".into()),
+        deleted_at: None,
       }]])
       .append_exec_results([MockExecResult {
         last_insert_id: 1,
@@ -1,7 +1,6 @@
 use super::MessageParser;
 use crate::errors::AppError;
-use crate::irc_chat::message_parser::streamlabs_donation::StreamlabsDonation;
-use crate::irc_chat::mirrored_twitch_objects::message::TwitchIrcMessage;
+use crate::irc_chat::message_parser::donation_bots::DonationBots;
 use crate::irc_chat::mirrored_twitch_objects::twitch_message_type::TwitchMessageType;
 use entities::sea_orm_active_enums::EventType;
 use entities::*;
@@ -28,21 +27,21 @@ impl MessageParser<'_> {
       });
     };
 
-    if user_id != TwitchIrcMessage::STREAMELEMENTS_TWITCH_ID {
-      return Err(AppError::IncorrectUserWhenParsingStreamlabsDonation {
-        got_user: user_id.to_string(),
-      });
-    }
-
-    let Command::PRIVMSG(_, message_contents) = &self.message.command() else {
+    let Command::PRIVMSG(channel, message_contents) = &self.message.command() else {
       return Err(AppError::IncorrectCommandWhenParsingMessage {
         location: "streamlabs parser",
         command_string: format!("{:?}", self.message.command()),
       });
     };
+    let donation_bots = DonationBots::get();
 
-    let Some(parsed_donation_contents) =
-      StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(message_contents)
+    if !donation_bots.is_donation_bot(channel, user_id) {
+      return Err(AppError::IncorrectUserWhenParsingStreamlabsDonation {
+        got_user: user_id.to_string(),
+      });
+    }
+
+    let Some(parsed_donation_contents) = donation_bots.parse(channel, user_id, message_contents)
     else {
       return Err(AppError::FailedToParseValue {
         value_name: "donation contents",
@@ -91,11 +90,14 @@ impl MessageParser<'_> {
     let donation_event = donation_event::ActiveModel {
       event_type: Set(EventType::StreamlabsDonation),
       amount: Set(parsed_donation_contents.amount),
+      currency: Set(Some(parsed_donation_contents.currency.to_owned())),
+      original_amount: Set(Some(parsed_donation_contents.amount)),
       timestamp: Set(*self.message.timestamp()),
       donator_twitch_user_id: Set(donator.map(|donator| donator.id)),
       unknown_user_id: Set(unknown_user.map(|user| user.id)),
       donation_receiver_twitch_user_id: Set(streamer_model.id),
       stream_id: Set(maybe_stream.map(|stream| stream.id)),
+      origin_id: Set(self.message.origin_id().map(str::to_owned)),
       source_id: Set(self.message.message_source_id().map(str::to_owned)),
       ..Default::default()
     };
@@ -112,6 +114,7 @@ mod tests {
   use irc::proto::message::Tag as IrcTag;
   use irc::proto::Message as IrcMessage;
   use irc::proto::{Command, Prefix};
+  use sea_orm::prelude::Decimal;
 
   #[tokio::test]
   async fn parse_streamlabs_donation_expected_value() {
@@ -130,15 +133,18 @@ mod tests {
     let expected_active_model = donation_event::ActiveModel {
       id: ActiveValue::NotSet,
       event_type: Set(EventType::StreamlabsDonation),
-      amount: Set(143.0_f32),
+      amount: Set(Decimal::from(143)),
       timestamp: Set(timestamp_from_string("1740956922774")),
       donator_twitch_user_id: Set(Some(3)),
       donation_receiver_twitch_user_id: Set(1),
       stream_id: Set(None),
       subscription_tier: ActiveValue::NotSet,
       unknown_user_id: Set(None),
-      origin_id: ActiveValue::NotSet,
+      origin_id: Set(Some("8f0c8a53-5e4b-4d0a-b8e4-7c2f9d6a1e35".into())),
       source_id: Set(None),
+      hype_train_id: ActiveValue::NotSet,
+      currency: Set(Some("GBP".into())),
+      original_amount: Set(Some(Decimal::from(143))),
     };
 
     assert_eq!(result, expected_active_model);
@@ -149,6 +155,10 @@ mod tests {
       IrcTag("room-id".into(), Some("578762718".into())),
       IrcTag("tmi-sent-ts".into(), Some("1740956922774".into())),
       IrcTag("user-id".into(), Some("100135110".into())),
+      IrcTag(
+        "id".into(),
+        Some("8f0c8a53-5e4b-4d0a-b8e4-7c2f9d6a1e35".into()),
+      ),
     ];
 
     let message = IrcMessage {
@@ -0,0 +1,57 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counters for the IRC message processing queue.
+///
+/// These are totals since startup, and are logged by the [`worker pool`](crate::irc_chat::message_worker_pool::MessageWorkerPool).
+#[derive(Debug, Default)]
+pub struct MessageQueueMetrics {
+  queued: AtomicU64,
+  processed: AtomicU64,
+  failed: AtomicU64,
+  /// Messages written to the spool, either from a full queue or an unreachable database.
+  spilled: AtomicU64,
+  /// Times a message had to wait for room in a full queue.
+  blocked: AtomicU64,
+}
+
+impl MessageQueueMetrics {
+  pub fn record_queued(&self) {
+    self.queued.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn record_processed(&self) {
+    self.processed.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn record_failed(&self) {
+    self.failed.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn record_spilled(&self) {
+    self.spilled.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn record_blocked(&self) {
+    self.blocked.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn queued(&self) -> u64 {
+    self.queued.load(Ordering::Relaxed)
+  }
+
+  pub fn processed(&self) -> u64 {
+    self.processed.load(Ordering::Relaxed)
+  }
+
+  pub fn failed(&self) -> u64 {
+    self.failed.load(Ordering::Relaxed)
+  }
+
+  pub fn spilled(&self) -> u64 {
+    self.spilled.load(Ordering::Relaxed)
+  }
+
+  pub fn blocked(&self) -> u64 {
+    self.blocked.load(Ordering::Relaxed)
+  }
+}
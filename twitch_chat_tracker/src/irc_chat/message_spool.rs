@@ -0,0 +1,168 @@
+use crate::errors::AppError;
+use irc::proto::Message as IrcMessage;
+use std::path::{Path, PathBuf};
+use tokio::{fs::OpenOptions, io::AsyncWriteExt, sync::Mutex};
+
+/// An append-only file of raw IRC lines, one message per line.
+///
+/// Used for messages that couldn't be processed when they were received.
+#[derive(Debug)]
+pub struct MessageSpool {
+  path: PathBuf,
+  /// Held while writing so lines from different tasks are never interleaved.
+  write_lock: Mutex<()>,
+}
+
+impl MessageSpool {
+  pub fn new(path: PathBuf) -> Self {
+    Self {
+      path,
+      write_lock: Mutex::new(()),
+    }
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// The file the spool is moved to while its messages are being replayed.
+  pub fn replay_path(&self) -> PathBuf {
+    let mut replay_path = self.path.clone().into_os_string();
+    replay_path.push(".replaying");
+
+    PathBuf::from(replay_path)
+  }
+
+  /// Moves the spooled messages to the [`replay file`](Self::replay_path) so new messages
+  /// can keep being appended while the old ones are replayed.
+  ///
+  /// If a previous replay was interrupted, its file is returned instead, and the spool is left alone.
+  /// Returns None if there's nothing to replay.
+  pub async fn take_for_replay(&self) -> Result<Option<PathBuf>, AppError> {
+    let replay_path = self.replay_path();
+
+    let _write_guard = self.write_lock.lock().await;
+
+    if tokio::fs::try_exists(&replay_path).await? {
+      return Ok(Some(replay_path));
+    }
+
+    match tokio::fs::metadata(&self.path).await {
+      Ok(metadata) if metadata.len() > 0 => (),
+      Ok(_) => return Ok(None),
+      Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+      Err(error) => return Err(error.into()),
+    }
+
+    tokio::fs::rename(&self.path, &replay_path).await?;
+
+    Ok(Some(replay_path))
+  }
+
+  /// Appends the raw IRC line of the message to the end of the spool file.
+  ///
+  /// The file, and any missing parent directories, are created if they don't exist.
+  pub async fn append(&self, message: &IrcMessage) -> Result<(), AppError> {
+    let raw_message = message.to_string();
+    let raw_message = raw_message.trim_end_matches(['\r', '\n']);
+
+    let _write_guard = self.write_lock.lock().await;
+
+    if let Some(parent_directory) = self.path.parent() {
+      if !parent_directory.as_os_str().is_empty() {
+        tokio::fs::create_dir_all(parent_directory).await?;
+      }
+    }
+
+    let mut spool_file = OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&self.path)
+      .await?;
+
+    spool_file
+      .write_all(format!("{raw_message}\n").as_bytes())
+      .await?;
+    spool_file.flush().await?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn appended_messages_can_be_parsed_back() {
+    let spool_path = std::env::temp_dir()
+      .join(format!(
+        "twitch_chat_tracker_spool_test_{}",
+        std::process::id()
+      ))
+      .join("irc_messages.spool");
+    let _ = tokio::fs::remove_file(&spool_path).await;
+    let message_spool = MessageSpool::new(spool_path.clone());
+
+    let first_message: IrcMessage =
+      "@room-id=578762718;user-id=128831052 :fallenshadow!fallenshadow@fallenshadow.tmi.twitch.tv PRIVMSG #fallenshadow :glorp\r\n"
+        .parse()
+        .unwrap();
+    let second_message: IrcMessage =
+      "@room-id=578762718 :tmi.twitch.tv USERNOTICE #fallenshadow :waaa\r\n"
+        .parse()
+        .unwrap();
+
+    message_spool.append(&first_message).await.unwrap();
+    message_spool.append(&second_message).await.unwrap();
+
+    let spool_contents = tokio::fs::read_to_string(&spool_path).await.unwrap();
+    let spooled_messages: Vec<IrcMessage> = spool_contents
+      .lines()
+      .map(|line| line.parse().unwrap())
+      .collect();
+
+    let _ = tokio::fs::remove_file(&spool_path).await;
+
+    assert_eq!(spooled_messages, vec![first_message, second_message]);
+  }
+
+  #[tokio::test]
+  async fn taking_for_replay_moves_the_spool_aside() {
+    let spool_path = std::env::temp_dir()
+      .join(format!(
+        "twitch_chat_tracker_spool_test_{}",
+        std::process::id()
+      ))
+      .join("replay.spool");
+    let message_spool = MessageSpool::new(spool_path.clone());
+    let _ = tokio::fs::remove_file(&spool_path).await;
+    let _ = tokio::fs::remove_file(message_spool.replay_path()).await;
+
+    let message: IrcMessage =
+      ":fallenshadow!fallenshadow@fallenshadow.tmi.twitch.tv PRIVMSG #fallenshadow :glorp\r\n"
+        .parse()
+        .unwrap();
+
+    let empty_result = message_spool.take_for_replay().await.unwrap();
+
+    message_spool.append(&message).await.unwrap();
+    let replay_path = message_spool.take_for_replay().await.unwrap();
+    let spool_exists_after_take = tokio::fs::try_exists(&spool_path).await.unwrap();
+
+    message_spool.append(&message).await.unwrap();
+    let interrupted_replay_path = message_spool.take_for_replay().await.unwrap();
+    let replay_contents = tokio::fs::read_to_string(message_spool.replay_path())
+      .await
+      .unwrap();
+
+    let _ = tokio::fs::remove_file(&spool_path).await;
+    let _ = tokio::fs::remove_file(message_spool.replay_path()).await;
+
+    assert!(empty_result.is_none());
+    assert_eq!(replay_path, Some(message_spool.replay_path()));
+    assert!(!spool_exists_after_take);
+    assert_eq!(interrupted_replay_path, Some(message_spool.replay_path()));
+    assert_eq!(replay_contents.lines().count(), 1);
+  }
+}
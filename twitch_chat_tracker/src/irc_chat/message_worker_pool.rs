@@ -0,0 +1,411 @@
+use crate::channel::third_party_emote_list_storage::EmoteListStorage;
+use crate::errors::AppError;
+use crate::irc_chat::{
+  message_queue_metrics::MessageQueueMetrics, message_spool::MessageSpool, twitch_irc::TwitchIrc,
+};
+use crate::processes::replay_message_spool;
+use app_config::{queue_full_policy::QueueFullPolicy, AppConfig};
+use irc::client::prelude::Command;
+use irc::proto::Message as IrcMessage;
+use std::future::Future;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+/// How often the queue depth and message counts are logged.
+const METRICS_REPORT_INTERVAL: Duration = Duration::new(60, 0);
+
+/// A fixed set of workers that parse IRC messages, each with its own bounded queue.
+///
+/// Messages are routed to a worker by channel, so every message from a channel is parsed in the order it was received.
+/// What happens when a worker's queue is full is decided by the [`queue full policy`](QueueFullPolicy).
+///
+/// Messages that fail because the database can't be reached are written to the spool,
+/// and replayed by [`replay_message_spool`] once it's back.
+pub struct MessageWorkerPool {
+  worker_senders: Vec<mpsc::Sender<IrcMessage>>,
+  queue_full_policy: QueueFullPolicy,
+  message_spool: Arc<MessageSpool>,
+  metrics: Arc<MessageQueueMetrics>,
+}
+
+impl MessageWorkerPool {
+  /// Spawns the workers, the metrics reporter, and the spool replayer as configured in the [`app config`](AppConfig).
+  pub fn new(third_party_emote_lists: Arc<EmoteListStorage>) -> Self {
+    let worker_count = AppConfig::message_worker_count().max(1);
+    let queue_capacity = AppConfig::message_queue_capacity().max(1);
+    let metrics = Arc::new(MessageQueueMetrics::default());
+    let message_spool = Arc::new(MessageSpool::new(
+      AppConfig::message_spool_path().to_owned(),
+    ));
+
+    tracing::info!(
+      "Starting {} message workers with a queue capacity of {} each.",
+      worker_count,
+      queue_capacity
+    );
+
+    let worker_senders: Vec<mpsc::Sender<IrcMessage>> = (0..worker_count)
+      .map(|worker_id| {
+        let (sender, receiver) = mpsc::channel(queue_capacity);
+        let third_party_emote_lists = third_party_emote_lists.clone();
+
+        tokio::spawn(run_worker(
+          worker_id,
+          receiver,
+          move |message| {
+            TwitchIrc::create_and_run_mesage_parser(message, third_party_emote_lists.clone())
+          },
+          message_spool.clone(),
+          metrics.clone(),
+        ));
+
+        sender
+      })
+      .collect();
+
+    tokio::spawn(report_metrics(worker_senders.clone(), metrics.clone()));
+    tokio::spawn(replay_message_spool(
+      message_spool.clone(),
+      third_party_emote_lists,
+    ));
+
+    Self {
+      worker_senders,
+      queue_full_policy: AppConfig::message_queue_full_policy(),
+      message_spool,
+      metrics,
+    }
+  }
+
+  /// Sends the message to the queue of the worker responsible for its channel.
+  ///
+  /// If that queue is full, the message either waits for room, or is written to the spool file,
+  /// depending on the configured [`queue full policy`](QueueFullPolicy).
+  ///
+  /// Note that blocking here also stops the IRC client from being read, which includes responding to PINGs.
+  pub async fn queue_message(&self, message: IrcMessage) -> Result<(), AppError> {
+    let worker_sender = &self.worker_senders[self.worker_index(&message)];
+
+    match worker_sender.try_send(message) {
+      Ok(()) => (),
+
+      Err(TrySendError::Full(message)) => match self.queue_full_policy {
+        QueueFullPolicy::Block => {
+          self.metrics.record_blocked();
+
+          worker_sender
+            .send(message)
+            .await
+            .map_err(|error| AppError::MpscConnectionClosed {
+              error: error.to_string(),
+            })?;
+        }
+        QueueFullPolicy::Spill => {
+          self.message_spool.append(&message).await?;
+          self.metrics.record_spilled();
+
+          return Ok(());
+        }
+      },
+
+      Err(TrySendError::Closed(message)) => {
+        return Err(AppError::MpscConnectionClosed {
+          error: format!("Worker queue closed. Dropped message: {:?}", message),
+        });
+      }
+    }
+
+    self.metrics.record_queued();
+
+    Ok(())
+  }
+
+  pub fn metrics(&self) -> &MessageQueueMetrics {
+    &self.metrics
+  }
+
+  pub fn message_spool(&self) -> &Arc<MessageSpool> {
+    &self.message_spool
+  }
+
+  /// Messages without a channel all go to the first worker.
+  fn worker_index(&self, message: &IrcMessage) -> usize {
+    let Some(channel) = message_channel(message) else {
+      return 0;
+    };
+    let mut hasher = DefaultHasher::new();
+    channel.hash(&mut hasher);
+
+    (hasher.finish() % self.worker_senders.len() as u64) as usize
+  }
+}
+
+/// Returns the channel a message was sent in, if it has one.
+fn message_channel(message: &IrcMessage) -> Option<&str> {
+  match &message.command {
+    Command::PRIVMSG(target, _) => Some(target),
+    Command::Raw(_, arguments) => arguments
+      .first()
+      .map(String::as_str)
+      .filter(|argument| argument.starts_with('#')),
+    _ => None,
+  }
+}
+
+/// Handles each message in the queue in order.
+///
+/// Every message is handled in its own task, so a panic while parsing one is counted as a failed message
+/// instead of taking the worker down with it.
+async fn run_worker<F, Fut>(
+  worker_id: usize,
+  mut receiver: mpsc::Receiver<IrcMessage>,
+  message_handler: F,
+  message_spool: Arc<MessageSpool>,
+  metrics: Arc<MessageQueueMetrics>,
+) where
+  F: Fn(IrcMessage) -> Fut,
+  Fut: Future<Output = Result<(), AppError>> + Send + 'static,
+{
+  tracing::info!("Running message worker {}.", worker_id);
+
+  while let Some(message) = receiver.recv().await {
+    let result = match tokio::spawn(message_handler(message.clone())).await {
+      Ok(result) => result,
+      Err(join_error) => {
+        metrics.record_failed();
+
+        tracing::error!(
+          "Message worker {} failed to parse a message. Reason: {}. Message: {:?}",
+          worker_id,
+          join_error,
+          message
+        );
+
+        continue;
+      }
+    };
+
+    match result {
+      Ok(()) => metrics.record_processed(),
+      Err(error) if error.is_database_unavailable() => {
+        tracing::warn!(
+          "The database is unavailable. Spooling the message for later. Reason: {}",
+          error
+        );
+
+        if let Err(spool_error) = message_spool.append(&message).await {
+          metrics.record_failed();
+
+          tracing::error!(
+            "Failed to spool a message. Dropping it. Reason: {}. Message: {:?}",
+            spool_error,
+            message
+          );
+        } else {
+          metrics.record_spilled();
+        }
+      }
+      Err(error) => {
+        metrics.record_failed();
+
+        tracing::error!("Failed to parse a message from the IRC client: {}", error);
+      }
+    }
+  }
+
+  tracing::error!("Message worker {} has lost its queue. Exiting.", worker_id);
+
+  // In the event where the connection fails, it's best to exit the program.
+  std::process::exit(1)
+}
+
+async fn report_metrics(
+  worker_senders: Vec<mpsc::Sender<IrcMessage>>,
+  metrics: Arc<MessageQueueMetrics>,
+) {
+  let mut report_timer = tokio::time::interval(METRICS_REPORT_INTERVAL);
+
+  loop {
+    report_timer.tick().await;
+
+    let queue_depths: Vec<usize> = worker_senders
+      .iter()
+      .map(|sender| sender.max_capacity() - sender.capacity())
+      .collect();
+
+    tracing::info!(
+      "Message queue depths: {:?} | Total queued: {} | Processed: {} | Failed: {} | Spilled: {} | Blocked: {}",
+      queue_depths,
+      metrics.queued(),
+      metrics.processed(),
+      metrics.failed(),
+      metrics.spilled(),
+      metrics.blocked()
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn privmsg(channel: &str, contents: &str) -> IrcMessage {
+    format!(
+      ":fallenshadow!fallenshadow@fallenshadow.tmi.twitch.tv PRIVMSG {channel} :{contents}\r\n"
+    )
+    .parse()
+    .unwrap()
+  }
+
+  fn get_pool_template(
+    worker_count: usize,
+    queue_capacity: usize,
+    queue_full_policy: QueueFullPolicy,
+    spool_name: &str,
+  ) -> (MessageWorkerPool, Vec<mpsc::Receiver<IrcMessage>>) {
+    let (worker_senders, receivers) = (0..worker_count)
+      .map(|_| mpsc::channel(queue_capacity))
+      .unzip();
+    let spool_path = std::env::temp_dir()
+      .join(format!(
+        "twitch_chat_tracker_pool_test_{}",
+        std::process::id()
+      ))
+      .join(spool_name);
+
+    let pool = MessageWorkerPool {
+      worker_senders,
+      queue_full_policy,
+      message_spool: Arc::new(MessageSpool::new(spool_path)),
+      metrics: Arc::new(MessageQueueMetrics::default()),
+    };
+
+    (pool, receivers)
+  }
+
+  #[test]
+  fn messages_from_the_same_channel_go_to_the_same_worker() {
+    let (pool, _receivers) = get_pool_template(8, 1, QueueFullPolicy::Block, "routing.spool");
+
+    let first_index = pool.worker_index(&privmsg("#fallenshadow", "glorp"));
+    let second_index = pool.worker_index(&privmsg("#fallenshadow", "waaa"));
+    let ping_index = pool.worker_index(&"PING :tmi.twitch.tv\r\n".parse().unwrap());
+
+    assert_eq!(first_index, second_index);
+    assert_eq!(ping_index, 0);
+  }
+
+  #[tokio::test]
+  async fn full_queue_spills_to_disk() {
+    let (pool, mut receivers) = get_pool_template(1, 1, QueueFullPolicy::Spill, "full_queue.spool");
+    let _ = tokio::fs::remove_file(pool.message_spool().path()).await;
+
+    pool
+      .queue_message(privmsg("#fallenshadow", "glorp"))
+      .await
+      .unwrap();
+    pool
+      .queue_message(privmsg("#fallenshadow", "waaa"))
+      .await
+      .unwrap();
+    pool
+      .queue_message(privmsg("#fallenshadow", "glorpass"))
+      .await
+      .unwrap();
+
+    let spool_contents = tokio::fs::read_to_string(pool.message_spool().path())
+      .await
+      .unwrap();
+    let _ = tokio::fs::remove_file(pool.message_spool().path()).await;
+
+    assert_eq!(pool.metrics().queued(), 1);
+    assert_eq!(pool.metrics().spilled(), 2);
+    assert_eq!(spool_contents.lines().count(), 2);
+    assert_eq!(
+      receivers[0].recv().await.unwrap(),
+      privmsg("#fallenshadow", "glorp")
+    );
+  }
+
+  #[tokio::test]
+  async fn workers_keep_running_after_a_parser_panics() {
+    let (sender, receiver) = mpsc::channel(4);
+    let metrics = Arc::new(MessageQueueMetrics::default());
+    let spool_path = std::env::temp_dir()
+      .join(format!(
+        "twitch_chat_tracker_pool_test_{}",
+        std::process::id()
+      ))
+      .join("panic.spool");
+
+    tokio::spawn(run_worker(
+      0,
+      receiver,
+      |message: IrcMessage| async move {
+        if let Command::PRIVMSG(_, contents) = &message.command {
+          if contents == "panic" {
+            panic!("Failed to parse the message.");
+          }
+        }
+
+        Ok(())
+      },
+      Arc::new(MessageSpool::new(spool_path)),
+      metrics.clone(),
+    ));
+
+    sender
+      .send(privmsg("#fallenshadow", "panic"))
+      .await
+      .unwrap();
+    sender
+      .send(privmsg("#fallenshadow", "glorp"))
+      .await
+      .unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+      while metrics.processed() == 0 {
+        tokio::task::yield_now().await;
+      }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(metrics.failed(), 1);
+    assert_eq!(metrics.processed(), 1);
+    assert!(!sender.is_closed());
+
+    // Dropping the sender would close the worker's queue, which exits the process.
+    std::mem::forget(sender);
+  }
+
+  #[tokio::test]
+  async fn full_queue_blocks_until_there_is_room() {
+    let (pool, mut receivers) = get_pool_template(1, 1, QueueFullPolicy::Block, "blocked.spool");
+
+    pool
+      .queue_message(privmsg("#fallenshadow", "glorp"))
+      .await
+      .unwrap();
+
+    let mut receiver = receivers.remove(0);
+    let consumer = tokio::spawn(async move {
+      let first = receiver.recv().await.unwrap();
+      let second = receiver.recv().await.unwrap();
+
+      (first, second)
+    });
+
+    pool
+      .queue_message(privmsg("#fallenshadow", "waaa"))
+      .await
+      .unwrap();
+
+    let (first, second) = consumer.await.unwrap();
+
+    assert_eq!(first, privmsg("#fallenshadow", "glorp"));
+    assert_eq!(second, privmsg("#fallenshadow", "waaa"));
+    assert_eq!(pool.metrics().spilled(), 0);
+  }
+}
@@ -1,9 +1,10 @@
 use super::twitch_message_type::TwitchMessageType;
-use crate::irc_chat::message_parser::streamlabs_donation::StreamlabsDonation;
+use crate::irc_chat::message_parser::donation_bots::DonationBots;
 use crate::irc_chat::mirrored_twitch_objects::tag_values::TwitchIrcTagValues;
 use crate::{errors::AppError, irc_chat::sub_tier::SubTier};
 use chrono::{DateTime, Utc};
 use irc::proto::{Command, Message as IrcMessage};
+use std::collections::BTreeMap;
 
 #[derive(Debug)]
 pub struct TwitchIrcMessage {
@@ -14,8 +15,8 @@ pub struct TwitchIrcMessage {
 }
 
 impl TwitchIrcMessage {
+  /// The bot used for channels without any configured donation bots.
   pub const STREAMELEMENTS_TWITCH_ID: &str = "100135110";
-  pub const IGNORED_MESSAGE_IDS: &[&str] = &["bitsbadgetier", "announcement", "viewermilestone"];
 
   pub fn new(message: &IrcMessage) -> Result<Option<Self>, AppError> {
     let Some(mut tags) = TwitchIrcTagValues::new(message)? else {
@@ -25,7 +26,6 @@ impl TwitchIrcMessage {
     let is_shared_chat = tags.replace_values_for_sharedchat_message();
 
     let message_type = match Self::calculate_message_type(&tags, message) {
-      Some(TwitchMessageType::Ignored) => return Ok(None), // Ignored message.
       Some(message_type) => message_type,
       _ => {
         return Err(AppError::FailedToParseValue {
@@ -47,26 +47,27 @@ impl TwitchIrcMessage {
   /// Determines the type of message that was received (timeout, bits, gift sub, user message, etc.);
   ///
   /// If the message could not be parsed, None is returned.
-  /// If the message was an [`ignored tag`](Self::IGNORED_MESSAGE_IDS), Some(None) is returned.
   ///
-  /// Otherwise Some(Some([`TwitchMessageType`](TwitchMessageType))) is returned.
+  /// Otherwise Some([`TwitchMessageType`](TwitchMessageType)) is returned.
   fn calculate_message_type(
     tags: &TwitchIrcTagValues,
     message: &IrcMessage,
   ) -> Option<TwitchMessageType> {
-    if let Some(message_id) = tags.message_id() {
-      if Self::IGNORED_MESSAGE_IDS.contains(&message_id) {
-        return Some(TwitchMessageType::Ignored);
-      }
-    }
-
     let result = match () {
       _ if Self::is_timeout(tags) => TwitchMessageType::Timeout,
+      _ if Self::is_message_deletion(tags, message) => TwitchMessageType::MessageDeletion,
+      _ if Self::is_chat_clear(tags, message) => TwitchMessageType::ChatClear,
       _ if Self::is_subscription(tags) => TwitchMessageType::Subscription,
       _ if Self::is_gift_sub(tags) => TwitchMessageType::GiftSub,
+      _ if Self::is_hype_chat(tags) => TwitchMessageType::HypeChat,
       _ if Self::is_bits(tags) => TwitchMessageType::Bits,
+      _ if Self::is_bits_power_up(tags) => TwitchMessageType::BitsPowerUp,
+      _ if Self::is_channel_point_redemption(tags, message) => {
+        TwitchMessageType::ChannelPointRedemption
+      }
       _ if Self::is_streamlabs_donation(tags, message) => TwitchMessageType::StreamlabsDonation,
       _ if Self::is_raid(tags) => TwitchMessageType::Raid,
+      _ if Self::is_channel_notice(tags) => TwitchMessageType::ChannelNotice,
       _ if Self::is_user_message(tags, message) => TwitchMessageType::UserMessage,
       _ => return None,
     };
@@ -78,6 +79,18 @@ impl TwitchIrcMessage {
     tags.timedout_user_id().is_some()
   }
 
+  fn is_message_deletion(tags: &TwitchIrcTagValues, message: &IrcMessage) -> bool {
+    matches!(&message.command, Command::Raw(command, _) if command == "CLEARMSG")
+      && tags.target_message_id().is_some()
+  }
+
+  /// A `CLEARCHAT` without a target user clears the whole chat.
+  /// With one it's a timeout or ban, which is checked by [`is_timeout`](Self::is_timeout).
+  fn is_chat_clear(tags: &TwitchIrcTagValues, message: &IrcMessage) -> bool {
+    matches!(&message.command, Command::Raw(command, _) if command == "CLEARCHAT")
+      && tags.timedout_user_id().is_none()
+  }
+
   fn is_subscription(tags: &TwitchIrcTagValues) -> bool {
     let Some(message_id) = tags.message_id() else {
       return false;
@@ -94,30 +107,57 @@ impl TwitchIrcMessage {
     TwitchIrcTagValues::GIFT_SUB_TAG_MSG_IDS.contains(&message_id)
   }
 
+  fn is_hype_chat(tags: &TwitchIrcTagValues) -> bool {
+    tags.hype_chat_amount().is_some()
+  }
+
   fn is_bits(tags: &TwitchIrcTagValues) -> bool {
     tags.bits().is_some()
   }
 
-  fn is_streamlabs_donation(tags: &TwitchIrcTagValues, message: &IrcMessage) -> bool {
-    let Some(user_id) = tags.user_id() else {
+  fn is_bits_power_up(tags: &TwitchIrcTagValues) -> bool {
+    let Some(message_id) = tags.message_id() else {
       return false;
     };
 
-    if user_id != Self::STREAMELEMENTS_TWITCH_ID {
-      return false;
-    }
+    message_id == TwitchIrcTagValues::GIGANTIFIED_EMOTE_MSG_ID
+      || message_id == TwitchIrcTagValues::MESSAGE_EFFECT_MSG_ID
+  }
+
+  /// Only redemptions with a message are sent through IRC.
+  fn is_channel_point_redemption(tags: &TwitchIrcTagValues, message: &IrcMessage) -> bool {
+    let is_redemption = tags.custom_reward_id().is_some()
+      || tags.message_id() == Some(TwitchIrcTagValues::HIGHLIGHTED_MESSAGE_MSG_ID);
+
+    is_redemption && matches!(message.command, Command::PRIVMSG(_, _))
+  }
 
-    let Command::PRIVMSG(_, contents) = &message.command else {
+  /// True if the message is an alert from one of the channel's [`donation bots`](DonationBots).
+  fn is_streamlabs_donation(tags: &TwitchIrcTagValues, message: &IrcMessage) -> bool {
+    let Some(user_id) = tags.user_id() else {
+      return false;
+    };
+    let Command::PRIVMSG(channel, contents) = &message.command else {
       return false;
     };
 
-    StreamlabsDonation::parse_streamlabs_donation_value_from_message_content(contents).is_some()
+    DonationBots::get()
+      .parse(channel, user_id, contents)
+      .is_some()
   }
 
   fn is_raid(tags: &TwitchIrcTagValues) -> bool {
     tags.message_id() == Some(TwitchIrcTagValues::RAID_TAG_MSG_ID)
   }
 
+  fn is_channel_notice(tags: &TwitchIrcTagValues) -> bool {
+    let Some(message_id) = tags.message_id() else {
+      return false;
+    };
+
+    TwitchIrcTagValues::CHANNEL_NOTICE_MSG_IDS.contains(&message_id)
+  }
+
   /// This should be checked last out of the list because true will be
   /// returned in most cases where the message was something else.
   fn is_user_message(tags: &TwitchIrcTagValues, message: &IrcMessage) -> bool {
@@ -128,6 +168,9 @@ impl TwitchIrcMessage {
     [
       TwitchMessageType::UserMessage,
       TwitchMessageType::Bits,
+      TwitchMessageType::HypeChat,
+      TwitchMessageType::BitsPowerUp,
+      TwitchMessageType::ChannelPointRedemption,
       TwitchMessageType::StreamlabsDonation,
     ]
     .contains(&self.message_type)
@@ -155,6 +198,30 @@ impl TwitchIrcMessage {
     self.tags.message_source_id()
   }
 
+  /// The ID that identifies this message across channels.
+  ///
+  /// This is the source ID for shared chat messages, and the message's own ID otherwise.
+  pub fn origin_id(&self) -> Option<&str> {
+    self
+      .tags
+      .message_source_id()
+      .or(self.tags.unique_message_id())
+  }
+
+  /// The ID of the message removed by a `CLEARMSG`.
+  pub fn deleted_message_id(&self) -> Option<&str> {
+    self.tags.target_message_id()
+  }
+
+  pub fn custom_reward_id(&self) -> Option<&str> {
+    self.tags.custom_reward_id()
+  }
+
+  /// Returns true if the message was sent with the `Highlight My Message` channel point reward.
+  pub fn is_highlighted_message(&self) -> bool {
+    self.tags.message_id() == Some(TwitchIrcTagValues::HIGHLIGHTED_MESSAGE_MSG_ID)
+  }
+
   pub fn login_name(&self) -> Option<&str> {
     self.tags.login_name()
   }
@@ -183,6 +250,22 @@ impl TwitchIrcMessage {
     self.tags.bits()
   }
 
+  pub fn hype_chat_amount(&self) -> Option<&str> {
+    self.tags.hype_chat_amount()
+  }
+
+  pub fn hype_chat_currency(&self) -> Option<&str> {
+    self.tags.hype_chat_currency()
+  }
+
+  pub fn hype_chat_exponent(&self) -> Option<&str> {
+    self.tags.hype_chat_exponent()
+  }
+
+  pub fn animation_id(&self) -> Option<&str> {
+    self.tags.animation_id()
+  }
+
   pub fn is_first_message(&self) -> bool {
     self.tags.first_message().unwrap_or("0") == "1"
   }
@@ -242,4 +325,8 @@ impl TwitchIrcMessage {
   pub fn gift_sub_origin_id(&self) -> Option<&str> {
     self.tags.gift_sub_origin_id()
   }
+
+  pub fn message_parameters(&self) -> &BTreeMap<String, String> {
+    self.tags.message_parameters()
+  }
 }
@@ -2,7 +2,7 @@ use crate::{errors::AppError, irc_chat::sub_tier::SubTier};
 use chrono::{DateTime, TimeZone, Utc};
 use irc::proto::Message as IrcMessage;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Deserialize, Default)]
 pub struct TwitchIrcTagValues {
@@ -38,6 +38,26 @@ pub struct TwitchIrcTagValues {
   #[serde(rename = "bits")]
   bits: Option<String>,
 
+  /// Comes with Hype Chats. The amount paid in the currency's smallest unit.
+  #[serde(rename = "pinned-chat-paid-amount")]
+  hype_chat_amount: Option<String>,
+
+  /// Comes with Hype Chats. The ISO 4217 code of the currency paid in.
+  #[serde(rename = "pinned-chat-paid-currency")]
+  hype_chat_currency: Option<String>,
+
+  /// Comes with Hype Chats. How many decimal places the amount is shifted by.
+  #[serde(rename = "pinned-chat-paid-exponent")]
+  hype_chat_exponent: Option<String>,
+
+  /// Comes with Hype Chats. The tier of the Hype Chat, `ONE` through `TEN`.
+  #[serde(rename = "pinned-chat-paid-level")]
+  hype_chat_level: Option<String>,
+
+  /// Comes with the `animated-message` message id. The effect the message was sent with.
+  #[serde(rename = "animation-id")]
+  animation_id: Option<String>,
+
   #[serde(rename = "first-msg")]
   first_message: Option<String>,
 
@@ -83,11 +103,28 @@ pub struct TwitchIrcTagValues {
   #[serde(rename = "msg-param-cumulative-months")]
   months_subscribed: Option<String>,
 
+  /// The unique ID of this message.
+  #[serde(rename = "id")]
+  unique_message_id: Option<String>,
+
+  /// Comes with `CLEARMSG`. The unique ID of the message that was deleted.
+  #[serde(rename = "target-msg-id")]
+  target_message_id: Option<String>,
+
+  /// Comes with messages sent as part of a channel point reward redemption.
+  #[serde(rename = "custom-reward-id")]
+  custom_reward_id: Option<String>,
+
   /// Comes with gift subs. Unique per set.
   /// Because Twitch sends a message for each person that received a gift sub, this is used
   /// to uniquely identify any given gift sub set.
   #[serde(rename = "msg-param-origin-id")]
   gift_sub_origin_id: Option<String>,
+
+  /// Every `msg-param-` tag, keyed without the prefix.
+  /// This value is set after initialization from the raw tags.
+  #[serde(skip_deserializing)]
+  message_parameters: BTreeMap<String, String>,
 }
 
 impl TwitchIrcTagValues {
@@ -98,6 +135,16 @@ impl TwitchIrcTagValues {
   pub const GIFT_SUB_TAG_MSG_IDS: &[&str] = &["submysterygift", "giftpaidupgrade", "subgift"];
   /// The msg-id tag that would indicate a message is a raid.
   pub const RAID_TAG_MSG_ID: &str = "raid";
+  /// The msg-id tag that would indicate bits were spent to gigantify the last emote in a message.
+  pub const GIGANTIFIED_EMOTE_MSG_ID: &str = "gigantified-emote-message";
+  /// The msg-id tag that would indicate bits were spent to send a message with an effect.
+  pub const MESSAGE_EFFECT_MSG_ID: &str = "animated-message";
+  /// The msg-id tag that would indicate a message was sent with the `Highlight My Message` channel point reward.
+  pub const HIGHLIGHTED_MESSAGE_MSG_ID: &str = "highlighted-message";
+  /// The msg-id tags that are stored as channel notices.
+  pub const CHANNEL_NOTICE_MSG_IDS: &[&str] = &["announcement", "viewermilestone", "bitsbadgetier"];
+  /// The prefix for tags holding the parameters of a USERNOTICE.
+  pub const MESSAGE_PARAMETER_TAG_PREFIX: &str = "msg-param-";
   /// The msg-id tag that would indicate a message is a shared chat message.
   pub const SHARED_CHAT_MSG_ID: &str = "sharedchatnotice";
 
@@ -126,6 +173,14 @@ impl TwitchIrcTagValues {
     let serialized_tag_map = serde_json::to_string(&tag_map)?;
 
     let mut message: Self = serde_json::from_str(&serialized_tag_map)?;
+    message.message_parameters = tag_map
+      .iter()
+      .filter_map(|(key, value)| {
+        let parameter_name = key.strip_prefix(Self::MESSAGE_PARAMETER_TAG_PREFIX)?;
+
+        Some((parameter_name.to_string(), value.to_string()))
+      })
+      .collect();
     message.set_timestamp()?;
     message.check_resub_after_giftsub();
 
@@ -187,6 +242,18 @@ impl TwitchIrcTagValues {
     self.message_source_id.as_deref()
   }
 
+  pub fn unique_message_id(&self) -> Option<&str> {
+    self.unique_message_id.as_deref()
+  }
+
+  pub fn target_message_id(&self) -> Option<&str> {
+    self.target_message_id.as_deref()
+  }
+
+  pub fn custom_reward_id(&self) -> Option<&str> {
+    self.custom_reward_id.as_deref()
+  }
+
   pub fn login_name(&self) -> Option<&str> {
     self.login_name.as_deref()
   }
@@ -226,6 +293,26 @@ impl TwitchIrcTagValues {
     self.bits.as_deref()
   }
 
+  pub fn hype_chat_amount(&self) -> Option<&str> {
+    self.hype_chat_amount.as_deref()
+  }
+
+  pub fn hype_chat_currency(&self) -> Option<&str> {
+    self.hype_chat_currency.as_deref()
+  }
+
+  pub fn hype_chat_exponent(&self) -> Option<&str> {
+    self.hype_chat_exponent.as_deref()
+  }
+
+  pub fn hype_chat_level(&self) -> Option<&str> {
+    self.hype_chat_level.as_deref()
+  }
+
+  pub fn animation_id(&self) -> Option<&str> {
+    self.animation_id.as_deref()
+  }
+
   pub fn first_message(&self) -> Option<&str> {
     self.first_message.as_deref()
   }
@@ -285,6 +372,11 @@ impl TwitchIrcTagValues {
     self.gift_sub_origin_id.as_deref()
   }
 
+  /// Every `msg-param-` tag on the message, keyed without the prefix.
+  pub fn message_parameters(&self) -> &BTreeMap<String, String> {
+    &self.message_parameters
+  }
+
   /// The original message id in the event of a shared chat.
   pub fn source_message_id(&self) -> Option<&str> {
     self.source_message_id.as_deref()
@@ -366,4 +458,22 @@ mod tests {
     assert_eq!(message.gift_sub_recipient_months_subscribed(), Some("3"));
     assert_eq!(message.gift_sub_recipient_twitch_id(), Some("1111"));
   }
+
+  #[test]
+  fn message_parameters_are_collected_without_their_prefix() {
+    let irc_message: IrcMessage =
+      "@msg-id=viewermilestone;msg-param-category=watch-streak;msg-param-value=7;tmi-sent-ts=12345 :tmi.twitch.tv USERNOTICE #fallenshadow\r\n"
+        .parse()
+        .unwrap();
+
+    let message = TwitchIrcTagValues::new(&irc_message).unwrap().unwrap();
+
+    assert_eq!(
+      message.message_parameters(),
+      &BTreeMap::from([
+        ("category".to_string(), "watch-streak".to_string()),
+        ("value".to_string(), "7".to_string()),
+      ])
+    );
+  }
 }
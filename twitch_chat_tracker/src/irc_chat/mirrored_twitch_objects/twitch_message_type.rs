@@ -1,15 +1,24 @@
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum TwitchMessageType {
   Timeout,
+  /// A single message removed by a moderator.
+  MessageDeletion,
+  /// The whole chat being cleared by a moderator.
+  ChatClear,
   Subscription,
   GiftSub,
   Bits,
+  /// Bits spent on gigantifying an emote or a message effect.
+  BitsPowerUp,
+  /// A paid message pinned to the top of chat.
+  HypeChat,
+  /// A channel point reward redeemed with a message, including highlighted messages.
+  ChannelPointRedemption,
   StreamlabsDonation,
   Raid,
+  /// Announcements, viewer milestones, and bits badge tiers.
+  ChannelNotice,
   UserMessage,
-
-  /// If the message was a tag that is ignored.
-  Ignored,
 }
 
 impl std::fmt::Display for TwitchMessageType {
@@ -1,4 +1,8 @@
+pub mod message_archive;
 pub mod message_parser;
+pub mod message_queue_metrics;
+pub mod message_spool;
+pub mod message_worker_pool;
 pub mod mirrored_twitch_objects;
 pub mod parse_results;
 pub mod sub_tier;
@@ -1,12 +1,16 @@
 use crate::channel::third_party_emote_list_storage::EmoteListStorage;
 use crate::errors::AppError;
+use crate::irc_chat::message_archive::MessageArchive;
 use crate::irc_chat::message_parser::MessageParser;
+use crate::irc_chat::message_worker_pool::MessageWorkerPool;
+use crate::processes::update_emote_lists;
 use app_config::{secret_string::Secret, AppConfig};
+use chrono::Utc;
 use database_connection::get_database_connection;
 use irc::client::{prelude::*, ClientStream};
 use irc::proto::{CapSubCommand, Message as IrcMessage};
 use std::{sync::Arc, time::Duration};
-use tokio::{sync::mpsc, task::JoinHandle, time::timeout};
+use tokio::time::timeout;
 use tokio_stream::StreamExt;
 
 const MESSAGE_WAIT_TIME: Duration = Duration::new(10, 0);
@@ -23,26 +27,30 @@ const PING_TIME: u32 = 10;
 pub struct TwitchIrc {
   irc_client: Client,
   irc_client_stream: Option<ClientStream>,
-  third_party_emote_lists: Arc<EmoteListStorage>,
-  message_result_processor_sender: mpsc::UnboundedSender<JoinHandle<Result<(), AppError>>>,
+  message_worker_pool: MessageWorkerPool,
+  /// Only exists when the [`raw archive`](AppConfig::raw_archive_enabled) is enabled.
+  message_archive: Option<MessageArchive>,
 }
 
 impl TwitchIrc {
-  pub async fn new(
-    message_result_processor_sender: mpsc::UnboundedSender<JoinHandle<Result<(), AppError>>>,
-  ) -> Result<Self, AppError> {
+  pub async fn new() -> Result<Self, AppError> {
     tracing::info!("Initializing Twitch IRC client.");
     let mut irc_client = Self::get_irc_client().await?;
     let irc_client_stream = irc_client.stream()?;
     let database_connection = get_database_connection().await;
     let third_party_emote_lists =
-      EmoteListStorage::new(AppConfig::channels(), database_connection).await?;
+      Arc::new(EmoteListStorage::new(AppConfig::channels(), database_connection).await?);
+
+    tokio::spawn(update_emote_lists(third_party_emote_lists.clone()));
+
+    let message_archive = AppConfig::raw_archive_enabled()
+      .then(|| MessageArchive::new(AppConfig::raw_archive_directory().to_owned()));
 
     Ok(Self {
       irc_client,
       irc_client_stream: Some(irc_client_stream),
-      third_party_emote_lists: Arc::new(third_party_emote_lists),
-      message_result_processor_sender,
+      message_worker_pool: MessageWorkerPool::new(third_party_emote_lists),
+      message_archive,
     })
   }
 
@@ -144,41 +152,25 @@ impl TwitchIrc {
   }
 
   async fn process_message(&mut self, message: IrcMessage) -> Result<(), AppError> {
+    if let Some(message_archive) = &self.message_archive {
+      message_archive.record(&message, Utc::now()).await;
+    }
+
     if let Command::PING(url, _) = message.command {
       self.irc_client.send_pong(url)?;
 
       return Ok(());
     };
-    let third_party_emote_lists = self.third_party_emote_lists.clone();
-
-    let process_message_future =
-      Self::create_and_run_mesage_parser(message, third_party_emote_lists);
-    let process_message_handle = tokio::spawn(process_message_future);
-
-    if let Err(error) = self
-      .message_result_processor_sender
-      .send(process_message_handle)
-    {
-      return Err(AppError::MpscConnectionClosed {
-        error: error.to_string(),
-      });
-    }
 
-    Ok(())
+    self.message_worker_pool.queue_message(message).await
   }
 
-  async fn create_and_run_mesage_parser(
+  pub async fn create_and_run_mesage_parser(
     message: IrcMessage,
     third_party_emote_lists: Arc<EmoteListStorage>,
   ) -> std::result::Result<(), AppError> {
-    match message.command {
-      Command::JOIN(_, _, _) | Command::PART(_, _) => return Ok(()),
-      Command::Response(_, _) => return Ok(()),
-      Command::Raw(command, _) if &command == "USERSTATE" => return Ok(()),
-      Command::Raw(command, _) if &command == "ROOMSTATE" => return Ok(()),
-      Command::CAP(_, _, _, _) => return Ok(()),
-      Command::PONG(ref _url, _) => return Ok(()),
-      _ => (),
+    if Self::is_ignored_command(&message) {
+      return Ok(());
     }
 
     let Some(message_parser) = MessageParser::new(&message, &third_party_emote_lists)? else {
@@ -202,6 +194,19 @@ impl TwitchIrc {
 
     result
   }
+
+  /// Returns true for commands that never contain anything to store.
+  pub fn is_ignored_command(message: &IrcMessage) -> bool {
+    match &message.command {
+      Command::JOIN(_, _, _) | Command::PART(_, _) => true,
+      Command::Response(_, _) => true,
+      Command::Raw(command, _) if command == "USERSTATE" => true,
+      Command::Raw(command, _) if command == "ROOMSTATE" => true,
+      Command::CAP(_, _, _, _) => true,
+      Command::PONG(_, _) => true,
+      _ => false,
+    }
+  }
 }
 
 #[cfg(test)]
@@ -18,8 +18,16 @@ async fn main() {
 
   tracing::info!("Tracking channels {:?}", AppConfig::channels());
 
-  let message_result_processor_sender =
-    twitch_chat_tracker::processes::create_sub_processes().await;
+  // Built up front so a misconfigured donation bot is caught before connecting.
+  if let Err(error) =
+    twitch_chat_tracker::irc_chat::message_parser::donation_bots::DonationBots::init()
+  {
+    println!("{error}");
 
-  twitch_chat_tracker::processes::run_main_process(message_result_processor_sender).await;
+    std::process::exit(1);
+  }
+
+  twitch_chat_tracker::processes::create_sub_processes().await;
+
+  twitch_chat_tracker::processes::run_main_process().await;
 }
@@ -1,18 +1,13 @@
 use crate::errors::AppError;
 use crate::irc_chat::twitch_irc::TwitchIrc;
 use std::time::Duration;
-use tokio::{sync::mpsc, task::JoinHandle};
 
 const RECONNECT_ATTEMPTS: usize = 10;
 
-pub async fn run_main_process(
-  message_result_processor_sender: mpsc::UnboundedSender<JoinHandle<Result<(), AppError>>>,
-) -> ! {
+pub async fn run_main_process() -> ! {
   tracing::info!("Starting main process.");
 
-  let mut irc_client = TwitchIrc::new(message_result_processor_sender)
-    .await
-    .unwrap();
+  let mut irc_client = TwitchIrc::new().await.unwrap();
 
   tracing::info!("Running main process.");
 
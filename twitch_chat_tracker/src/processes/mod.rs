@@ -1,10 +1,13 @@
 pub mod app_animation;
 pub mod main_process;
-pub mod message_results;
+pub mod post_stream_reports;
+pub mod replay_message_spool;
 pub mod sub_process_creation;
 pub mod update_channel_live_status;
+pub mod update_emote_lists;
 
 pub use main_process::run_main_process;
-pub use message_results::process_irc_message_results;
+pub use replay_message_spool::replay_message_spool;
 pub use sub_process_creation::create_sub_processes;
 pub use update_channel_live_status::update_channel_live_statuses;
+pub use update_emote_lists::update_emote_lists;
@@ -0,0 +1,261 @@
+use crate::errors::AppError;
+use app_config::post_stream_report_config::{PostStreamReportConfig, PostStreamReportKind};
+use app_config::AppConfig;
+use chrono::{DateTime, TimeDelta, Utc};
+use database_connection::get_database_connection;
+use entities::sea_orm_active_enums::ReportRunStatus;
+use entities::{stream, twitch_user};
+use report_generator::conditions::query_conditions_builder::AppQueryConditionsBuilder;
+use report_generator::errors::AppError as ReportGeneratorError;
+use report_generator::generated_reports::{
+  claim_report_run, finish_report_run, record_sent_reports,
+};
+use report_generator::report_formats::ReportFormat;
+use report_generator::report_sinks::configured_report_sinks;
+use report_generator::reports::report_options::ReportOptions;
+use report_generator::reports::{basic_reports, subathon_reports};
+use report_generator::upload_reports::{send_reports, SentReport};
+use sea_orm::*;
+
+/// How long after a stream ended its reports are still generated when the tracker starts back up.
+const MISSED_REPORT_WINDOW: TimeDelta = TimeDelta::days(1);
+
+/// Generates the reports for a stream that just ended, sending them to the configured report sinks.
+///
+/// Nothing is generated if the stream's channel has no entry in the
+/// [`post stream reports`](AppConfig::post_stream_reports), or if another report run already claimed the stream.
+pub async fn generate_post_stream_reports(stream: stream::Model) {
+  let database_connection = get_database_connection().await;
+  let stream_id = stream.id;
+
+  if let Err(error) = generate_and_record_reports(stream, database_connection).await {
+    tracing::error!(
+      "Failed to generate the post stream reports for stream {}. Reason: {}",
+      stream_id,
+      error
+    );
+  }
+}
+
+/// Generates the reports for each channel's latest stream if it ended recently and never had any generated.
+///
+/// Covers streams that ended while the tracker was down, or before their reports were finished.
+pub async fn generate_missed_post_stream_reports() {
+  let database_connection = get_database_connection().await;
+
+  for report_config in AppConfig::post_stream_reports() {
+    match get_latest_ended_stream(&report_config.channel, database_connection).await {
+      Ok(Some(stream)) => generate_post_stream_reports(stream).await,
+      Ok(None) => (),
+      Err(error) => tracing::error!(
+        "Failed to check for missed post stream reports for {}. Reason: {}",
+        report_config.channel,
+        error
+      ),
+    }
+  }
+}
+
+async fn generate_and_record_reports(
+  stream: stream::Model,
+  database_connection: &DatabaseConnection,
+) -> Result<(), AppError> {
+  let Some(streamer) = twitch_user::Entity::find_by_id(stream.twitch_user_id)
+    .one(database_connection)
+    .await?
+  else {
+    return Ok(());
+  };
+  let Some(report_config) =
+    get_post_stream_report_config(&streamer.login_name, AppConfig::post_stream_reports())
+  else {
+    return Ok(());
+  };
+
+  if !claim_report_run(stream.id, database_connection).await? {
+    tracing::info!(
+      "Reports for stream {} were already generated or are being generated. Skipping.",
+      stream.id
+    );
+
+    return Ok(());
+  }
+
+  tracing::info!(
+    "Generating post stream reports for {}'s stream {}.",
+    streamer.login_name,
+    stream.id
+  );
+
+  let result = generate_and_send_reports(&stream, report_config, database_connection).await;
+  let report_run_status = match result {
+    Ok(()) => ReportRunStatus::Done,
+    Err(_) => ReportRunStatus::Failed,
+  };
+
+  finish_report_run(stream.id, report_run_status, database_connection).await?;
+  result?;
+
+  tracing::info!("Finished the post stream reports for stream {}.", stream.id);
+
+  Ok(())
+}
+
+async fn generate_and_send_reports(
+  stream: &stream::Model,
+  report_config: &PostStreamReportConfig,
+  database_connection: &DatabaseConnection,
+) -> Result<(), AppError> {
+  let report_options = report_options_from_config(report_config)?;
+  let query_conditions = AppQueryConditionsBuilder::new()
+    .set_stream_id(stream.id)
+    .set_streamer_twitch_user_id(stream.twitch_user_id)
+    .build()?;
+
+  let reports = match report_config.report {
+    PostStreamReportKind::Basic => {
+      basic_reports::generate_reports(query_conditions, stream.twitch_user_id, &report_options)
+        .await?
+    }
+    PostStreamReportKind::Subathon => {
+      subathon_reports::generate_reports(query_conditions, stream.twitch_user_id, &report_options)
+        .await?
+    }
+  };
+  let sent_reports = send_reports(
+    stream,
+    &reports,
+    ReportFormat::default(),
+    &configured_report_sinks(),
+  )
+  .await?;
+
+  record_sent_reports(stream.id, &sent_reports, database_connection).await?;
+
+  if !sent_reports.iter().any(SentReport::was_sent) {
+    return Err(ReportGeneratorError::NoReportsWereSent(stream.id).into());
+  }
+
+  Ok(())
+}
+
+fn get_post_stream_report_config<'a>(
+  channel: &str,
+  report_configs: &'a [PostStreamReportConfig],
+) -> Option<&'a PostStreamReportConfig> {
+  report_configs
+    .iter()
+    .find(|report_config| report_config.channel.eq_ignore_ascii_case(channel))
+}
+
+/// Returns the channel's latest stream if it ended within the [`missed report window`](MISSED_REPORT_WINDOW).
+async fn get_latest_ended_stream(
+  channel: &str,
+  database_connection: &DatabaseConnection,
+) -> Result<Option<stream::Model>, AppError> {
+  let Some(streamer) = twitch_user::Entity::find()
+    .filter(twitch_user::Column::LoginName.eq(channel))
+    .one(database_connection)
+    .await?
+  else {
+    return Ok(None);
+  };
+  let latest_stream = stream::Entity::find()
+    .filter(stream::Column::TwitchUserId.eq(streamer.id))
+    .order_by_desc(stream::Column::Id)
+    .one(database_connection)
+    .await?;
+
+  Ok(latest_stream.filter(|stream| {
+    stream
+      .end_timestamp
+      .is_some_and(|end_timestamp| Utc::now() - end_timestamp <= MISSED_REPORT_WINDOW)
+  }))
+}
+
+fn report_options_from_config(
+  report_config: &PostStreamReportConfig,
+) -> Result<ReportOptions, AppError> {
+  let parse_time = |time: &Option<String>| {
+    time
+      .as_deref()
+      .map(DateTime::parse_from_rfc3339)
+      .transpose()
+      .map(|time| time.map(|time| time.to_utc()))
+      .map_err(|error| AppError::InvalidPostStreamReportConfig {
+        channel: report_config.channel.clone(),
+        reason: format!("Failed to parse a subathon time. {error}"),
+      })
+  };
+
+  let subathon_start_date = parse_time(&report_config.subathon_start)?;
+  let subathon_end_date = parse_time(&report_config.subathon_end)?;
+
+  if report_config.report == PostStreamReportKind::Subathon && subathon_start_date.is_none() {
+    return Err(AppError::InvalidPostStreamReportConfig {
+      channel: report_config.channel.clone(),
+      reason: "Subathon reports require a subathon start time.".into(),
+    });
+  }
+
+  Ok(ReportOptions {
+    subathon_start_date,
+    subathon_end_date,
+    ..Default::default()
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn subathon_times_are_read_from_the_config() {
+    let report_config = PostStreamReportConfig {
+      channel: "fallenshadow".into(),
+      report: PostStreamReportKind::Subathon,
+      subathon_start: Some("2025-05-08T00:00:00Z".into()),
+      subathon_end: None,
+    };
+
+    let report_options = report_options_from_config(&report_config).unwrap();
+
+    assert_eq!(
+      report_options.subathon_start_date,
+      Some(
+        DateTime::parse_from_rfc3339("2025-05-08T00:00:00Z")
+          .unwrap()
+          .to_utc()
+      )
+    );
+    assert_eq!(report_options.subathon_end_date, None);
+  }
+
+  #[test]
+  fn subathon_reports_require_a_start_time() {
+    let report_config = PostStreamReportConfig {
+      channel: "fallenshadow".into(),
+      report: PostStreamReportKind::Subathon,
+      subathon_start: None,
+      subathon_end: None,
+    };
+
+    assert!(matches!(
+      report_options_from_config(&report_config),
+      Err(AppError::InvalidPostStreamReportConfig { .. })
+    ));
+  }
+
+  #[test]
+  fn channels_are_matched_regardless_of_case() {
+    let report_configs = [PostStreamReportConfig {
+      channel: "FallenShadow".into(),
+      report: PostStreamReportKind::Basic,
+      subathon_start: None,
+      subathon_end: None,
+    }];
+
+    assert!(get_post_stream_report_config("fallenshadow", &report_configs).is_some());
+    assert!(get_post_stream_report_config("shadowchama", &report_configs).is_none());
+  }
+}
@@ -0,0 +1,601 @@
+use crate::channel::third_party_emote_list_storage::EmoteListStorage;
+use crate::errors::AppError;
+use crate::irc_chat::mirrored_twitch_objects::twitch_message_type::TwitchMessageType;
+use crate::irc_chat::{
+  message_spool::MessageSpool, mirrored_twitch_objects::message::TwitchIrcMessage,
+  twitch_irc::TwitchIrc,
+};
+use app_config::AppConfig;
+use chrono::{DateTime, Utc};
+use database_connection::get_database_connection;
+use entities::sea_orm_active_enums::EventType;
+use entities::*;
+use entity_extensions::{prelude::*, twitch_user::ChannelIdentifier};
+use irc::proto::Message as IrcMessage;
+use sea_orm::*;
+use std::{path::Path, sync::Arc, time::Duration};
+
+/// Feeds spooled IRC messages back through the message parser once the database can be reached.
+///
+/// Messages are checked against the rows they would be stored as first, so a replay that was
+/// interrupted partway through can be picked back up without storing anything twice.
+pub async fn replay_message_spool(
+  message_spool: Arc<MessageSpool>,
+  third_party_emote_lists: Arc<EmoteListStorage>,
+) -> ! {
+  tracing::info!("Starting message spool replay process.");
+  let replay_interval = Duration::from_secs(AppConfig::message_spool_replay_interval().max(1));
+  let mut replay_timer = tokio::time::interval(replay_interval);
+
+  loop {
+    replay_timer.tick().await;
+
+    let database_connection = get_database_connection().await;
+
+    if database_connection.ping().await.is_err() {
+      continue;
+    }
+
+    let replay_path = match message_spool.take_for_replay().await {
+      Ok(Some(replay_path)) => replay_path,
+      Ok(None) => continue,
+      Err(error) => {
+        tracing::error!(
+          "Failed to take the message spool for replay. Reason: {}",
+          error
+        );
+
+        continue;
+      }
+    };
+
+    if let Err(error) = replay_file(
+      &replay_path,
+      &message_spool,
+      &third_party_emote_lists,
+      database_connection,
+    )
+    .await
+    {
+      tracing::error!(
+        "Failed to replay the message spool at {:?}. Reason: {}",
+        replay_path,
+        error
+      );
+    }
+  }
+}
+
+/// Replays every message in the file, then removes it.
+///
+/// If the database goes away again partway through, the remaining messages are written back to the spool.
+async fn replay_file(
+  replay_path: &Path,
+  message_spool: &MessageSpool,
+  third_party_emote_lists: &Arc<EmoteListStorage>,
+  database_connection: &DatabaseConnection,
+) -> Result<(), AppError> {
+  let spooled_messages = tokio::fs::read_to_string(replay_path).await?;
+  let mut spooled_messages = spooled_messages
+    .lines()
+    .filter(|line| !line.trim().is_empty());
+  let mut replayed_count = 0;
+  let mut skipped_count = 0;
+
+  tracing::info!("Replaying spooled messages from {:?}.", replay_path);
+
+  while let Some(raw_message) = spooled_messages.next() {
+    let message = match format!("{raw_message}\r\n").parse::<IrcMessage>() {
+      Ok(message) => message,
+      Err(error) => {
+        tracing::error!(
+          "Failed to parse a spooled message. Skipping it. Reason: {}. Message: {:?}",
+          error,
+          raw_message
+        );
+
+        continue;
+      }
+    };
+
+    match message_already_stored(&message, database_connection).await {
+      Ok(true) => {
+        skipped_count += 1;
+
+        continue;
+      }
+      Ok(false) => (),
+      Err(error) if error.is_database_unavailable() => {
+        return respool_remaining(message, spooled_messages, message_spool).await;
+      }
+      Err(error) => {
+        tracing::error!(
+          "Failed to check if a spooled message was already stored. Skipping it. Reason: {}. Message: {:?}",
+          error,
+          message
+        );
+
+        continue;
+      }
+    }
+
+    let result =
+      TwitchIrc::create_and_run_mesage_parser(message.clone(), third_party_emote_lists.clone())
+        .await;
+
+    match result {
+      Ok(()) => replayed_count += 1,
+      Err(error) if error.is_database_unavailable() => {
+        return respool_remaining(message, spooled_messages, message_spool).await;
+      }
+      Err(error) => {
+        tracing::error!("Failed to replay a spooled message. Reason: {}", error);
+      }
+    }
+  }
+
+  tokio::fs::remove_file(replay_path).await?;
+
+  tracing::info!(
+    "Finished replaying the message spool. Replayed: {} | Already stored: {}",
+    replayed_count,
+    skipped_count
+  );
+
+  Ok(())
+}
+
+/// Writes the message that failed, and every message after it, back to the spool
+/// before the replay file is removed.
+async fn respool_remaining<'a>(
+  failed_message: IrcMessage,
+  remaining_messages: impl Iterator<Item = &'a str>,
+  message_spool: &MessageSpool,
+) -> Result<(), AppError> {
+  tracing::warn!("Lost the database connection while replaying the message spool.");
+
+  message_spool.append(&failed_message).await?;
+
+  for raw_message in remaining_messages {
+    match format!("{raw_message}\r\n").parse::<IrcMessage>() {
+      Ok(message) => message_spool.append(&message).await?,
+      Err(error) => tracing::error!(
+        "Failed to parse a spooled message. Dropping it. Reason: {}. Message: {:?}",
+        error,
+        raw_message
+      ),
+    }
+  }
+
+  tokio::fs::remove_file(message_spool.replay_path()).await?;
+
+  Ok(())
+}
+
+/// Returns true if the row the parser would write for this message already exists.
+///
+/// Each message type is checked against its own table. Rows with an origin id are matched on it,
+/// the rest are matched on their channel, user, and timestamp.
+pub async fn message_already_stored(
+  message: &IrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  if TwitchIrc::is_ignored_command(message) {
+    return Ok(false);
+  }
+
+  let Some(message) = TwitchIrcMessage::new(message)? else {
+    return Ok(false);
+  };
+
+  match message.message_type() {
+    TwitchMessageType::UserMessage => stream_message_exists(&message, database_connection).await,
+    TwitchMessageType::ChannelNotice => channel_notice_exists(&message, database_connection).await,
+    TwitchMessageType::Bits | TwitchMessageType::BitsPowerUp => {
+      donation_event_exists(&message, EventType::Bits, database_connection).await
+    }
+    TwitchMessageType::HypeChat => {
+      donation_event_exists(&message, EventType::HypeChat, database_connection).await
+    }
+    TwitchMessageType::StreamlabsDonation => {
+      donation_event_exists(&message, EventType::StreamlabsDonation, database_connection).await
+    }
+    TwitchMessageType::GiftSub => gift_sub_exists(&message, database_connection).await,
+    TwitchMessageType::Subscription => {
+      subscription_event_exists(&message, database_connection).await
+    }
+    TwitchMessageType::Timeout => user_timeout_exists(&message, database_connection).await,
+    TwitchMessageType::Raid => raid_exists(&message, database_connection).await,
+    TwitchMessageType::ChannelPointRedemption => {
+      channel_point_redemption_exists(&message, database_connection).await
+    }
+    TwitchMessageType::MessageDeletion | TwitchMessageType::ChatClear => {
+      message_deletion_exists(&message, database_connection).await
+    }
+  }
+}
+
+async fn stream_message_exists(
+  message: &TwitchIrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let Some(origin_id) = message.origin_id() else {
+    return Ok(false);
+  };
+
+  let stream_message = stream_message::Entity::find()
+    .filter(stream_message::Column::OriginId.eq(origin_id))
+    .one(database_connection)
+    .await?;
+
+  Ok(stream_message.is_some())
+}
+
+async fn channel_notice_exists(
+  message: &TwitchIrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let Some(origin_id) = message.origin_id() else {
+    return Ok(false);
+  };
+
+  let channel_notice = channel_notice::Entity::find()
+    .filter(channel_notice::Column::OriginId.eq(origin_id))
+    .one(database_connection)
+    .await?;
+
+  Ok(channel_notice.is_some())
+}
+
+/// Messages with a donation attached also store a chat message, so the donation itself is
+/// checked to catch replays that were stopped between the two inserts.
+async fn donation_event_exists(
+  message: &TwitchIrcMessage,
+  event_type: EventType,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let Some(origin_id) = message.origin_id() else {
+    return Ok(false);
+  };
+
+  let donation_event = donation_event::Entity::find()
+    .filter(donation_event::Column::OriginId.eq(origin_id))
+    .filter(donation_event::Column::EventType.eq(event_type))
+    .one(database_connection)
+    .await?;
+
+  Ok(donation_event.is_some())
+}
+
+/// Every message in a gift sub batch shares the batch's origin id, so messages with a recipient
+/// are checked against that recipient's row.
+async fn gift_sub_exists(
+  message: &TwitchIrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let Some(origin_id) = message.gift_sub_origin_id() else {
+    return Ok(false);
+  };
+  let Some(donation_event) = donation_event::Entity::find()
+    .filter(donation_event::Column::OriginId.eq(origin_id))
+    .filter(donation_event::Column::EventType.eq(EventType::GiftSubs))
+    .one(database_connection)
+    .await?
+  else {
+    return Ok(false);
+  };
+  let Some(recipient_twitch_id) = message.gift_sub_recipient_twitch_id() else {
+    return Ok(true);
+  };
+  let Some(recipient) = get_user(
+    ChannelIdentifier::TwitchID(recipient_twitch_id),
+    database_connection,
+  )
+  .await?
+  else {
+    return Ok(false);
+  };
+
+  let gift_sub_recipient = gift_sub_recipient::Entity::find()
+    .filter(gift_sub_recipient::Column::DonationEventId.eq(donation_event.id))
+    .filter(gift_sub_recipient::Column::TwitchUserId.eq(recipient.id))
+    .one(database_connection)
+    .await?;
+
+  Ok(gift_sub_recipient.is_some())
+}
+
+async fn subscription_event_exists(
+  message: &TwitchIrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let Some(channel) = get_channel(message, database_connection).await? else {
+    return Ok(false);
+  };
+  let Some(login_name) = message.login_name() else {
+    return Ok(false);
+  };
+  let Some(subscriber) =
+    get_user(ChannelIdentifier::Login(login_name), database_connection).await?
+  else {
+    return Ok(false);
+  };
+  let (window_start, window_end) = timestamp_window(message);
+
+  let subscription_event = subscription_event::Entity::find()
+    .filter(subscription_event::Column::ChannelId.eq(channel.id))
+    .filter(subscription_event::Column::SubscriberTwitchUserId.eq(subscriber.id))
+    .filter(subscription_event::Column::Timestamp.between(window_start, window_end))
+    .one(database_connection)
+    .await?;
+
+  Ok(subscription_event.is_some())
+}
+
+async fn user_timeout_exists(
+  message: &TwitchIrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let Some(channel) = get_channel(message, database_connection).await? else {
+    return Ok(false);
+  };
+  let Some(timedout_user_id) = message.timedout_user_id() else {
+    return Ok(false);
+  };
+  let Some(timedout_user) = get_user(
+    ChannelIdentifier::TwitchID(timedout_user_id),
+    database_connection,
+  )
+  .await?
+  else {
+    return Ok(false);
+  };
+  let (window_start, window_end) = timestamp_window(message);
+
+  let user_timeout = user_timeout::Entity::find()
+    .filter(user_timeout::Column::ChannelId.eq(channel.id))
+    .filter(user_timeout::Column::TwitchUserId.eq(timedout_user.id))
+    .filter(user_timeout::Column::Timestamp.between(window_start, window_end))
+    .one(database_connection)
+    .await?;
+
+  Ok(user_timeout.is_some())
+}
+
+async fn raid_exists(
+  message: &TwitchIrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let Some(channel) = get_channel(message, database_connection).await? else {
+    return Ok(false);
+  };
+  let Some(raider_id) = message.user_id() else {
+    return Ok(false);
+  };
+  let Some(raider) = get_user(ChannelIdentifier::TwitchID(raider_id), database_connection).await?
+  else {
+    return Ok(false);
+  };
+  let (window_start, window_end) = timestamp_window(message);
+
+  let raid = raid::Entity::find()
+    .filter(raid::Column::TwitchUserId.eq(channel.id))
+    .filter(raid::Column::RaiderTwitchUserId.eq(raider.id))
+    .filter(raid::Column::Timestamp.between(window_start, window_end))
+    .one(database_connection)
+    .await?;
+
+  Ok(raid.is_some())
+}
+
+async fn channel_point_redemption_exists(
+  message: &TwitchIrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let Some(channel) = get_channel(message, database_connection).await? else {
+    return Ok(false);
+  };
+  let Some(redeemer_id) = message.user_id() else {
+    return Ok(false);
+  };
+  let Some(redeemer) = get_user(
+    ChannelIdentifier::TwitchID(redeemer_id),
+    database_connection,
+  )
+  .await?
+  else {
+    return Ok(false);
+  };
+  let (window_start, window_end) = timestamp_window(message);
+
+  let channel_point_redemption = channel_point_redemption::Entity::find()
+    .filter(channel_point_redemption::Column::ChannelId.eq(channel.id))
+    .filter(channel_point_redemption::Column::TwitchUserId.eq(redeemer.id))
+    .filter(channel_point_redemption::Column::Timestamp.between(window_start, window_end))
+    .one(database_connection)
+    .await?;
+
+  Ok(channel_point_redemption.is_some())
+}
+
+async fn message_deletion_exists(
+  message: &TwitchIrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<bool, AppError> {
+  let Some(channel) = get_channel(message, database_connection).await? else {
+    return Ok(false);
+  };
+  let (window_start, window_end) = timestamp_window(message);
+  let target_condition = match message.deleted_message_id() {
+    Some(target_origin_id) => message_deletion::Column::TargetOriginId.eq(target_origin_id),
+    None => message_deletion::Column::IsChatClear.eq(1),
+  };
+
+  let message_deletion = message_deletion::Entity::find()
+    .filter(message_deletion::Column::ChannelId.eq(channel.id))
+    .filter(message_deletion::Column::Timestamp.between(window_start, window_end))
+    .filter(target_condition)
+    .one(database_connection)
+    .await?;
+
+  Ok(message_deletion.is_some())
+}
+
+async fn get_channel(
+  message: &TwitchIrcMessage,
+  database_connection: &DatabaseConnection,
+) -> Result<Option<twitch_user::Model>, AppError> {
+  let Some(room_id) = message.room_id() else {
+    return Ok(None);
+  };
+
+  get_user(ChannelIdentifier::TwitchID(room_id), database_connection).await
+}
+
+/// Looks up a user without storing them, as an unknown user can't have anything stored yet.
+async fn get_user(
+  identifier: ChannelIdentifier<&str>,
+  database_connection: &DatabaseConnection,
+) -> Result<Option<twitch_user::Model>, AppError> {
+  Ok(twitch_user::Model::get_by_identifier(identifier, database_connection).await?)
+}
+
+/// MySQL drops the milliseconds from stored timestamps, so rows are matched within a second of the message.
+fn timestamp_window(message: &TwitchIrcMessage) -> (DateTime<Utc>, DateTime<Utc>) {
+  let timestamp = *message.timestamp();
+  let one_second = chrono::Duration::seconds(1);
+
+  (timestamp - one_second, timestamp + one_second)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::testing_helper_methods::timestamp_from_string;
+
+  const SOURCE_ID: &str = "159ba37c-c6aa-4fdd-bc62-c5fadbab0770";
+
+  fn shared_chat_message() -> IrcMessage {
+    format!(
+      "@room-id=578762718;user-id=128831052;tmi-sent-ts=1740956922774;display-name=LinkTheDot;source-id={SOURCE_ID};source-room-id=578762718 :linkthedot!linkthedot@linkthedot.tmi.twitch.tv PRIVMSG #fallenshadow :glorp\r\n"
+    )
+    .parse()
+    .unwrap()
+  }
+
+  #[tokio::test]
+  async fn messages_with_a_stored_source_id_are_skipped() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([vec![stream_message::Model {
+        id: 1,
+        is_first_message: 0_i8,
+        timestamp: timestamp_from_string("1740956922774"),
+        emote_only: 0_i8,
+        contents: Some("glorp".to_string()),
+        twitch_user_id: 3,
+        channel_id: 1,
+        stream_id: None,
+        is_subscriber: 0_i8,
+        origin_id: Some(SOURCE_ID.into()),
+        deleted_at: None,
+      }]])
+      .into_connection();
+
+    let already_stored = message_already_stored(&shared_chat_message(), &mock_database)
+      .await
+      .unwrap();
+
+    assert!(already_stored);
+  }
+
+  #[tokio::test]
+  async fn messages_with_an_unknown_source_id_are_replayed() {
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([Vec::<stream_message::Model>::new()])
+      .into_connection();
+
+    let already_stored = message_already_stored(&shared_chat_message(), &mock_database)
+      .await
+      .unwrap();
+
+    assert!(!already_stored);
+  }
+
+  #[tokio::test]
+  async fn messages_without_an_origin_id_are_replayed() {
+    let message: IrcMessage =
+      "@room-id=578762718;user-id=128831052;tmi-sent-ts=1740956922774;display-name=LinkTheDot :linkthedot!linkthedot@linkthedot.tmi.twitch.tv PRIVMSG #fallenshadow :glorp\r\n"
+        .parse()
+        .unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql).into_connection();
+
+    let already_stored = message_already_stored(&message, &mock_database)
+      .await
+      .unwrap();
+
+    assert!(!already_stored);
+  }
+
+  #[tokio::test]
+  async fn stored_subscriptions_without_a_source_id_are_skipped() {
+    let message: IrcMessage =
+      "@room-id=578762718;user-id=128831052;login=linkthedot;display-name=LinkTheDot;msg-id=resub;msg-param-sub-plan=1000;msg-param-cumulative-months=12;tmi-sent-ts=1740956922774 :tmi.twitch.tv USERNOTICE #fallenshadow\r\n"
+        .parse()
+        .unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([vec![fallenshadow()], vec![linkthedot()]])
+      .append_query_results([vec![subscription_event::Model {
+        id: 1,
+        months_subscribed: 12,
+        timestamp: timestamp_from_string("1740956922000"),
+        channel_id: 1,
+        stream_id: None,
+        subscriber_twitch_user_id: Some(3),
+        subscription_tier: Some(1),
+        source_id: None,
+      }]])
+      .into_connection();
+
+    let already_stored = message_already_stored(&message, &mock_database)
+      .await
+      .unwrap();
+
+    assert!(already_stored);
+  }
+
+  #[tokio::test]
+  async fn timeouts_that_were_not_stored_are_replayed() {
+    let message: IrcMessage =
+      "@room-id=578762718;target-user-id=128831052;ban-duration=600;tmi-sent-ts=1740956922774 :tmi.twitch.tv CLEARCHAT #fallenshadow :linkthedot\r\n"
+        .parse()
+        .unwrap();
+    let mock_database = MockDatabase::new(DatabaseBackend::MySql)
+      .append_query_results([vec![fallenshadow()], vec![linkthedot()]])
+      .append_query_results([Vec::<user_timeout::Model>::new()])
+      .into_connection();
+
+    let already_stored = message_already_stored(&message, &mock_database)
+      .await
+      .unwrap();
+
+    assert!(!already_stored);
+  }
+
+  fn fallenshadow() -> twitch_user::Model {
+    twitch_user::Model {
+      id: 1,
+      twitch_id: 578762718,
+      login_name: "fallenshadow".into(),
+      display_name: "fallenshadow".into(),
+    }
+  }
+
+  fn linkthedot() -> twitch_user::Model {
+    twitch_user::Model {
+      id: 3,
+      twitch_id: 128831052,
+      login_name: "linkthedot".into(),
+      display_name: "LinkTheDot".into(),
+    }
+  }
+}
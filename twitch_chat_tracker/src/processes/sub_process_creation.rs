@@ -1,22 +1,14 @@
 use crate::channel::tracked_channels::TrackedChannels;
-use crate::errors::AppError;
-use crate::processes::{
-  app_animation::run_animation, process_irc_message_results, update_channel_live_statuses,
-};
-use tokio::{sync::mpsc, task::JoinHandle};
+use crate::processes::{app_animation::run_animation, update_channel_live_statuses};
 
 /// Creates the necessary sub processes for running the app.
-/// These include the running animation, channel updator, and message parsing result manager.
+/// These include the running animation and channel updator.
 ///
-/// Returns the sender to the message parsing result manager.
-pub async fn create_sub_processes() -> mpsc::UnboundedSender<JoinHandle<Result<(), AppError>>> {
+/// The message workers are started alongside the IRC client in [`run_main_process`](crate::processes::run_main_process).
+pub async fn create_sub_processes() {
   tracing::info!("Creating sub processes.");
   let connected_channels = TrackedChannels::new().await.unwrap();
-  let (irc_message_processing_sender, irc_message_processing_receiver) = mpsc::unbounded_channel();
 
   tokio::spawn(run_animation());
   tokio::spawn(update_channel_live_statuses(connected_channels));
-  tokio::spawn(process_irc_message_results(irc_message_processing_receiver));
-
-  irc_message_processing_sender
 }
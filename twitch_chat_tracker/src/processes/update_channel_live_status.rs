@@ -1,10 +1,13 @@
 use crate::channel::tracked_channels::TrackedChannels;
+use crate::processes::post_stream_reports::generate_missed_post_stream_reports;
 use crate::{errors::AppError, websocket_connection::config::TwitchWebsocketConfig};
+use chrono::{DateTime, Utc};
 use database_connection::get_database_connection;
-use entities::stream;
+use entities::{stream, stream_message};
 use entity_extensions::stream::StreamExtensions;
 use sea_orm::*;
 use sea_query::OnConflict;
+use std::collections::HashMap;
 
 const TIMEOUT_COUNT_UNTIL_RESET: usize = 5;
 
@@ -20,6 +23,8 @@ pub async fn update_channel_live_statuses(tracked_channels: TrackedChannels) ->
     );
   }
 
+  tokio::spawn(generate_missed_post_stream_reports());
+
   let mut websocket_config = TwitchWebsocketConfig::new(tracked_channels, database_connection)
     .await
     .unwrap();
@@ -90,6 +95,13 @@ async fn update_active_streams(
   let current_live_channels = stream::Model::get_active_livestreams(channels).await?;
   let mut live_stream_active_models: Vec<stream::ActiveModel> = vec![];
 
+  close_stale_streams(
+    tracked_channels,
+    &current_live_channels,
+    database_connection,
+  )
+  .await?;
+
   for (streamer_login_name, (stream_start_time, stream_twitch_id)) in
     current_live_channels.into_iter()
   {
@@ -133,3 +145,53 @@ async fn update_active_streams(
 
   Ok(())
 }
+
+/// Ends any stored stream that's still open for a channel Helix doesn't report as live with that stream.
+///
+/// These are streams that went offline while the tracker was down, so they're ended at their last chat message.
+async fn close_stale_streams(
+  tracked_channels: &TrackedChannels,
+  current_live_channels: &HashMap<String, (DateTime<Utc>, String)>,
+  database_connection: &DatabaseConnection,
+) -> Result<(), AppError> {
+  for channel in tracked_channels.all_channels() {
+    let Some(open_stream) =
+      stream::Model::get_active_stream_for_user(channel, database_connection).await?
+    else {
+      continue;
+    };
+    let is_still_live =
+      current_live_channels
+        .get(&channel.login_name)
+        .is_some_and(|(_, stream_twitch_id)| {
+          stream_twitch_id.parse::<u64>().ok() == Some(open_stream.twitch_stream_id)
+        });
+
+    if is_still_live {
+      continue;
+    }
+
+    let last_message = stream_message::Entity::find()
+      .filter(stream_message::Column::StreamId.eq(open_stream.id))
+      .order_by_desc(stream_message::Column::Timestamp)
+      .one(database_connection)
+      .await?;
+    let end_timestamp = last_message
+      .map(|message| message.timestamp)
+      .or(open_stream.start_timestamp)
+      .unwrap_or_else(Utc::now);
+
+    tracing::info!(
+      "Ending stream {} for {}, which went offline while the tracker was down.",
+      open_stream.id,
+      channel.login_name
+    );
+
+    let mut stream_active_model = open_stream.into_active_model();
+
+    stream_active_model.end_timestamp = Set(Some(end_timestamp));
+    stream_active_model.update(database_connection).await?;
+  }
+
+  Ok(())
+}
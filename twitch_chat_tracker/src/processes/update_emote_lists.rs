@@ -0,0 +1,181 @@
+use crate::channel::third_party_emote_list_storage::EmoteListStorage;
+use crate::websocket_connection::seventv_event_api::{SevenTvEventApi, SEVENTV_EVENT_API_URL};
+use app_config::AppConfig;
+use database_connection::get_database_connection;
+use std::{sync::Arc, time::Duration};
+use tokio::time::{interval_at, sleep, Instant, Interval};
+
+/// How long to wait before the first attempt to reconnect to 7TV's EventAPI.
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// The longest the wait between attempts to reconnect to 7TV's EventAPI can grow to.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(300);
+/// How long a session has to last before it's considered stable, resetting the reconnect delay.
+const STABLE_SESSION_DURATION: Duration = Duration::from_secs(60);
+
+/// Why an EventAPI session ended.
+enum EventApiSessionEnd {
+  /// The tracked emote sets changed, so the session should be restarted right away.
+  Resubscribe,
+  /// The connection failed or was lost.
+  Disconnected,
+}
+
+/// Keeps the emote lists in storage up to date.
+///
+/// 7TV emote sets are refreshed as soon as 7TV's EventAPI reports a change to them.
+/// Every list is also queried again on the interval set in the [`app config`](AppConfig::emote_list_refresh_interval),
+/// which covers BTTV, FrankerFaceZ, and any time the EventAPI can't be reached.
+pub async fn update_emote_lists(emote_list_storage: Arc<EmoteListStorage>) -> ! {
+  tracing::info!("Starting emote list update process.");
+  let refresh_interval = Duration::from_secs(AppConfig::emote_list_refresh_interval().max(1));
+  let mut refresh_timer = interval_at(Instant::now() + refresh_interval, refresh_interval);
+  let mut reconnect_delay = INITIAL_RECONNECT_DELAY;
+
+  loop {
+    if !AppConfig::seventv_event_api_enabled() {
+      refresh_timer.tick().await;
+      refresh_all_lists(&emote_list_storage).await;
+
+      continue;
+    }
+
+    let session_start = Instant::now();
+
+    match run_event_api_session(&emote_list_storage, &mut refresh_timer).await {
+      EventApiSessionEnd::Resubscribe => reconnect_delay = INITIAL_RECONNECT_DELAY,
+      EventApiSessionEnd::Disconnected => {
+        if session_start.elapsed() >= STABLE_SESSION_DURATION {
+          reconnect_delay = INITIAL_RECONNECT_DELAY;
+        }
+
+        tracing::info!(
+          "Reconnecting to 7TV's EventAPI in {} seconds.",
+          reconnect_delay.as_secs()
+        );
+
+        wait_before_reconnecting(reconnect_delay, &emote_list_storage, &mut refresh_timer).await;
+        reconnect_delay = next_reconnect_delay(reconnect_delay);
+      }
+    }
+  }
+}
+
+/// Doubles the delay, up to [`MAX_RECONNECT_DELAY`].
+fn next_reconnect_delay(reconnect_delay: Duration) -> Duration {
+  (reconnect_delay * 2).min(MAX_RECONNECT_DELAY)
+}
+
+/// Waits out the reconnect delay, still refreshing every list whenever the refresh timer ticks.
+async fn wait_before_reconnecting(
+  reconnect_delay: Duration,
+  emote_list_storage: &EmoteListStorage,
+  refresh_timer: &mut Interval,
+) {
+  let reconnect_timer = sleep(reconnect_delay);
+  tokio::pin!(reconnect_timer);
+
+  loop {
+    tokio::select! {
+      _ = &mut reconnect_timer => return,
+      _ = refresh_timer.tick() => refresh_all_lists(emote_list_storage).await,
+    }
+  }
+}
+
+/// Listens for emote set updates until the EventAPI connection ends,
+/// or the emote sets being tracked change.
+///
+/// Refreshes every list whenever the refresh timer ticks.
+async fn run_event_api_session(
+  emote_list_storage: &EmoteListStorage,
+  refresh_timer: &mut Interval,
+) -> EventApiSessionEnd {
+  let database_connection = get_database_connection().await;
+  let emote_set_ids = emote_list_storage.seventv_emote_set_ids();
+  let subscribed_emote_set_ids: Vec<&str> = emote_set_ids.keys().map(String::as_str).collect();
+
+  let mut event_api = match SevenTvEventApi::connect(
+    SEVENTV_EVENT_API_URL,
+    &subscribed_emote_set_ids,
+  )
+  .await
+  {
+    Ok(event_api) => event_api,
+    Err(error) => {
+      tracing::error!(
+          "Failed to connect to 7TV's EventAPI. Falling back to polling until the next attempt. Reason: {}",
+          error
+        );
+
+      return EventApiSessionEnd::Disconnected;
+    }
+  };
+
+  loop {
+    tokio::select! {
+      _ = refresh_timer.tick() => {
+        refresh_all_lists(emote_list_storage).await;
+
+        if emote_list_storage.seventv_emote_set_ids() != emote_set_ids {
+          tracing::info!("Tracked 7TV emote sets have changed. Resubscribing.");
+
+          return EventApiSessionEnd::Resubscribe;
+        }
+      }
+
+      emote_set_update = event_api.next_emote_set_update() => {
+        let emote_set_id = match emote_set_update {
+          Ok(emote_set_id) => emote_set_id,
+          Err(error) => {
+            tracing::error!("Lost connection to 7TV's EventAPI. Reason: {}", error);
+
+            return EventApiSessionEnd::Disconnected;
+          }
+        };
+        let Some(channel_name) = emote_set_ids.get(&emote_set_id) else {
+          tracing::warn!("Received an update for an untracked emote set: {}", emote_set_id);
+
+          continue;
+        };
+
+        if let Err(error) = emote_list_storage
+          .refresh_list(channel_name, database_connection)
+          .await
+        {
+          tracing::error!(
+            "Failed to refresh the emote list for {} after a 7TV update. Reason: {}",
+            channel_name,
+            error
+          );
+        }
+      }
+    }
+  }
+}
+
+async fn refresh_all_lists(emote_list_storage: &EmoteListStorage) {
+  tracing::info!("Refreshing all emote lists.");
+  let database_connection = get_database_connection().await;
+
+  emote_list_storage
+    .refresh_all_lists(database_connection)
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reconnect_delay_doubles_up_to_the_cap() {
+    let mut reconnect_delay = INITIAL_RECONNECT_DELAY;
+    let mut delays = vec![];
+
+    for _ in 0..10 {
+      delays.push(reconnect_delay.as_secs());
+      reconnect_delay = next_reconnect_delay(reconnect_delay);
+    }
+
+    assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 64, 128, 256, 300]);
+  }
+}
@@ -1,6 +1,14 @@
 use crate::{
-  channel::tracked_channels::TrackedChannels, errors::AppError,
-  irc_chat::message_parser::MessageParser, websocket_connection::subscriptions::EventSubscription,
+  channel::tracked_channels::TrackedChannels,
+  errors::AppError,
+  irc_chat::message_parser::MessageParser,
+  websocket_connection::{
+    subscriptions::EventSubscription,
+    twitch_objects::{
+      channel_point_redemption::TwitchChannelPointRedemptionMessage,
+      hype_train::TwitchHypeTrainMessage,
+    },
+  },
 };
 use app_config::{secret_string::Secret, AppConfig};
 use database_connection::get_database_connection;
@@ -40,6 +48,23 @@ const SUBSCRIPTIONS: &[EventSubscription] = &[
   // https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#streamoffline
   EventSubscription::new(None, "stream.offline", 1),
 ];
+/// Only subscribed to when `channel_point_reward_titles_enabled` is set, as it needs extra scopes from each broadcaster.
+///
+/// https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelchannel_points_custom_reward_redemptionadd
+const CHANNEL_POINT_REDEMPTION_SUBSCRIPTION: EventSubscription = EventSubscription::new(
+  None,
+  TwitchChannelPointRedemptionMessage::SUBSCRIPTION_TYPE,
+  1,
+);
+/// Only subscribed to when `hype_train_tracking_enabled` is set, as they need extra scopes from each broadcaster.
+const HYPE_TRAIN_SUBSCRIPTIONS: &[EventSubscription] = &[
+  // https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelhype_trainbegin
+  EventSubscription::new(None, "channel.hype_train.begin", 2),
+  // https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelhype_trainprogress
+  EventSubscription::new(None, "channel.hype_train.progress", 2),
+  // https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelhype_trainend
+  EventSubscription::new(None, "channel.hype_train.end", 2),
+];
 const SUBSCRIPTION_FAIL_RETRY_BASE_DURATION: Duration = Duration::new(30, 0);
 
 /// In seconds.
@@ -127,7 +152,7 @@ impl TwitchWebsocketConfig {
       .header("Content-Type", "application/json");
 
     let subscription_bodies = EventSubscription::create_subscription_bodies_from_list(
-      SUBSCRIPTIONS,
+      &Self::enabled_subscriptions(),
       tracked_channels,
       running_user,
       session_id,
@@ -165,6 +190,21 @@ impl TwitchWebsocketConfig {
     Ok(subscription_failed)
   }
 
+  /// [`SUBSCRIPTIONS`], along with any optional subscriptions enabled in the config.
+  fn enabled_subscriptions() -> Vec<EventSubscription> {
+    let mut subscriptions = SUBSCRIPTIONS.to_vec();
+
+    if AppConfig::channel_point_reward_titles_enabled() {
+      subscriptions.push(CHANNEL_POINT_REDEMPTION_SUBSCRIPTION);
+    }
+
+    if AppConfig::hype_train_tracking_enabled() {
+      subscriptions.extend_from_slice(HYPE_TRAIN_SUBSCRIPTIONS);
+    }
+
+    subscriptions
+  }
+
   pub async fn send_subscription(
     value: Value,
     request_builder: RequestBuilder,
@@ -246,6 +286,8 @@ impl TwitchWebsocketConfig {
   /// Wait for the next message from the websocket connection, returning if none was received.
   ///
   /// If the message was for `stream.offline` or `stream.online` events, they are parsed, and the database is updated.
+  /// Channel point redemption events fill in the reward details of redemptions received through IRC.
+  /// Hype train events are stored as they progress.
   ///
   /// Returns true if there was a message received.
   /// Otherwise, if [`keep_alive`](KEEP_ALIVE_DURATION) + [`grace`](KEEP_ALIVE_GRACE_PERIOD)
@@ -302,6 +344,31 @@ impl TwitchWebsocketConfig {
       return Ok(true);
     }
 
+    if message["metadata"]["subscription_type"]
+      == TwitchChannelPointRedemptionMessage::SUBSCRIPTION_TYPE
+    {
+      MessageParser::parse_websocket_channel_point_redemption_message(
+        message,
+        get_database_connection().await,
+      )
+      .await?;
+
+      return Ok(true);
+    }
+
+    let is_hype_train_message = message["metadata"]["subscription_type"]
+      .as_str()
+      .is_some_and(|subscription_type| {
+        TwitchHypeTrainMessage::SUBSCRIPTION_TYPES.contains(&subscription_type)
+      });
+
+    if is_hype_train_message {
+      MessageParser::parse_websocket_hype_train_message(message, get_database_connection().await)
+        .await?;
+
+      return Ok(true);
+    }
+
     MessageParser::parse_websocket_stream_status_update_message(
       message,
       get_database_connection().await,
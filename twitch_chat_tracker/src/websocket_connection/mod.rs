@@ -1,3 +1,4 @@
 pub mod config;
+pub mod seventv_event_api;
 pub mod subscriptions;
 pub mod twitch_objects;
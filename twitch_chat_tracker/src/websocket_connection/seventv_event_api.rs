@@ -0,0 +1,318 @@
+use crate::errors::AppError;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::{net::TcpStream, time::timeout};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+pub const SEVENTV_EVENT_API_URL: &str = "wss://events.7tv.io/v3";
+
+// Opcodes as per the [documentation](https://github.com/SevenTV/EventAPI#opcodes)
+const DISPATCH_OPCODE: u64 = 0;
+const HELLO_OPCODE: u64 = 1;
+const RECONNECT_OPCODE: u64 = 4;
+const END_OF_STREAM_OPCODE: u64 = 7;
+const SUBSCRIBE_OPCODE: u64 = 35;
+
+const EMOTE_SET_UPDATE_EVENT: &str = "emote_set.update";
+
+/// The amount of messages to go through at startup to retrieve the hello message.
+const GET_HELLO_RETRY_ATTEMPTS: i32 = 5;
+/// How many extra seconds to wait for a heartbeat past the interval given in the hello message.
+const HEARTBEAT_GRACE_PERIOD: Duration = Duration::new(5, 0);
+/// How long to wait for the hello message after connecting.
+const HELLO_TIMEOUT: Duration = Duration::new(10, 0);
+
+/// A connection to 7TV's EventAPI, subscribed to changes for a set of emote sets.
+pub struct SevenTvEventApi {
+  socket_stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+  heartbeat_interval: Duration,
+}
+
+impl SevenTvEventApi {
+  /// Connects to the EventAPI under the given url, then subscribes to updates for each emote set ID.
+  ///
+  /// Returns AppError::WebsocketTimeout if the hello message isn't received within [`HELLO_TIMEOUT`].
+  pub async fn connect(url: &str, emote_set_ids: &[&str]) -> Result<Self, AppError> {
+    Self::connect_with_hello_timeout(url, emote_set_ids, HELLO_TIMEOUT).await
+  }
+
+  async fn connect_with_hello_timeout(
+    url: &str,
+    emote_set_ids: &[&str],
+    hello_timeout: Duration,
+  ) -> Result<Self, AppError> {
+    let (mut socket_stream, _) = connect_async(url).await?;
+    let Ok(heartbeat_interval) = timeout(
+      hello_timeout,
+      Self::get_heartbeat_interval(&mut socket_stream),
+    )
+    .await
+    else {
+      return Err(AppError::WebsocketTimeout);
+    };
+    let Some(heartbeat_interval) = heartbeat_interval else {
+      return Err(AppError::MissingExpectedValue {
+        expected_value_name: "hello message",
+        location: "7tv event api connect",
+      });
+    };
+
+    for emote_set_id in emote_set_ids {
+      let subscription = Self::create_subscription_body(emote_set_id);
+
+      socket_stream
+        .send(Message::text(subscription.to_string()))
+        .await?;
+    }
+
+    tracing::info!(
+      "Subscribed to {} emote sets through 7TV's EventAPI.",
+      emote_set_ids.len()
+    );
+
+    Ok(Self {
+      socket_stream,
+      heartbeat_interval,
+    })
+  }
+
+  /// Waits for the next update to any of the subscribed emote sets, returning the ID of the set that changed.
+  ///
+  /// Returns AppError::WebsocketTimeout if no message, including heartbeats, was received within the heartbeat interval.
+  /// Returns AppError::SevenTvEventApiClosed if 7TV closed the connection or asked for a reconnect.
+  pub async fn next_emote_set_update(&mut self) -> Result<String, AppError> {
+    loop {
+      let message_result = timeout(
+        self.heartbeat_interval + HEARTBEAT_GRACE_PERIOD,
+        self.socket_stream.next(),
+      )
+      .await;
+
+      let Ok(message_result) = message_result else {
+        return Err(AppError::WebsocketTimeout);
+      };
+      let Some(message) = message_result else {
+        return Err(AppError::SevenTvEventApiClosed);
+      };
+      let message = message?;
+
+      if message.is_close() {
+        tracing::warn!("7TV's EventAPI has issued a close. Message: {:?}", message);
+
+        return Err(AppError::SevenTvEventApiClosed);
+      }
+
+      let message = message.to_text()?;
+
+      if message.is_empty() {
+        continue;
+      }
+
+      let Ok(message) = serde_json::from_str::<Value>(message) else {
+        return Err(AppError::FailedToParseValue {
+          value_name: "message",
+          location: "7tv event api next emote set update",
+          value: message.to_string(),
+        });
+      };
+
+      match message["op"].as_u64() {
+        Some(DISPATCH_OPCODE) => {
+          if let Some(emote_set_id) = Self::emote_set_id_from_dispatch(&message) {
+            return Ok(emote_set_id);
+          }
+        }
+        Some(RECONNECT_OPCODE) | Some(END_OF_STREAM_OPCODE) => {
+          tracing::warn!("7TV's EventAPI has ended the session. Message: {}", message);
+
+          return Err(AppError::SevenTvEventApiClosed);
+        }
+        _ => (),
+      }
+    }
+  }
+
+  /// Extracts the heartbeat interval from the hello message sent when connecting.
+  async fn get_heartbeat_interval(
+    socket_stream: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+  ) -> Option<Duration> {
+    let mut attempts = GET_HELLO_RETRY_ATTEMPTS;
+
+    while let Some(message) = socket_stream.next().await {
+      if let Ok(message) = message {
+        if let Ok(message) = serde_json::from_str::<Value>(&message.to_string()) {
+          if message["op"].as_u64() == Some(HELLO_OPCODE) {
+            return message["d"]["heartbeat_interval"]
+              .as_u64()
+              .map(Duration::from_millis);
+          }
+        }
+      }
+
+      attempts -= 1;
+
+      if attempts == 0 {
+        break;
+      }
+    }
+
+    None
+  }
+
+  fn create_subscription_body(emote_set_id: &str) -> Value {
+    json!({
+      "op": SUBSCRIBE_OPCODE,
+      "d": {
+        "type": EMOTE_SET_UPDATE_EVENT,
+        "condition": {
+          "object_id": emote_set_id
+        }
+      }
+    })
+  }
+
+  fn emote_set_id_from_dispatch(message: &Value) -> Option<String> {
+    let data = &message["d"];
+
+    if data["type"] != EMOTE_SET_UPDATE_EVENT {
+      return None;
+    }
+
+    data["body"]["id"].as_str().map(ToOwned::to_owned)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::net::TcpListener;
+
+  const EMOTE_SET_ID: &str = "01HKQJ0B0G000BRZ1VQMSX9Q1E";
+
+  /// Starts a mock EventAPI server that sends a hello, waits for a single subscription,
+  /// then sends each message in `messages_after_subscribe`.
+  ///
+  /// Returns the address of the server, and a handle to the subscription the server received.
+  async fn start_mock_event_api(
+    messages_after_subscribe: Vec<Value>,
+  ) -> (String, tokio::task::JoinHandle<Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("ws://{}", listener.local_addr().unwrap());
+
+    let server_handle = tokio::spawn(async move {
+      let (tcp_stream, _) = listener.accept().await.unwrap();
+      let mut socket_stream = tokio_tungstenite::accept_async(tcp_stream).await.unwrap();
+
+      let hello = json!({
+        "op": HELLO_OPCODE,
+        "d": {
+          "heartbeat_interval": 45000,
+          "session_id": "7f0e6c4a9b2d4e11a1c3d5e7f9a1b3c5",
+          "subscription_limit": 500
+        }
+      });
+      socket_stream
+        .send(Message::text(hello.to_string()))
+        .await
+        .unwrap();
+
+      let subscription = socket_stream.next().await.unwrap().unwrap();
+      let subscription: Value = serde_json::from_str(subscription.to_text().unwrap()).unwrap();
+
+      for message in messages_after_subscribe {
+        socket_stream
+          .send(Message::text(message.to_string()))
+          .await
+          .unwrap();
+      }
+
+      // Keep the connection open until the client is done with it.
+      let _ = socket_stream.next().await;
+
+      subscription
+    });
+
+    (address, server_handle)
+  }
+
+  #[tokio::test]
+  async fn emote_set_updates_are_received_from_the_event_api() {
+    let messages = vec![
+      json!({ "op": 5, "d": { "command": "subscribe" } }),
+      json!({ "op": 2, "d": { "count": 1 } }),
+      json!({
+        "op": DISPATCH_OPCODE,
+        "d": {
+          "type": "user.update",
+          "body": { "id": "01FKTJJG9R000C7W7GG0EPZQMQ" }
+        }
+      }),
+      json!({
+        "op": DISPATCH_OPCODE,
+        "d": {
+          "type": EMOTE_SET_UPDATE_EVENT,
+          "body": {
+            "id": EMOTE_SET_ID,
+            "pushed": [
+              {
+                "key": "emotes",
+                "index": 4,
+                "value": { "id": "01F6MQ33FG000FFJ97ZB8MWV52", "name": "catJAM" }
+              }
+            ]
+          }
+        }
+      }),
+    ];
+    let (address, server_handle) = start_mock_event_api(messages).await;
+
+    let mut event_api = SevenTvEventApi::connect(&address, &[EMOTE_SET_ID])
+      .await
+      .unwrap();
+    let updated_emote_set_id = event_api.next_emote_set_update().await.unwrap();
+
+    drop(event_api);
+    let received_subscription = server_handle.await.unwrap();
+
+    assert_eq!(updated_emote_set_id, EMOTE_SET_ID);
+    assert_eq!(
+      received_subscription,
+      SevenTvEventApi::create_subscription_body(EMOTE_SET_ID)
+    );
+  }
+
+  #[tokio::test]
+  async fn connecting_times_out_without_a_hello() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("ws://{}", listener.local_addr().unwrap());
+    let _server_handle = tokio::spawn(async move {
+      let (tcp_stream, _) = listener.accept().await.unwrap();
+      let mut socket_stream = tokio_tungstenite::accept_async(tcp_stream).await.unwrap();
+
+      let _ = socket_stream.next().await;
+    });
+
+    let result = SevenTvEventApi::connect_with_hello_timeout(
+      &address,
+      &[EMOTE_SET_ID],
+      Duration::from_millis(100),
+    )
+    .await;
+
+    assert!(matches!(result, Err(AppError::WebsocketTimeout)));
+  }
+
+  #[tokio::test]
+  async fn reconnect_request_closes_the_session() {
+    let messages = vec![json!({ "op": RECONNECT_OPCODE, "d": { "reason": "server restart" } })];
+    let (address, _server_handle) = start_mock_event_api(messages).await;
+
+    let mut event_api = SevenTvEventApi::connect(&address, &[EMOTE_SET_ID])
+      .await
+      .unwrap();
+    let result = event_api.next_emote_set_update().await;
+
+    assert!(matches!(result, Err(AppError::SevenTvEventApiClosed)));
+  }
+}
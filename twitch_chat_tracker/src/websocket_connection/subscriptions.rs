@@ -1,6 +1,7 @@
 use entities::twitch_user;
 use serde_json::{json, Value};
 
+#[derive(Debug, Clone, Copy)]
 pub struct EventSubscription {
   pub custom_user_identifier_condition: Option<&'static str>,
   pub _type: &'static str,
@@ -0,0 +1,97 @@
+use serde::Deserialize;
+
+/// A `channel.channel_points_custom_reward_redemption.add` notification.
+///
+/// Only the values used to fill in reward details are deserialized.
+#[derive(Deserialize, Debug)]
+pub struct TwitchChannelPointRedemptionMessage {
+  payload: TwitchPayload,
+}
+
+#[derive(Deserialize, Debug)]
+struct TwitchPayload {
+  event: ChannelPointRedemptionEvent,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChannelPointRedemptionEvent {
+  #[serde(rename = "broadcaster_user_id")]
+  streamer_user_id: String,
+  reward: ChannelPointReward,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChannelPointReward {
+  id: String,
+  title: String,
+  cost: i32,
+}
+
+impl TwitchChannelPointRedemptionMessage {
+  pub const SUBSCRIPTION_TYPE: &str = "channel.channel_points_custom_reward_redemption.add";
+
+  pub fn get_streamer_twitch_id(&self) -> &str {
+    &self.payload.event.streamer_user_id
+  }
+
+  /// Matches the `custom-reward-id` tag sent with redemptions in IRC.
+  pub fn get_reward_id(&self) -> &str {
+    &self.payload.event.reward.id
+  }
+
+  pub fn get_reward_title(&self) -> &str {
+    &self.payload.event.reward.title
+  }
+
+  pub fn get_reward_cost(&self) -> i32 {
+    self.payload.event.reward.cost
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_redemption_json_deserialization_and_getters() {
+    let json_data = r#"{
+  "metadata": {
+    "message_id": "befa7b53-d79d-478f-86b9-120f112b044e",
+    "message_timestamp": "2025-05-08T00:02:29.579998945Z",
+    "message_type": "notification",
+    "subscription_type": "channel.channel_points_custom_reward_redemption.add",
+    "subscription_version": "1"
+  },
+  "payload": {
+    "event": {
+      "id": "17fa2df1-ad76-4804-bfa5-a40ef63efe63",
+      "broadcaster_user_id": "578762718",
+      "broadcaster_user_login": "fallenshadow",
+      "broadcaster_user_name": "fallenshadow",
+      "user_id": "128831052",
+      "user_login": "linkthedot",
+      "user_name": "LinkTheDot",
+      "user_input": "glorp",
+      "status": "unfulfilled",
+      "reward": {
+        "id": "5d1f6b3e-1a4c-4b5e-9c3e-2f3a4b5c6d7e",
+        "title": "Hydrate",
+        "cost": 500,
+        "prompt": "Make the streamer drink water"
+      },
+      "redeemed_at": "2025-05-08T00:02:29.532137847Z"
+    }
+  }
+}"#;
+
+    let message: TwitchChannelPointRedemptionMessage = serde_json::from_str(json_data).unwrap();
+
+    assert_eq!(message.get_streamer_twitch_id(), "578762718");
+    assert_eq!(
+      message.get_reward_id(),
+      "5d1f6b3e-1a4c-4b5e-9c3e-2f3a4b5c6d7e"
+    );
+    assert_eq!(message.get_reward_title(), "Hydrate");
+    assert_eq!(message.get_reward_cost(), 500);
+  }
+}
@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// A `channel.hype_train.begin`, `channel.hype_train.progress`, or `channel.hype_train.end` notification.
+#[derive(Deserialize, Debug)]
+pub struct TwitchHypeTrainMessage {
+  metadata: TwitchMetadata,
+  payload: TwitchPayload,
+}
+
+#[derive(Deserialize, Debug)]
+struct TwitchMetadata {
+  #[serde(rename = "subscription_type")]
+  subscription_event_type: HypeTrainEventType,
+}
+
+#[derive(Deserialize, Debug)]
+struct TwitchPayload {
+  event: HypeTrainEvent,
+}
+
+#[derive(Deserialize, Debug)]
+struct HypeTrainEvent {
+  #[serde(rename = "id")]
+  hype_train_id: String,
+  #[serde(rename = "broadcaster_user_id")]
+  streamer_user_id: String,
+  level: i32,
+  total: i32,
+  #[serde(default)]
+  top_contributions: Vec<HypeTrainContribution>,
+  started_at: DateTime<Utc>,
+  expires_at: Option<DateTime<Utc>>,
+  ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct HypeTrainContribution {
+  pub user_id: String,
+  #[serde(rename = "type")]
+  pub contribution_type: HypeTrainContributionType,
+  pub total: i32,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize, PartialEq)]
+pub enum HypeTrainContributionType {
+  #[serde(rename = "bits")]
+  Bits,
+  #[serde(rename = "subscription")]
+  Subscription,
+  #[serde(other)]
+  Other,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize, PartialEq)]
+pub enum HypeTrainEventType {
+  #[serde(rename = "channel.hype_train.begin")]
+  Begin,
+  #[serde(rename = "channel.hype_train.progress")]
+  Progress,
+  #[serde(rename = "channel.hype_train.end")]
+  End,
+  #[serde(other)]
+  Unknown,
+}
+
+impl TwitchHypeTrainMessage {
+  pub const SUBSCRIPTION_TYPES: &[&str] = &[
+    "channel.hype_train.begin",
+    "channel.hype_train.progress",
+    "channel.hype_train.end",
+  ];
+
+  pub fn get_subscription_event_type(&self) -> HypeTrainEventType {
+    self.metadata.subscription_event_type
+  }
+
+  pub fn get_hype_train_id(&self) -> &str {
+    &self.payload.event.hype_train_id
+  }
+
+  pub fn get_streamer_twitch_id(&self) -> &str {
+    &self.payload.event.streamer_user_id
+  }
+
+  pub fn get_level(&self) -> i32 {
+    self.payload.event.level
+  }
+
+  /// The total points contributed to the hype train.
+  pub fn get_total(&self) -> i32 {
+    self.payload.event.total
+  }
+
+  pub fn get_top_contributions(&self) -> &[HypeTrainContribution] {
+    &self.payload.event.top_contributions
+  }
+
+  pub fn get_started_at(&self) -> &DateTime<Utc> {
+    &self.payload.event.started_at
+  }
+
+  /// Only exists when the event type is `Begin` or `Progress`.
+  pub fn get_expires_at(&self) -> Option<&DateTime<Utc>> {
+    self.payload.event.expires_at.as_ref()
+  }
+
+  /// Only exists when the event type is `End`.
+  pub fn get_ended_at(&self) -> Option<&DateTime<Utc>> {
+    self.payload.event.ended_at.as_ref()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_progress_json_deserialization_and_getters() {
+    let json_data = r#"{
+  "metadata": {
+    "message_id": "befa7b53-d79d-478f-86b9-120f112b044e",
+    "message_timestamp": "2025-05-08T00:10:29.579998945Z",
+    "message_type": "notification",
+    "subscription_type": "channel.hype_train.progress",
+    "subscription_version": "2"
+  },
+  "payload": {
+    "event": {
+      "id": "1b0AsbInCHZW2SQFQkCzqN07Ib2",
+      "broadcaster_user_id": "578762718",
+      "broadcaster_user_login": "fallenshadow",
+      "broadcaster_user_name": "fallenshadow",
+      "level": 2,
+      "total": 700,
+      "progress": 200,
+      "goal": 1000,
+      "top_contributions": [
+        { "user_id": "128831052", "user_login": "linkthedot", "user_name": "LinkTheDot", "type": "bits", "total": 500 },
+        { "user_id": "123", "user_login": "glorp", "user_name": "glorp", "type": "subscription", "total": 200 }
+      ],
+      "started_at": "2025-05-08T00:02:29.532137847Z",
+      "expires_at": "2025-05-08T00:15:29.532137847Z",
+      "type": "regular",
+      "is_shared_train": false
+    }
+  }
+}"#;
+
+    let message: TwitchHypeTrainMessage = serde_json::from_str(json_data).unwrap();
+
+    assert_eq!(
+      message.get_subscription_event_type(),
+      HypeTrainEventType::Progress
+    );
+    assert_eq!(message.get_hype_train_id(), "1b0AsbInCHZW2SQFQkCzqN07Ib2");
+    assert_eq!(message.get_streamer_twitch_id(), "578762718");
+    assert_eq!(message.get_level(), 2);
+    assert_eq!(message.get_total(), 700);
+    assert_eq!(
+      message.get_top_contributions(),
+      &[
+        HypeTrainContribution {
+          user_id: "128831052".into(),
+          contribution_type: HypeTrainContributionType::Bits,
+          total: 500,
+        },
+        HypeTrainContribution {
+          user_id: "123".into(),
+          contribution_type: HypeTrainContributionType::Subscription,
+          total: 200,
+        },
+      ]
+    );
+    assert_eq!(
+      message.get_expires_at(),
+      Some(
+        &"2025-05-08T00:15:29.532137847Z"
+          .parse::<DateTime<Utc>>()
+          .unwrap()
+      )
+    );
+    assert_eq!(message.get_ended_at(), None);
+  }
+
+  #[test]
+  fn test_end_json_deserialization_and_getters() {
+    let json_data = r#"{
+  "metadata": {
+    "message_id": "734fce04-be84-b905-89e5-54a23163c6ee",
+    "message_timestamp": "2025-05-08T00:20:29.579998945Z",
+    "message_type": "notification",
+    "subscription_type": "channel.hype_train.end",
+    "subscription_version": "2"
+  },
+  "payload": {
+    "event": {
+      "id": "1b0AsbInCHZW2SQFQkCzqN07Ib2",
+      "broadcaster_user_id": "578762718",
+      "broadcaster_user_login": "fallenshadow",
+      "broadcaster_user_name": "fallenshadow",
+      "level": 3,
+      "total": 1500,
+      "top_contributions": [],
+      "started_at": "2025-05-08T00:02:29.532137847Z",
+      "ended_at": "2025-05-08T00:20:29.532137847Z",
+      "cooldown_ends_at": "2025-05-08T01:20:29.532137847Z",
+      "type": "regular",
+      "is_shared_train": false
+    }
+  }
+}"#;
+
+    let message: TwitchHypeTrainMessage = serde_json::from_str(json_data).unwrap();
+    let expected_ended_at: DateTime<Utc> = "2025-05-08T00:20:29.532137847Z".parse().unwrap();
+
+    assert_eq!(
+      message.get_subscription_event_type(),
+      HypeTrainEventType::End
+    );
+    assert_eq!(message.get_level(), 3);
+    assert!(message.get_top_contributions().is_empty());
+    assert_eq!(message.get_ended_at(), Some(&expected_ended_at));
+  }
+}
@@ -1 +1,3 @@
+pub mod channel_point_redemption;
+pub mod hype_train;
 pub mod stream_status;